@@ -0,0 +1,220 @@
+use crate::{
+    app::App,
+    color::{
+        ChromaticityDiagram, Illuminant, RgbWorkingSpace, Xyz, brightest_rgb, spectral_locus, xyY,
+    },
+    context::FrameCtx,
+};
+
+use egui::{
+    Align2, Color32, CursorIcon, FontId, Pos2, Sense, Shape, Stroke, Ui, epaint::Mesh, pos2, vec2,
+};
+
+const DIAGRAM_MAX_WIDTH: f32 = 600.;
+const GRID_STEP: f32 = 0.1;
+/// Wavelengths in nanometers labeled along the spectral locus.
+const LABELED_WAVELENGTHS: [u32; 9] = [460, 480, 500, 520, 540, 560, 580, 600, 620];
+/// Luminance of colors picked from the diagram when the current color is black.
+const DEFAULT_PICK_LUMINANCE: f32 = 0.5;
+/// Distance in points within which a marker is described on hover.
+const MARKER_HOVER_DISTANCE: f32 = 6.;
+
+impl App {
+    pub fn chromaticity_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        self.windows.chromaticity.options(ui);
+        let window = &self.windows.chromaticity;
+        let diagram = window.diagram;
+        let ws = ctx.app.picker.sliders.rgb_working_space;
+
+        let (max_a, max_b) = diagram.max_coordinates();
+        let width = ui.available_width().min(DIAGRAM_MAX_WIDTH);
+        let (rect, response) =
+            ui.allocate_exact_size(vec2(width, width * max_b / max_a), Sense::click_and_drag());
+        let to_screen = |(a, b): (f32, f32)| {
+            pos2(
+                rect.left() + a / max_a * rect.width(),
+                rect.bottom() - b / max_b * rect.height(),
+            )
+        };
+        let from_screen = |pos: Pos2| {
+            (
+                (pos.x - rect.left()) / rect.width() * max_a,
+                (rect.bottom() - pos.y) / rect.height() * max_b,
+            )
+        };
+        let project = |xyy: xyY| to_screen(diagram.project(xyy.x(), xyy.y()));
+
+        let painter = ui.painter_at(rect);
+        let fg = ui.visuals().text_color();
+        let font = FontId::proportional(10.);
+
+        // Grid
+        let grid_stroke = Stroke::new(1., fg.gamma_multiply(0.15));
+        for i in 0..=(max_a / GRID_STEP).round() as u32 {
+            let a = i as f32 * GRID_STEP;
+            let top = to_screen((a, max_b));
+            let bottom = to_screen((a, 0.));
+            painter.line_segment([top, bottom], grid_stroke);
+            painter.text(
+                bottom,
+                Align2::LEFT_BOTTOM,
+                format!("{a:.1}"),
+                font.clone(),
+                fg,
+            );
+        }
+        for i in 0..=(max_b / GRID_STEP).round() as u32 {
+            let b = i as f32 * GRID_STEP;
+            let left = to_screen((0., b));
+            painter.line_segment([left, to_screen((max_a, b))], grid_stroke);
+            if i > 0 {
+                painter.text(left, Align2::LEFT_TOP, format!("{b:.1}"), font.clone(), fg);
+            }
+        }
+
+        // Spectral locus filled with a fan of triangles around the D65 white point
+        let locus = spectral_locus();
+        let white = to_screen(diagram.project(0.3127, 0.329));
+        let mut mesh = Mesh::default();
+        mesh.colored_vertex(white, Color32::WHITE);
+        for (_, x, y) in &locus {
+            mesh.colored_vertex(
+                to_screen(diagram.project(*x, *y)),
+                brightest_rgb(*x, *y, RgbWorkingSpace::SRGB).into(),
+            );
+        }
+        let n = locus.len() as u32;
+        for i in 1..n {
+            mesh.add_triangle(0, i, i + 1);
+        }
+        mesh.add_triangle(0, n, 1);
+        painter.add(Shape::mesh(mesh));
+        let outline: Vec<_> = locus
+            .iter()
+            .map(|(_, x, y)| to_screen(diagram.project(*x, *y)))
+            .collect();
+        painter.add(Shape::closed_line(outline, Stroke::new(1., fg)));
+        for (wavelength, x, y) in &locus {
+            if LABELED_WAVELENGTHS.contains(wavelength) {
+                let pos = to_screen(diagram.project(*x, *y));
+                let direction = (pos - white).normalized();
+                painter.text(
+                    pos + direction * 14.,
+                    Align2::CENTER_CENTER,
+                    wavelength.to_string(),
+                    font.clone(),
+                    fg,
+                );
+            }
+        }
+
+        // Working space gamuts
+        let triangle = |ws: RgbWorkingSpace| {
+            vec![
+                project(ws.reference_red_xyy()),
+                project(ws.reference_green_xyy()),
+                project(ws.reference_blue_xyy()),
+            ]
+        };
+        let mut markers = vec![];
+        if let Some(other) = window.compare_with {
+            let mut path = triangle(other);
+            path.push(path[0]);
+            painter.extend(Shape::dashed_line(&path, Stroke::new(1.5, fg), 6., 4.));
+        }
+        painter.add(Shape::closed_line(triangle(ws), Stroke::new(2., fg)));
+        let ws_white = project(xyY::from(ws.reference_illuminant().xyz()));
+        painter.circle_stroke(ws_white, 4., Stroke::new(1.5, fg));
+        markers.push((
+            ws_white,
+            format!("{} white point", ctx.app.settings.working_space_name()),
+        ));
+
+        if window.show_illuminants {
+            let mut illuminants: Vec<_> = Illuminant::ALL
+                .iter()
+                .map(|illuminant| (illuminant.as_ref().to_string(), illuminant.xyz()))
+                .collect();
+            illuminants.extend(
+                ctx.app
+                    .settings
+                    .custom_illuminants
+                    .iter()
+                    .map(|(name, white)| (format!("*{name}"), white.xyz())),
+            );
+            for (name, xyz) in illuminants {
+                let pos = project(xyY::from(xyz));
+                painter.circle_filled(pos, 2., fg);
+                markers.push((pos, name));
+            }
+        }
+
+        // Colors
+        if window.show_palette {
+            for color in ctx.app.palettes.current().palette.iter() {
+                if let Some(coordinates) = diagram.coordinates(color.xyz(ws)) {
+                    let pos = to_screen(coordinates);
+                    painter.circle(
+                        pos,
+                        4.,
+                        color.color32(),
+                        Stroke::new(1., color.contrast().color32()),
+                    );
+                    markers.push((pos, color.as_hex()));
+                }
+            }
+        }
+        let current = ctx.app.picker.current_color;
+        let current_xyz = current.xyz(ws);
+        if let Some(coordinates) = diagram.coordinates(current_xyz) {
+            painter.circle(
+                to_screen(coordinates),
+                7.,
+                current.color32(),
+                Stroke::new(2., current.contrast().color32()),
+            );
+        }
+
+        if let Some(pos) = response.hover_pos() {
+            let (a, b) = from_screen(pos);
+            let mut text = match diagram {
+                ChromaticityDiagram::Xy => format!("x {a:.4}, y {b:.4}"),
+                ChromaticityDiagram::UvPrime => format!("u' {a:.4}, v' {b:.4}"),
+            };
+            if let Some((_, name)) = markers
+                .iter()
+                .filter(|(marker, _)| marker.distance(pos) < MARKER_HOVER_DISTANCE)
+                .min_by(|(a, _), (b, _)| a.distance(pos).total_cmp(&b.distance(pos)))
+            {
+                text = format!("{name}\n{text}");
+            }
+            painter.text(
+                rect.right_top() + vec2(-4., 4.),
+                Align2::RIGHT_TOP,
+                text,
+                FontId::proportional(12.),
+                fg,
+            );
+        }
+
+        if (response.clicked() || response.dragged())
+            && let Some(pos) = response.interact_pointer_pos()
+        {
+            let (a, b) = from_screen(pos);
+            let (x, y) = diagram.unproject(a, b);
+            if x >= 0. && y > 0. && x + y <= 1. {
+                let luminance = if current_xyz.y() > 0.001 {
+                    current_xyz.y()
+                } else {
+                    DEFAULT_PICK_LUMINANCE
+                };
+                ctx.app
+                    .picker
+                    .set_cie_color(Xyz::from(xyY::new(x, y, luminance)));
+            }
+        }
+        response
+            .on_hover_cursor(CursorIcon::Crosshair)
+            .on_hover_text("Click to set the chromaticity of the current color");
+    }
+}
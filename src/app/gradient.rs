@@ -0,0 +1,191 @@
+use crate::{
+    app::{App, window::GRADIENT_PREVIEW_WIDTH},
+    color::InterpolationSpace,
+    context::FrameCtx,
+    error::append_global_error,
+    render::render_gradient,
+    ui::{
+        HALF_SPACE,
+        colorbox::{COLORBOX_PICK_TOOLTIP, ColorBox},
+        icon,
+    },
+};
+
+use egui::{
+    Button, Color32, ComboBox, CursorIcon, Grid, RichText, ScrollArea, Slider, TextEdit, Ui, vec2,
+};
+use std::{fs, path::PathBuf};
+
+const GRADIENT_PREVIEW_HEIGHT: f32 = 60.;
+const STOP_COLOR_SIZE: f32 = 24.;
+
+impl App {
+    pub fn gradient_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ScrollArea::vertical()
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Name");
+                    ui.text_edit_singleline(&mut ctx.app.gradient.name);
+                    ComboBox::from_label("interpolation")
+                        .selected_text(ctx.app.gradient.space.as_ref())
+                        .show_ui(ui, |ui| {
+                            for space in InterpolationSpace::ALL {
+                                ui.selectable_value(
+                                    &mut ctx.app.gradient.space,
+                                    space,
+                                    space.as_ref(),
+                                );
+                            }
+                        });
+                });
+                ui.add_space(HALF_SPACE);
+                self.gradient_preview(ctx, ui);
+                ui.add_space(HALF_SPACE);
+                self.gradient_stops(ctx, ui);
+                ui.separator();
+                self.gradient_export(ctx, ui);
+            });
+    }
+
+    fn gradient_preview(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        let gradient = ctx.app.gradient.to_gradient(GRADIENT_PREVIEW_WIDTH);
+        let size = vec2(ui.available_width() - HALF_SPACE, GRADIENT_PREVIEW_HEIGHT);
+        let tex_allocator = &mut ctx.tex_allocator();
+        let resp = render_gradient(
+            ui,
+            tex_allocator,
+            ctx.tex_manager,
+            &gradient,
+            size,
+            Some("Click to use the color under cursor"),
+            true,
+        );
+        if let Some(resp) = resp
+            && resp.clicked()
+            && let Some(pos) = resp.interact_pointer_pos()
+        {
+            let t = ((pos.x - resp.rect.left()) / resp.rect.width()).clamp(0., 1.);
+            let color = ctx.app.gradient.sample(t);
+            ctx.app.picker.set_cur_color(color);
+        }
+    }
+
+    fn gradient_stops(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        let current = ctx.app.picker.current_color;
+        let len = ctx.app.gradient.stops().len();
+        let mut remove = None;
+        Grid::new("gradient_stops").show(ui, |ui| {
+            for i in 0..len {
+                let stops = ctx.app.gradient.stops();
+                let color = stops[i].color;
+                // Keep stops ordered by only allowing them to move between their neighbours
+                let min = i.checked_sub(1).map(|i| stops[i].position).unwrap_or(0.);
+                let max = stops.get(i + 1).map(|s| s.position).unwrap_or(1.);
+
+                let cb = ColorBox::builder()
+                    .size((STOP_COLOR_SIZE, STOP_COLOR_SIZE))
+                    .color(color)
+                    .hover_help(COLORBOX_PICK_TOOLTIP)
+                    .build();
+                cb.display(ctx, ui);
+                ui.monospace(ctx.app.display_color(&color));
+                let Some(stop) = ctx.app.gradient.stop_mut(i) else {
+                    continue;
+                };
+                ui.add(
+                    Slider::new(&mut stop.position, min..=max)
+                        .clamping(egui::SliderClamping::Always)
+                        .text("position"),
+                );
+                if ui
+                    .button(icon::APPLY)
+                    .on_hover_text("Use current color")
+                    .on_hover_cursor(CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    stop.color = current;
+                }
+                if ui
+                    .add_enabled(len > 2, Button::new(icon::DELETE))
+                    .on_hover_text("Remove this stop")
+                    .clicked()
+                {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(i) = remove {
+            ctx.app.gradient.remove_stop(i);
+        }
+
+        ui.horizontal(|ui| {
+            if ui
+                .button(icon::ADD)
+                .on_hover_text("Add a stop in the widest gap")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.gradient.split_widest_gap(None);
+            }
+            if ui
+                .button("add current color")
+                .on_hover_text("Add the current color as a stop in the widest gap")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.gradient.split_widest_gap(Some(current));
+            }
+        });
+    }
+
+    fn gradient_export(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        let window = &mut self.windows.gradient;
+        window.export_options(ui);
+        let css = ctx.app.gradient.as_css_linear_gradient(window.css_angle);
+        ui.label(RichText::new(&css).monospace());
+        ui.horizontal(|ui| {
+            if ui
+                .button(icon::COPY)
+                .on_hover_text("Copy CSS to clipboard")
+                .on_hover_cursor(CursorIcon::Alias)
+                .clicked()
+            {
+                ui.ctx().copy_text(css);
+            }
+            if ui
+                .button("save as palette")
+                .on_hover_text("Save colors sampled at each step as a new palette")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                let palette = ctx.app.gradient.as_palette(window.num_of_steps as usize);
+                let name = palette.name.clone();
+                if !ctx.app.palettes.add(palette) {
+                    append_global_error(format!("Palette {name} already exists"));
+                }
+            }
+        });
+
+        ui.label("Export path:");
+        ui.add(TextEdit::singleline(&mut window.export_path));
+        match &window.export_status {
+            Ok(msg) => ui.colored_label(Color32::GREEN, msg),
+            Err(msg) => ui.colored_label(Color32::RED, msg),
+        };
+        if ui
+            .button("export ggr")
+            .on_hover_text("Export as GIMP gradient")
+            .on_hover_cursor(CursorIcon::PointingHand)
+            .clicked()
+        {
+            let gradient = &ctx.app.gradient;
+            let path = PathBuf::from(&window.export_path).join(format!("{}.ggr", gradient.name));
+            window.export_status = match fs::write(path, gradient.as_gimp_gradient()) {
+                Ok(()) => Ok("export succesful".to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+        }
+    }
+}
@@ -1,24 +1,31 @@
+mod chromaticity;
+mod gradient;
 mod palette;
 mod scheme;
 pub mod window;
 
 use crate::{
-    color::{Color, ColorHarmony, Gradient, PaletteFormat},
+    color::{Color, ColorHarmony, Gradient, PaletteFormat, TokenNotation, format_elapsed},
     context::{AppCtx, FrameCtx},
     error::{DisplayError, ERROR_STACK, append_global_error},
     keybinding::{KeyBindings, default_keybindings},
     render::{TextureManager, render_gradient},
     screen_size::ScreenSize,
     settings::{self, ColorDisplayFmtEnum},
+    swatch,
     ui::{
-        HALF_SPACE, SPACE,
+        HALF_SPACE, SPACE, color_name_label,
         colorbox::{COLORBOX_PICK_TOOLTIP, ColorBox},
         colors::*,
         dark_visuals, icon,
     },
     zoom_picker::ZoomPicker,
 };
-use window::{ExportWindow, HelpWindow, HuesWindow, SettingsWindow, ShadesWindow, TintsWindow};
+use window::{
+    ChromaticityWindow, DuplicatesWindow, ExportWindow, GeneratorWindow, GradientWindow,
+    HelpWindow, HistoryWindow, HuesWindow, MergeWindow, SettingsWindow, ShadesWindow,
+    SpectralWindow, TintsWindow, TonalScaleWindow,
+};
 
 use eframe::{CreationContext, Storage};
 use egui::{
@@ -38,6 +45,7 @@ pub static TEXTURE_MANAGER: LazyLock<RwLock<TextureManager>> =
     LazyLock::new(|| RwLock::new(TextureManager::default()));
 
 pub const CURRENT_COLOR_BOX_SIZE: f32 = 40.0;
+const PICK_HISTORY_BOX_SIZE: f32 = 16.0;
 
 #[derive(Copy, Clone, Debug, Deserialize, Serialize)]
 pub enum CentralPanelTab {
@@ -46,6 +54,8 @@ pub enum CentralPanelTab {
     Hues,
     Shades,
     Tints,
+    Gradient,
+    Chromaticity,
     Settings,
     Formats,
 }
@@ -55,9 +65,17 @@ pub struct Windows {
     pub settings: SettingsWindow,
     pub export: ExportWindow,
     pub help: HelpWindow,
+    pub history: HistoryWindow,
+    pub duplicates: DuplicatesWindow,
+    pub merge: MergeWindow,
+    pub tonal_scale: TonalScaleWindow,
+    pub generator: GeneratorWindow,
+    pub spectral: SpectralWindow,
     pub hues: HuesWindow,
     pub tints: TintsWindow,
     pub shades: ShadesWindow,
+    pub gradient: GradientWindow,
+    pub chromaticity: ChromaticityWindow,
 }
 
 pub struct App {
@@ -109,6 +127,11 @@ impl eframe::App for App {
 
             if ctx.egui.memory(|mem| mem.focused().is_none()) {
                 self.check_keys_pressed(&mut ctx);
+                // Wait with recording until drags and text edits are finished so that they end
+                // up as a single history entry
+                if !ctx.egui.input(|inp| inp.pointer.any_down()) {
+                    ctx.app.record_history();
+                }
             }
 
             // No need to repaint in wasm, there is no way to pick color from under the cursor anyway
@@ -132,6 +155,7 @@ impl eframe::App for App {
     fn save(&mut self, storage: &mut dyn Storage) {
         if let Some(ctx) = CONTEXT.get().and_then(|ctx| ctx.read().ok()) {
             ctx.save_palettes(storage);
+            ctx.save_pick_history();
             settings::save_global(&ctx.settings, storage);
         }
         storage.flush();
@@ -158,6 +182,8 @@ impl App {
             };
 
             ctx.app.load_palettes(context.storage);
+            ctx.app.load_pick_history();
+            ctx.app.load_display_profile();
 
             ctx.set_dark_theme();
         }
@@ -165,9 +191,7 @@ impl App {
         let mut fonts = egui::FontDefinitions::default();
         fonts.font_data.insert(
             "Firacode".to_string(),
-            Arc::new(egui::FontData::from_static(include_bytes!(
-                "../../assets/fonts/FiraCode/FiraCode-Regular.ttf"
-            ))),
+            Arc::new(egui::FontData::from_static(swatch::FONT)),
         );
         fonts
             .families
@@ -192,7 +216,11 @@ impl App {
 
     fn check_keys_pressed(&mut self, ctx: &mut FrameCtx) {
         for kb in KEYBINDINGS.iter() {
-            if ctx.egui.input(|inp| inp.key_pressed(kb.key())) {
+            // Bindings without modifiers keep triggering regardless of the held modifiers
+            if ctx.egui.input(|inp| {
+                (kb.modifiers().is_none() || inp.modifiers.matches_exact(kb.modifiers()))
+                    && inp.key_pressed(kb.key())
+            }) {
                 let f = kb.binding();
                 f(ctx)
             }
@@ -294,6 +322,20 @@ impl App {
                     ctx.app.central_panel_tab = CentralPanelTab::Tints;
                 }
             );
+            add_button_if!(
+                "gradient",
+                matches!(ctx.app.central_panel_tab, CentralPanelTab::Gradient),
+                {
+                    ctx.app.central_panel_tab = CentralPanelTab::Gradient;
+                }
+            );
+            add_button_if!(
+                "chromaticity",
+                matches!(ctx.app.central_panel_tab, CentralPanelTab::Chromaticity),
+                {
+                    ctx.app.central_panel_tab = CentralPanelTab::Chromaticity;
+                }
+            );
             add_button_if!(
                 "formats",
                 matches!(ctx.app.central_panel_tab, CentralPanelTab::Formats),
@@ -311,6 +353,14 @@ impl App {
                 {
                     self.windows.help.toggle_window();
                 }
+                if ui
+                    .button(icon::HISTORY)
+                    .on_hover_text("Show undo history")
+                    .on_hover_cursor(CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    self.windows.history.toggle_window();
+                }
                 let mut text = egui::RichText::new(icon::SETTINGS);
                 if matches!(ctx.app.central_panel_tab, CentralPanelTab::Settings) {
                     text = text.color(egui::Color32::YELLOW);
@@ -358,10 +408,18 @@ impl App {
                 CentralPanelTab::Hues => self.hues_window(ctx, ui),
                 CentralPanelTab::Shades => self.shades_window(ctx, ui),
                 CentralPanelTab::Tints => self.tints_window(ctx, ui),
+                CentralPanelTab::Gradient => self.gradient_ui(ctx, ui),
+                CentralPanelTab::Chromaticity => self.chromaticity_ui(ctx, ui),
                 CentralPanelTab::Settings => self.display_settings_stuff(ctx, ui),
                 CentralPanelTab::Formats => self.formats_ui(ctx, ui),
             });
         self.windows.help.display(ctx.egui);
+        self.windows.history.display(ctx);
+        self.windows.duplicates.display(ctx);
+        self.windows.merge.display(ctx);
+        self.windows.tonal_scale.display(ctx);
+        self.windows.generator.display(ctx);
+        self.windows.spectral.display(ctx);
     }
 
     fn picker_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
@@ -407,6 +465,13 @@ impl App {
                         .build();
                     cb.display(ctx, ui);
                     ui.label("Current");
+                    let name = ctx.app.picker.current_color.nearest_name();
+                    ui.label(RichText::new(color_name_label(&name)).italics())
+                        .on_hover_text(format!(
+                            "Nearest named color {} (ΔE {:.2})",
+                            name.color.as_hex(),
+                            name.delta_e
+                        ));
                     let hover_ui = |ui: &mut egui::Ui| {
                         ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
                         ui.label(
@@ -463,7 +528,7 @@ impl App {
                                 } else if let Some(color) = Color::from_hex(
                                     ctx.app.picker.hex_color.trim_start_matches('#'),
                                 ) {
-                                    ctx.app.picker.set_cur_color(color);
+                                    ctx.app.pick_color(color);
                                 } else {
                                     append_global_error(
                                         "The entered hex color is not valid".to_owned(),
@@ -482,6 +547,7 @@ impl App {
                         })
                     });
                 });
+                self.pick_history_ui(ctx, ui);
 
                 self.zoom_picker.display(ctx, ui);
             });
@@ -499,10 +565,72 @@ impl App {
             });
     }
 
+    fn pick_history_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        if ctx.app.pick_history.is_empty() {
+            return;
+        }
+        let now = crate::get_timestamp();
+        let mut remove = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 2.;
+            let picked: Vec<_> = ctx.app.pick_history.iter().copied().collect();
+            for (i, picked) in picked.into_iter().enumerate() {
+                let cb = ColorBox::builder()
+                    .size((PICK_HISTORY_BOX_SIZE, PICK_HISTORY_BOX_SIZE))
+                    .color(picked.color)
+                    .hover_help(format!(
+                        "Picked {}\n{COLORBOX_PICK_TOOLTIP}",
+                        format_elapsed(now.saturating_sub(picked.timestamp))
+                    ))
+                    .build();
+                if let Some(re) = cb.display(ctx, ui) {
+                    re.context_menu(|ui| {
+                        if ui.button("Promote to palette").clicked() {
+                            ctx.app.add_color(picked.color);
+                            ui.close();
+                        }
+                        if ui.button("Remove").clicked() {
+                            remove = Some(i);
+                            ui.close();
+                        }
+                    });
+                }
+            }
+            if ui
+                .button(icon::CLEAR)
+                .on_hover_text("Clear pick history")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                ctx.app.pick_history.clear();
+            }
+        });
+        if let Some(i) = remove {
+            ctx.app.pick_history.remove(i);
+        }
+    }
+
     fn sliders(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
-        ui.horizontal(|ui| {
+        ui.horizontal_wrapped(|ui| {
             let sliders = [
-                "HSV", "RGB", "CMYK", "HSL", "LUV", "LCH_UV", "LAB", "LCH_AB", "egui",
+                "HSV",
+                "RGB",
+                "CMYK",
+                "HSL",
+                "HWB",
+                "HSI",
+                "LUV",
+                "LCH_UV",
+                "LAB",
+                "LCH_AB",
+                "CAM16",
+                "CAM16_UCS",
+                "HCT",
+                "KELVIN",
+                "YCbCr",
+                "YUV",
+                "YIQ",
+                "egui",
             ];
             for (i, name) in sliders.into_iter().enumerate() {
                 if ui
@@ -518,11 +646,20 @@ impl App {
             1 => ctx.app.picker.rgb_sliders(ui),
             2 => ctx.app.picker.cmyk_sliders(ui),
             3 => ctx.app.picker.hsl_sliders(ui),
-            4 => ctx.app.picker.luv_sliders(ui),
-            5 => ctx.app.picker.lch_uv_sliders(ui),
-            6 => ctx.app.picker.lab_sliders(ui),
-            7 => ctx.app.picker.lch_ab_sliders(ui),
-            8 => ctx.app.picker.egui(ui),
+            4 => ctx.app.picker.hwb_sliders(ui),
+            5 => ctx.app.picker.hsi_sliders(ui),
+            6 => ctx.app.picker.luv_sliders(ui),
+            7 => ctx.app.picker.lch_uv_sliders(ui),
+            8 => ctx.app.picker.lab_sliders(ui),
+            9 => ctx.app.picker.lch_ab_sliders(ui),
+            10 => ctx.app.picker.cam16_sliders(ui),
+            11 => ctx.app.picker.cam16_ucs_sliders(ui),
+            12 => ctx.app.picker.hct_sliders(ui),
+            13 => ctx.app.picker.kelvin_sliders(ui),
+            14 => ctx.app.picker.ycbcr_sliders(ui),
+            15 => ctx.app.picker.yuv_sliders(ui),
+            16 => ctx.app.picker.yiq_sliders(ui),
+            17 => ctx.app.picker.egui(ui),
             _ => {}
         }
     }
@@ -572,16 +709,7 @@ impl App {
         ComboBox::from_label("Palette clipboard")
             .selected_text(app_ctx.settings.palette_clipboard_format.as_ref())
             .show_ui(ui, |ui| {
-                ui.selectable_value(
-                    &mut app_ctx.settings.palette_clipboard_format,
-                    PaletteFormat::Gimp,
-                    PaletteFormat::Gimp.as_ref(),
-                );
-                ui.selectable_value(
-                    &mut app_ctx.settings.palette_clipboard_format,
-                    PaletteFormat::HexList,
-                    PaletteFormat::HexList.as_ref(),
-                );
+                palette_format_selection_fill(&mut app_ctx.settings.palette_clipboard_format, ui);
                 for (name, fmt) in app_ctx.settings.saved_palette_formats.clone() {
                     ui.selectable_value(
                         &mut app_ctx.settings.palette_clipboard_format,
@@ -590,6 +718,7 @@ impl App {
                     );
                 }
             });
+        palette_notation_combobox(&mut app_ctx.settings.palette_clipboard_format, ui);
         ui.checkbox(
             &mut app_ctx.settings.auto_copy_picked_color,
             "Auto copy picked color",
@@ -625,6 +754,16 @@ fn color_format_selection_fill<'a, T: From<ColorDisplayFmtEnum> + PartialEq>(
         ColorDisplayFmtEnum::CssHsl.into(),
         ColorDisplayFmtEnum::CssHsl.as_ref(),
     );
+    ui.selectable_value(
+        fmt_ref,
+        ColorDisplayFmtEnum::CssDisplayP3.into(),
+        ColorDisplayFmtEnum::CssDisplayP3.as_ref(),
+    );
+    ui.selectable_value(
+        fmt_ref,
+        ColorDisplayFmtEnum::CssHwb.into(),
+        ColorDisplayFmtEnum::CssHwb.as_ref(),
+    );
     for custom in customs {
         ui.selectable_value(
             fmt_ref,
@@ -634,6 +773,33 @@ fn color_format_selection_fill<'a, T: From<ColorDisplayFmtEnum> + PartialEq>(
     }
 }
 
+/// Fill the values for a palette format selection with built-in formats.
+///
+/// Formats with a configurable notation keep the notation of the currently selected format.
+/// Returns `true` if any of the formats was clicked.
+fn palette_format_selection_fill(fmt_ref: &mut PaletteFormat, ui: &mut Ui) -> bool {
+    let notation = fmt_ref.notation().unwrap_or_default();
+    let mut clicked = false;
+    for fmt in PaletteFormat::builtin(notation) {
+        let name = fmt.as_ref().to_string();
+        clicked |= ui.selectable_value(fmt_ref, fmt, name).clicked();
+    }
+    clicked
+}
+
+/// Displays a combobox for selecting the color notation if the format supports it.
+fn palette_notation_combobox(fmt_ref: &mut PaletteFormat, ui: &mut Ui) {
+    if let Some(notation) = fmt_ref.notation_mut() {
+        ComboBox::from_label("Color notation")
+            .selected_text(notation.as_ref())
+            .show_ui(ui, |ui| {
+                for n in TokenNotation::ALL {
+                    ui.selectable_value(notation, n, n.as_ref());
+                }
+            });
+    }
+}
+
 pub fn custom_popup_below_widget<R>(
     ctx: &egui::Context,
     popup_id: Id,
@@ -1,6 +1,6 @@
 use crate::{
     app::App,
-    color::NamedPalette,
+    color::{NamedPalette, PaletteEntry, PaletteSort, SlotMapping},
     context::FrameCtx,
     ui::{
         SPACE,
@@ -45,6 +45,34 @@ impl App {
                 {
                     ctx.app.palettes.append_empty();
                 }
+                if ui
+                    .button("merge")
+                    .on_hover_text("Merge palettes into a new one")
+                    .clicked()
+                {
+                    self.windows.merge.toggle_window();
+                }
+                if ui
+                    .button("tonal scale")
+                    .on_hover_text("Create a palette from the tonal scale of the current color")
+                    .clicked()
+                {
+                    self.windows.tonal_scale.toggle_window();
+                }
+                if ui
+                    .button("generate")
+                    .on_hover_text("Generate a palette of distinct random colors")
+                    .clicked()
+                {
+                    self.windows.generator.toggle_window();
+                }
+                if ui
+                    .button("spectral")
+                    .on_hover_text("Load a palette from measured reflectance or emission spectra")
+                    .clicked()
+                {
+                    self.windows.spectral.toggle_window();
+                }
             });
             ui.add_space(SPACE);
 
@@ -106,7 +134,7 @@ impl App {
         action: &mut Option<UiAction>,
     ) {
         ui.horizontal(|ui| {
-            self.display_palette_buttons(palette, ctx, ui);
+            self.display_palette_buttons(palette, index, ctx, ui);
             let mut label = RichText::new(&palette.name);
             if active {
                 label = label.strong().heading();
@@ -143,6 +171,7 @@ impl App {
     fn display_palette_buttons(
         &mut self,
         palette: &NamedPalette,
+        index: usize,
         ctx: &mut FrameCtx<'_>,
         ui: &mut Ui,
     ) -> egui::InnerResponse<()> {
@@ -162,6 +191,7 @@ impl App {
                 .clicked()
             {
                 self.windows.export.show = true;
+                self.windows.export.slot_mapping = SlotMapping::auto(palette);
                 self.windows.export.export_palette = Some(palette.clone());
             }
             if ui
@@ -176,6 +206,43 @@ impl App {
                     ctx.app.settings.illuminant,
                 ));
             }
+            ui.menu_button(icon::SORT, |ui| {
+                ui.label("Sort by");
+                for sort in PaletteSort::ALL {
+                    if ui.button(sort.as_ref()).clicked() {
+                        ctx.app.palettes.palettes[index].palette.sort_by(sort);
+                        ui.close();
+                    }
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Group similar")
+                        .on_hover_text(
+                            "Move colors closer than the ΔE threshold next to each other",
+                        )
+                        .clicked()
+                    {
+                        ctx.app.palettes.palettes[index]
+                            .palette
+                            .group_similar(ctx.app.palettes_group_threshold);
+                        ui.close();
+                    }
+                    ui.add(
+                        egui::DragValue::new(&mut ctx.app.palettes_group_threshold)
+                            .range(0.0..=100.)
+                            .speed(0.1)
+                            .prefix("ΔE "),
+                    );
+                });
+                ui.separator();
+                if ui.button("Find near-duplicates").clicked() {
+                    self.windows.duplicates.open(index);
+                    ui.close();
+                }
+            })
+            .response
+            .on_hover_text("Sort, group or find duplicate colors");
             if ui
                 .button(icon::DELETE)
                 .on_hover_text("Delete this palette")
@@ -199,7 +266,8 @@ impl App {
             .show(ui, |ui| {
                 let mut color_src_row = None;
                 let mut color_dst_row = None;
-                for (i, color) in palette.palette.iter().enumerate() {
+                for (i, entry) in palette.palette.entries().enumerate() {
+                    let color = &entry.color;
                     let resp = drop_target(ui, true, |ui| {
                         let color_id = Id::new(&palette.name).with(i);
                         let is_current = &ctx.app.picker.current_color == color;
@@ -261,6 +329,11 @@ impl App {
                                     }
                                 }
                                 re.context_menu(|ui| {
+                                    if let Some(entry) =
+                                        ctx.app.palettes.palettes[index].palette.get_mut(i)
+                                    {
+                                        entry_edit_ui(entry, ui);
+                                    }
                                     if ui.button("Delete").clicked() {
                                         *action = Some(UiAction::DeleteColor {
                                             pal_idx: index,
@@ -269,6 +342,12 @@ impl App {
                                     }
                                 });
                             }
+                            if let Some(name) = entry.name() {
+                                let re = ui.label(RichText::new(name).small());
+                                if let Some(note) = entry.note() {
+                                    re.on_hover_text(note);
+                                }
+                            }
                         });
                         if ctx.egui.is_being_dragged(color_id) {
                             color_src_row = Some(i);
@@ -292,3 +371,21 @@ impl App {
             })
     }
 }
+
+fn entry_edit_ui(entry: &mut PaletteEntry, ui: &mut Ui) {
+    egui::Grid::new("palette_entry_edit").show(ui, |ui| {
+        ui.label("Name");
+        ui.text_edit_singleline(entry.name.get_or_insert_with(String::new));
+        ui.end_row();
+        ui.label("Note");
+        ui.text_edit_multiline(entry.note.get_or_insert_with(String::new));
+        ui.end_row();
+    });
+    if entry.name().is_none() {
+        entry.name = None;
+    }
+    if entry.note().is_none() {
+        entry.note = None;
+    }
+    ui.separator();
+}
@@ -1,13 +1,16 @@
 use crate::{
     app::{App, ColorHarmony, FrameCtx},
-    color::{Color, Gradient},
+    color::{Color, Gradient, HarmonySpace, NamedPalette, Palette, ToneKind},
+    error::append_global_error,
     ui::{
+        SPACE,
         colorbox::{COLORBOX_PICK_TOOLTIP, ColorBox},
+        icon,
         layout::HarmonyLayout,
     },
 };
 
-use egui::{ComboBox, Grid, Ui, Vec2, vec2};
+use egui::{ComboBox, CursorIcon, DragValue, Grid, Ui, Vec2, vec2};
 
 macro_rules! scheme_window_impl {
     ($title:literal, $self:ident, $ctx:ident, $ui:ident, $win:ident, $size_field:ident, $colors:expr) => {{
@@ -17,6 +20,12 @@ macro_rules! scheme_window_impl {
                 $self.windows.$win.sliders(ui);
 
                 let colors = $colors;
+                let name = format!(
+                    "{} of {}",
+                    $title.to_lowercase(),
+                    $ctx.app.picker.current_color.as_hex()
+                );
+                save_palette_button(name, &colors, $ctx, ui);
                 let size = vec2(
                     $self.windows.$win.$size_field,
                     $self.windows.$win.$size_field,
@@ -36,6 +45,97 @@ macro_rules! scheme_window_impl {
     }};
 }
 
+/// Displays a button that saves `colors` as a new palette called `name`.
+fn save_palette_button(name: String, colors: &[Color], ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+    if save_palette_clicked(&name, ui) {
+        save_palette(name, colors, ctx);
+    }
+}
+
+/// Displays a button for saving colors as a new palette called `name`, returns `true` if it was
+/// clicked.
+fn save_palette_clicked(name: &str, ui: &mut Ui) -> bool {
+    ui.button("save as palette")
+        .on_hover_text(format!("Save all colors as a new palette \"{name}\""))
+        .on_hover_cursor(CursorIcon::PointingHand)
+        .clicked()
+}
+
+fn save_palette(name: String, colors: &[Color], ctx: &mut FrameCtx<'_>) {
+    let mut palette = Palette::default();
+    for color in colors {
+        palette.add(*color);
+    }
+    let palette = NamedPalette {
+        name: name.clone(),
+        palette,
+    };
+    if !ctx.app.palettes.add(palette) {
+        append_global_error(format!("Palette {name} already exists"));
+    }
+}
+
+/// Displays each list of colors as a column of color boxes with a header for comparison. Each
+/// column can be saved as a palette with the name next to its header.
+fn variant_columns(
+    id: &str,
+    columns: &[(&str, String, Vec<Color>)],
+    size: Vec2,
+    ctx: &mut FrameCtx<'_>,
+    ui: &mut Ui,
+) {
+    Grid::new(id).spacing((SPACE, 2.)).show(ui, |ui| {
+        for (header, _, _) in columns {
+            ui.label(*header);
+        }
+        ui.end_row();
+        for (_, name, colors) in columns {
+            save_palette_button(name.clone(), colors, ctx, ui);
+        }
+        ui.end_row();
+        let rows = columns
+            .iter()
+            .map(|(_, _, c)| c.len())
+            .max()
+            .unwrap_or_default();
+        for i in 0..rows {
+            for (_, _, colors) in columns {
+                match colors.get(i) {
+                    Some(color) => {
+                        let cb = ColorBox::builder()
+                            .hover_help(COLORBOX_PICK_TOOLTIP)
+                            .label(true)
+                            .size(size)
+                            .color(*color)
+                            .build();
+                        ui.horizontal(|ui| {
+                            cb.display(ctx, ui);
+                        });
+                    }
+                    None => {
+                        ui.label("");
+                    }
+                }
+            }
+            ui.end_row();
+        }
+    });
+}
+
+/// Returns the current color followed by the colors of the selected harmony.
+fn harmony_colors(ctx: &FrameCtx<'_>) -> Vec<Color> {
+    let color = ctx.app.picker.current_color;
+    let settings = &ctx.app.settings;
+    let mut colors = vec![color];
+    colors.extend(color.harmony(
+        settings.harmony,
+        settings.harmony_space,
+        settings.harmony_analogous_spread,
+        &settings.harmony_custom_angles,
+    ));
+    colors
+}
+
 fn cb(
     color: Color,
     display_labels: bool,
@@ -76,33 +176,67 @@ impl App {
     }
 
     pub fn tints_window(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
-        scheme_window_impl!(
-            "Tints",
-            self,
-            ctx,
-            ui,
-            tints,
-            tint_color_size,
-            ctx.app
-                .picker
-                .current_color
-                .tints(self.windows.tints.num_of_tints)
-        );
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                let window = &mut self.windows.tints;
+                window.sliders(ui);
+                let color = ctx.app.picker.current_color;
+                let total = window.num_of_tints;
+                let hex = color.as_hex();
+                let space = window.perceptual_space.as_ref();
+                let columns = [
+                    ("RGB", format!("RGB tints of {hex}"), color.tints(total)),
+                    (
+                        space,
+                        format!("{space} tints of {hex}"),
+                        color.perceptual_variants(
+                            ToneKind::Tint,
+                            total,
+                            window.perceptual_space,
+                            window.tone_mode,
+                        ),
+                    ),
+                ];
+                let size = vec2(window.tint_color_size, window.tint_color_size);
+                variant_columns("tints", &columns, size, ctx, ui);
+            });
     }
 
     pub fn shades_window(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
-        scheme_window_impl!(
-            "Shades",
-            self,
-            ctx,
-            ui,
-            shades,
-            shade_color_size,
-            ctx.app
-                .picker
-                .current_color
-                .shades(self.windows.shades.num_of_shades)
-        );
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                let window = &mut self.windows.shades;
+                window.sliders(ui);
+                let color = ctx.app.picker.current_color;
+                let total = window.num_of_shades;
+                let perceptual = |kind| {
+                    color.perceptual_variants(
+                        kind,
+                        total,
+                        window.perceptual_space,
+                        window.tone_mode,
+                    )
+                };
+                let hex = color.as_hex();
+                let space = window.perceptual_space.as_ref();
+                let columns = [
+                    ("RGB", format!("RGB shades of {hex}"), color.shades(total)),
+                    (
+                        space,
+                        format!("{space} shades of {hex}"),
+                        perceptual(ToneKind::Shade),
+                    ),
+                    (
+                        "Tones",
+                        format!("{space} tones of {hex}"),
+                        perceptual(ToneKind::Tone),
+                    ),
+                ];
+                let size = vec2(window.shade_color_size, window.shade_color_size);
+                variant_columns("shades", &columns, size, ctx, ui);
+            });
     }
 
     pub fn harmonies_ctl_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
@@ -115,160 +249,60 @@ impl App {
                     .range(20.0..=ui.available_width() / 4.),
             );
             ui.checkbox(&mut ctx.app.settings.harmony_display_color_label, "labels");
+            let name = format!(
+                "{} of {}",
+                ctx.app.settings.harmony.as_ref(),
+                ctx.app.picker.current_color.as_hex()
+            );
+            // Harmonies are only computed for saving when the button is clicked
+            if save_palette_clicked(&name, ui) {
+                let colors = harmony_colors(ctx);
+                save_palette(name, &colors, ctx);
+            }
         });
+        self.harmony_angles_ui(ctx, ui);
     }
 
     pub fn display_harmonies(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
         let color_size = ctx.app.settings.harmony_color_size;
-        let gradient_size = vec2(color_size * 4., color_size * 2.);
-        let dbl_width = vec2(color_size * 2., color_size);
         let dbl_width_third_height = vec2(color_size * 2., color_size * 2. / 3.);
         let dbl_height_third_width = vec2(color_size * 2. / 3., color_size * 2.);
-        let dbl_height = vec2(color_size, color_size * 2.);
         let half_height = vec2(color_size, color_size * 1. / 2.);
         let half_width = vec2(color_size * 1. / 2., color_size);
 
         let display_labels = ctx.app.settings.harmony_display_color_label;
-        let color = ctx.app.picker.current_color;
-        match ctx.app.settings.harmony {
-            ColorHarmony::Complementary => {
-                let compl = color.complementary();
-                Grid::new("complementary").spacing((0., 0.)).show(ui, |ui| {
-                    match ctx.app.settings.harmony_layout {
-                        HarmonyLayout::Square => {
-                            cb(color, display_labels, None, ctx, ui);
-                            cb(compl, display_labels, None, ctx, ui);
-                        }
-                        HarmonyLayout::Stacked => {
-                            cb(color, display_labels, Some(dbl_width), ctx, ui);
-                            ui.end_row();
-                            cb(compl, display_labels, Some(dbl_width), ctx, ui);
-                        }
-                        HarmonyLayout::Line => {
-                            cb(color, display_labels, Some(dbl_height), ctx, ui);
-                            cb(compl, display_labels, Some(dbl_height), ctx, ui);
-                        }
-                        HarmonyLayout::Gradient => {
-                            let gradient = Gradient::from_colors([color, compl]);
-                            ui.vertical(|ui| {
-                                self.gradient_box(ctx, &gradient, gradient_size, ui, false);
-                            });
-                        }
-                    }
-                    ui.end_row();
-                });
-            }
-            ColorHarmony::Triadic => {
-                let tri = color.triadic();
-                Grid::new("triadic").spacing((0., 0.)).show(ui, |ui| {
-                    let c1 = tri.0;
-                    let c2 = tri.1;
-                    self.three_colors_in_layout(
-                        color,
-                        c1,
-                        c2,
-                        dbl_width_third_height,
-                        dbl_height_third_width,
-                        display_labels,
-                        ctx,
-                        ui,
-                    );
-                });
-            }
-            ColorHarmony::Tetradic => {
-                let tetr = color.tetradic();
-                Grid::new("tetradic").spacing((0., 0.)).show(ui, |ui| {
-                    let c1 = tetr.0;
-                    let c2 = tetr.1;
-                    let c3 = tetr.2;
-                    self.four_colors_in_layout(
-                        color,
-                        c1,
-                        c2,
-                        c3,
-                        half_height,
-                        half_width,
-                        display_labels,
-                        ctx,
-                        ui,
-                    );
-                });
-            }
-            ColorHarmony::Analogous => {
-                let an = color.analogous();
-                Grid::new("analogous").spacing((0., 0.)).show(ui, |ui| {
-                    let c1 = an.0;
-                    let c2 = an.1;
-                    self.three_colors_in_layout(
-                        color,
-                        c1,
-                        c2,
-                        dbl_width_third_height,
-                        dbl_height_third_width,
-                        display_labels,
-                        ctx,
-                        ui,
-                    );
-                });
-            }
-            ColorHarmony::SplitComplementary => {
-                let sc = color.split_complementary();
-                Grid::new("split-complementary")
-                    .spacing((0., 0.))
-                    .show(ui, |ui| {
-                        let c1 = sc.0;
-                        let c2 = sc.1;
-                        self.three_colors_in_layout(
-                            color,
-                            c1,
-                            c2,
-                            dbl_width_third_height,
-                            dbl_height_third_width,
-                            display_labels,
-                            ctx,
-                            ui,
-                        );
-                    });
-            }
-            ColorHarmony::Square => {
-                let s = color.square();
-                Grid::new("square").spacing((0., 0.)).show(ui, |ui| {
-                    let c1 = s.0;
-                    let c2 = s.1;
-                    let c3 = s.2;
-                    self.four_colors_in_layout(
-                        color,
-                        c1,
-                        c2,
-                        c3,
-                        half_height,
-                        half_width,
-                        display_labels,
-                        ctx,
-                        ui,
-                    );
-                });
-            }
-            ColorHarmony::Monochromatic => {
-                let mono = color.monochromatic();
-                Grid::new("monochromatic").spacing((0., 0.)).show(ui, |ui| {
-                    let c1 = mono.0;
-                    let c2 = mono.1;
-                    let c3 = mono.2;
-                    self.four_colors_in_layout(
-                        color,
-                        c1,
-                        c2,
-                        c3,
-                        half_height,
-                        half_width,
-                        display_labels,
-                        ctx,
-                        ui,
-                    );
-                });
-            }
-        }
+        let harmony = ctx.app.settings.harmony;
+        let colors = harmony_colors(ctx);
+        Grid::new(harmony.as_ref())
+            .spacing((0., 0.))
+            .show(ui, |ui| match colors[..] {
+                [c1] => {
+                    cb(c1, display_labels, None, ctx, ui);
+                }
+                [c1, c2] => self.two_colors_in_layout(c1, c2, display_labels, ctx, ui),
+                [c1, c2, c3] => self.three_colors_in_layout(
+                    c1,
+                    c2,
+                    c3,
+                    dbl_width_third_height,
+                    dbl_height_third_width,
+                    display_labels,
+                    ctx,
+                    ui,
+                ),
+                [c1, c2, c3, c4] => self.four_colors_in_layout(
+                    c1,
+                    c2,
+                    c3,
+                    c4,
+                    half_height,
+                    half_width,
+                    display_labels,
+                    ctx,
+                    ui,
+                ),
+                _ => self.colors_in_layout(&colors, display_labels, ctx, ui),
+            });
     }
 
     fn harmony_layout_combobox(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
@@ -303,38 +337,100 @@ impl App {
         ComboBox::from_id_salt("Harmony")
             .selected_text(harmony.as_ref())
             .show_ui(ui, |ui| {
-                ui.selectable_value(
-                    harmony,
-                    ColorHarmony::Complementary,
-                    ColorHarmony::Complementary.as_ref(),
-                );
-                ui.selectable_value(
-                    harmony,
-                    ColorHarmony::Triadic,
-                    ColorHarmony::Triadic.as_ref(),
-                );
-                ui.selectable_value(
-                    harmony,
-                    ColorHarmony::Tetradic,
-                    ColorHarmony::Tetradic.as_ref(),
-                );
-                ui.selectable_value(
-                    harmony,
-                    ColorHarmony::Analogous,
-                    ColorHarmony::Analogous.as_ref(),
-                );
-                ui.selectable_value(
-                    harmony,
-                    ColorHarmony::SplitComplementary,
-                    ColorHarmony::SplitComplementary.as_ref(),
-                );
-                ui.selectable_value(harmony, ColorHarmony::Square, ColorHarmony::Square.as_ref());
-                ui.selectable_value(
-                    harmony,
-                    ColorHarmony::Monochromatic,
-                    ColorHarmony::Monochromatic.as_ref(),
-                );
+                for h in ColorHarmony::ALL {
+                    ui.selectable_value(harmony, h, h.as_ref());
+                }
             });
+        let space = &mut ctx.app.settings.harmony_space;
+        ComboBox::from_id_salt("Harmony space")
+            .selected_text(space.as_ref())
+            .show_ui(ui, |ui| {
+                for s in HarmonySpace::ALL {
+                    ui.selectable_value(space, s, s.as_ref());
+                }
+            })
+            .response
+            .on_hover_text("Color space in which the hue is rotated");
+    }
+
+    fn harmony_angles_ui(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        let settings = &mut ctx.app.settings;
+        match settings.harmony {
+            ColorHarmony::Analogous => {
+                ui.horizontal(|ui| {
+                    ui.label("Spread");
+                    ui.add(
+                        DragValue::new(&mut settings.harmony_analogous_spread)
+                            .range(1.0..=180.)
+                            .suffix("°"),
+                    );
+                });
+            }
+            ColorHarmony::Custom => {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Angles");
+                    let mut remove = None;
+                    for (i, angle) in settings.harmony_custom_angles.iter_mut().enumerate() {
+                        ui.add(DragValue::new(angle).range(0.0..=360.).suffix("°"));
+                        if ui
+                            .small_button(icon::DELETE)
+                            .on_hover_text("Remove this angle")
+                            .clicked()
+                        {
+                            remove = Some(i);
+                        }
+                    }
+                    if let Some(i) = remove {
+                        settings.harmony_custom_angles.remove(i);
+                    }
+                    if ui
+                        .button(icon::ADD)
+                        .on_hover_text("Add a hue rotation")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        settings.harmony_custom_angles.push(180.);
+                    }
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn two_colors_in_layout(
+        &mut self,
+        c1: Color,
+        c2: Color,
+        display_labels: bool,
+        ctx: &mut FrameCtx<'_>,
+        ui: &mut Ui,
+    ) {
+        let color_size = ctx.app.settings.harmony_color_size;
+        let dbl_width = vec2(color_size * 2., color_size);
+        let dbl_height = vec2(color_size, color_size * 2.);
+        let gradient_size = vec2(color_size * 4., color_size * 2.);
+        match ctx.app.settings.harmony_layout {
+            HarmonyLayout::Square => {
+                cb(c1, display_labels, None, ctx, ui);
+                cb(c2, display_labels, None, ctx, ui);
+            }
+            HarmonyLayout::Stacked => {
+                cb(c1, display_labels, Some(dbl_width), ctx, ui);
+                ui.end_row();
+                cb(c2, display_labels, Some(dbl_width), ctx, ui);
+            }
+            HarmonyLayout::Line => {
+                cb(c1, display_labels, Some(dbl_height), ctx, ui);
+                cb(c2, display_labels, Some(dbl_height), ctx, ui);
+            }
+            HarmonyLayout::Gradient => {
+                let gradient = Gradient::from_colors([c1, c2]);
+                ui.vertical(|ui| {
+                    self.gradient_box(ctx, &gradient, gradient_size, ui, false);
+                });
+            }
+        }
+        ui.end_row();
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -436,4 +532,50 @@ impl App {
             }
         }
     }
+
+    /// Lays out any number of colors, used by custom harmonies with more than four colors.
+    fn colors_in_layout(
+        &mut self,
+        colors: &[Color],
+        display_labels: bool,
+        ctx: &mut FrameCtx<'_>,
+        ui: &mut Ui,
+    ) {
+        let color_size = ctx.app.settings.harmony_color_size;
+        let gradient_size = vec2(color_size * 4., color_size * 2.);
+        let n = colors.len() as f32;
+        match ctx.app.settings.harmony_layout {
+            HarmonyLayout::Square => {
+                let per_row = n.sqrt().ceil() as usize;
+                for row in colors.chunks(per_row) {
+                    ui.scope(|ui| {
+                        ui.spacing_mut().item_spacing = (0., 0.).into();
+                        for color in row {
+                            cb(*color, display_labels, None, ctx, ui);
+                        }
+                    });
+                    ui.end_row();
+                }
+            }
+            HarmonyLayout::Stacked => {
+                let size = vec2(color_size * 2., color_size * 2. / n);
+                for color in colors {
+                    cb(*color, display_labels, Some(size), ctx, ui);
+                    ui.end_row();
+                }
+            }
+            HarmonyLayout::Line => {
+                let size = vec2(color_size * 2. / n, color_size * 2.);
+                for color in colors {
+                    cb(*color, display_labels, Some(size), ctx, ui);
+                }
+            }
+            HarmonyLayout::Gradient => {
+                ui.vertical(|ui| {
+                    let gradient = Gradient::from_colors(colors.iter().copied());
+                    self.gradient_box(ctx, &gradient, gradient_size, ui, false);
+                });
+            }
+        }
+    }
 }
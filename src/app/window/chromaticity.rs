@@ -0,0 +1,47 @@
+use crate::color::{ChromaticityDiagram, RgbWorkingSpace};
+
+use egui::{ComboBox, Ui};
+
+#[derive(Debug)]
+pub struct ChromaticityWindow {
+    pub diagram: ChromaticityDiagram,
+    /// Working space drawn next to the selected one for comparison
+    pub compare_with: Option<RgbWorkingSpace>,
+    pub show_palette: bool,
+    pub show_illuminants: bool,
+}
+
+impl Default for ChromaticityWindow {
+    fn default() -> Self {
+        Self {
+            diagram: ChromaticityDiagram::default(),
+            compare_with: None,
+            show_palette: true,
+            show_illuminants: true,
+        }
+    }
+}
+
+impl ChromaticityWindow {
+    pub fn options(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ComboBox::from_id_salt("chromaticity_diagram")
+                .selected_text(self.diagram.as_ref())
+                .show_ui(ui, |ui| {
+                    for diagram in ChromaticityDiagram::ALL {
+                        ui.selectable_value(&mut self.diagram, diagram, diagram.as_ref());
+                    }
+                });
+            ComboBox::from_label("compare with")
+                .selected_text(self.compare_with.as_ref().map_or("none", |ws| ws.as_ref()))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.compare_with, None, "none");
+                    for ws in RgbWorkingSpace::ALL {
+                        ui.selectable_value(&mut self.compare_with, Some(ws), ws.as_ref());
+                    }
+                });
+            ui.checkbox(&mut self.show_palette, "palette");
+            ui.checkbox(&mut self.show_illuminants, "illuminants");
+        });
+    }
+}
@@ -21,7 +21,7 @@ impl CustomFormatsWindow {
         ui: &mut egui::Ui,
         preview_color: Color,
     ) {
-        ui.label("Help: {r} {g} {b} {r255} {g255} ...");
+        ui.label("Help: {r} {g} {b} {r255} {g255} {hwb_h360} {ycbcr709_y8l} ...");
         let mut replace = false;
         let keys: Vec<String> = settings.saved_color_formats.keys().cloned().collect();
         let enter_pressed = ui.input(|inp| inp.key_pressed(Key::Enter));
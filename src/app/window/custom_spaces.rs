@@ -0,0 +1,208 @@
+use crate::{
+    color::{CCT_RANGE, CustomWorkingSpace, TransferFunction, WhitePoint},
+    settings::Settings,
+    ui::icon,
+};
+
+use egui::{Button, CollapsingHeader, Color32, DragValue, Grid, TextBuffer, Ui};
+use std::collections::HashMap;
+
+const XY_SPEED: f64 = 0.0001;
+
+#[derive(Debug)]
+pub struct CustomSpacesWindow {
+    pub new_space_name: String,
+    pub new_illuminant_name: String,
+    /// Color temperature used to set white points
+    pub cct: f32,
+    /// Name of the working space whose last edit was rejected for being invalid
+    rejected: Option<String>,
+}
+
+impl Default for CustomSpacesWindow {
+    fn default() -> Self {
+        Self {
+            new_space_name: String::new(),
+            new_illuminant_name: String::new(),
+            cct: 6500.,
+            rejected: None,
+        }
+    }
+}
+
+impl CustomSpacesWindow {
+    pub fn display(&mut self, settings: &mut Settings, ui: &mut Ui) {
+        CollapsingHeader::new("Custom working spaces").show(ui, |ui| {
+            self.working_spaces(settings, ui);
+        });
+        CollapsingHeader::new("Custom white points").show(ui, |ui| {
+            self.white_points(settings, ui);
+        });
+    }
+
+    fn working_spaces(&mut self, settings: &mut Settings, ui: &mut Ui) {
+        let mut names: Vec<_> = settings.custom_working_spaces.keys().cloned().collect();
+        names.sort();
+        let mut remove = None;
+        for name in names {
+            let Some(ws) = settings.custom_working_spaces.get_mut(&name) else {
+                continue;
+            };
+            let mut edited = *ws;
+            CollapsingHeader::new(&name).show(ui, |ui| {
+                Grid::new(format!("custom_ws_{name}")).show(ui, |ui| {
+                    for (label, (x, y)) in [
+                        ("Red", &mut edited.red),
+                        ("Green", &mut edited.green),
+                        ("Blue", &mut edited.blue),
+                    ] {
+                        ui.label(label);
+                        xy_ui(x, y, ui);
+                        ui.end_row();
+                    }
+                    ui.label("White");
+                    white_point_ui(&mut edited.white, &mut self.cct, ui);
+                    ui.end_row();
+                    ui.label("Transfer");
+                    transfer_ui(&mut edited.transfer, ui);
+                    ui.end_row();
+                });
+                if self.rejected.as_ref() == Some(&name) {
+                    ui.colored_label(Color32::RED, "Primaries must not lie on a line");
+                }
+                if ui.button(icon::DELETE).on_hover_text("Remove").clicked() {
+                    remove = Some(name.clone());
+                }
+            });
+            if edited != *ws {
+                if edited.is_valid() {
+                    *ws = edited;
+                    self.rejected = None;
+                } else {
+                    self.rejected = Some(name);
+                }
+            }
+        }
+        if let Some(name) = remove {
+            settings.custom_working_spaces.remove(&name);
+        }
+        // Keep the selected working space in sync with its definition
+        settings.sync_custom_spaces();
+
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.new_space_name);
+            let valid = name_valid(&self.new_space_name, &settings.custom_working_spaces);
+            if ui
+                .add_enabled(valid, Button::new(icon::ADD))
+                .on_hover_text("Add a working space with sRGB primaries")
+                .clicked()
+            {
+                settings
+                    .custom_working_spaces
+                    .insert(self.new_space_name.take(), CustomWorkingSpace::default());
+            }
+        });
+    }
+
+    fn white_points(&mut self, settings: &mut Settings, ui: &mut Ui) {
+        let mut names: Vec<_> = settings.custom_illuminants.keys().cloned().collect();
+        names.sort();
+        let mut remove = None;
+        Grid::new("custom_white_points").show(ui, |ui| {
+            for name in names {
+                let Some(white) = settings.custom_illuminants.get_mut(&name) else {
+                    continue;
+                };
+                if ui.button(icon::DELETE).on_hover_text("Remove").clicked() {
+                    remove = Some(name.clone());
+                }
+                ui.label(&name);
+                white_point_ui(white, &mut self.cct, ui);
+                ui.end_row();
+            }
+        });
+        if let Some(name) = remove {
+            settings.custom_illuminants.remove(&name);
+        }
+        settings.sync_custom_spaces();
+
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.new_illuminant_name);
+            let valid = name_valid(&self.new_illuminant_name, &settings.custom_illuminants);
+            if ui
+                .add_enabled(valid, Button::new(icon::ADD))
+                .on_hover_text(format!("Add a white point of {} K", self.cct))
+                .clicked()
+            {
+                settings.custom_illuminants.insert(
+                    self.new_illuminant_name.take(),
+                    WhitePoint::from_cct(self.cct),
+                );
+            }
+        });
+    }
+}
+
+fn name_valid<T>(name: &str, existing: &HashMap<String, T>) -> bool {
+    !name.is_empty() && !existing.contains_key(name)
+}
+
+/// Edits chromaticity coordinates, the ranges keep them convertible to XYZ.
+fn xy_ui(x: &mut f32, y: &mut f32, ui: &mut Ui) {
+    ui.add(
+        DragValue::new(x)
+            .range(0.0..=1.)
+            .speed(XY_SPEED)
+            .prefix("x "),
+    );
+    ui.add(
+        DragValue::new(y)
+            .range(XY_SPEED..=1.)
+            .speed(XY_SPEED)
+            .prefix("y "),
+    );
+}
+
+fn white_point_ui(white: &mut WhitePoint, cct: &mut f32, ui: &mut Ui) {
+    ui.horizontal(|ui| {
+        xy_ui(&mut white.x, &mut white.y, ui);
+        ui.menu_button("CCT", |ui| {
+            ui.add(DragValue::new(cct).range(CCT_RANGE).suffix(" K"));
+            if ui.button("apply").clicked() {
+                *white = WhitePoint::from_cct(*cct);
+            }
+        })
+        .response
+        .on_hover_text("Set from a correlated color temperature");
+    });
+}
+
+fn transfer_ui(transfer: &mut TransferFunction, ui: &mut Ui) {
+    ui.horizontal(|ui| {
+        let is_gamma = matches!(transfer, TransferFunction::Gamma(_));
+        if ui.selectable_label(is_gamma, "gamma").clicked() && !is_gamma {
+            *transfer = TransferFunction::Gamma(transfer.gamma());
+        }
+        if ui.selectable_label(!is_gamma, "parametric").clicked() && is_gamma {
+            *transfer = TransferFunction::SRGB;
+        }
+        match transfer {
+            TransferFunction::Gamma(gamma) => {
+                ui.add(DragValue::new(gamma).range(0.1..=10.).speed(0.01));
+            }
+            TransferFunction::Parametric { gamma, a, b, c, d } => {
+                ui.add(
+                    DragValue::new(gamma)
+                        .range(0.1..=10.)
+                        .speed(0.01)
+                        .prefix("γ "),
+                );
+                for (value, prefix) in [(a, "a "), (b, "b "), (c, "c "), (d, "d ")] {
+                    ui.add(DragValue::new(value).speed(XY_SPEED).prefix(prefix));
+                }
+            }
+        }
+    })
+    .response
+    .on_hover_text("Parametric curves decode values from d up as (a·v + b)^γ and below as c·v");
+}
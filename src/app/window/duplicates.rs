@@ -0,0 +1,121 @@
+use crate::{
+    app::window::{self, WINDOW_X_OFFSET, WINDOW_Y_OFFSET},
+    color::{Color, DEFAULT_NEAR_DUPLICATE_TOLERANCE, NearDuplicate, Palette},
+    context::FrameCtx,
+};
+
+use egui::{CursorIcon, DragValue, Grid, RichText, ScrollArea, Ui, Window};
+
+#[derive(Debug)]
+pub struct DuplicatesWindow {
+    pub is_open: bool,
+    /// Index of the inspected palette
+    pub palette_idx: usize,
+    pub tolerance: f32,
+    /// Palette and tolerance the near-duplicates were last found for
+    cache: Option<(Palette, f32, Vec<NearDuplicate>)>,
+}
+
+impl Default for DuplicatesWindow {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            palette_idx: 0,
+            tolerance: DEFAULT_NEAR_DUPLICATE_TOLERANCE,
+            cache: None,
+        }
+    }
+}
+
+impl DuplicatesWindow {
+    pub fn open(&mut self, palette_idx: usize) {
+        self.palette_idx = palette_idx;
+        self.is_open = true;
+    }
+
+    pub fn display(&mut self, ctx: &mut FrameCtx<'_>) {
+        if !self.is_open {
+            return;
+        }
+        let Some(palette) = ctx.app.palettes.palettes.get(self.palette_idx) else {
+            self.is_open = false;
+            return;
+        };
+        let offset = ctx.egui.style().spacing.slider_width * WINDOW_X_OFFSET;
+        let mut is_open = true;
+        let is_dark_mode = ctx.egui.style().visuals.dark_mode;
+        let mut remove = None;
+
+        let outdated = !matches!(&self.cache, Some((cached, tolerance, _))
+            if *tolerance == self.tolerance && cached == &palette.palette);
+        if outdated {
+            let duplicates = palette.palette.near_duplicates(self.tolerance);
+            self.cache = Some((palette.palette.clone(), self.tolerance, duplicates));
+        }
+        let Some((_, _, duplicates)) = &self.cache else {
+            return;
+        };
+
+        Window::new("Near-duplicates")
+            .collapsible(false)
+            .frame(window::default_frame(is_dark_mode))
+            .default_pos((offset, WINDOW_Y_OFFSET))
+            .open(&mut is_open)
+            .show(ctx.egui, |ui| {
+                window::apply_default_style(ui, is_dark_mode);
+                ui.label(RichText::new(&palette.name).strong());
+                ui.horizontal(|ui| {
+                    ui.label("Tolerance");
+                    ui.add(
+                        DragValue::new(&mut self.tolerance)
+                            .range(0.0..=50.)
+                            .speed(0.1)
+                            .prefix("ΔE "),
+                    );
+                });
+                ui.separator();
+                if duplicates.is_empty() {
+                    ui.label("No near-duplicates found");
+                    return;
+                }
+                ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+                    Grid::new("near_duplicates").striped(true).show(ui, |ui| {
+                        for dup in duplicates {
+                            let first = palette.palette.0[dup.first].color;
+                            let second = palette.palette.0[dup.second].color;
+                            swatch(ui, &first);
+                            swatch(ui, &second);
+                            ui.label(format!("ΔE {:.2}", dup.delta_e));
+                            if ui
+                                .button("remove second")
+                                .on_hover_text("Remove the second color from the palette")
+                                .on_hover_cursor(CursorIcon::PointingHand)
+                                .clicked()
+                            {
+                                remove = Some(dup.second);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+
+        if let Some(i) = remove {
+            ctx.app.palettes.palettes[self.palette_idx]
+                .palette
+                .remove_pos(i);
+        }
+
+        if !is_open {
+            self.is_open = false;
+        }
+    }
+}
+
+fn swatch(ui: &mut Ui, color: &Color) {
+    ui.horizontal(|ui| {
+        let (rect, _) = ui.allocate_exact_size((16., 16.).into(), egui::Sense::hover());
+        ui.painter().rect_filled(rect, 2., color.color32());
+        ui.monospace(color.as_hex());
+    });
+}
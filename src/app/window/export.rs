@@ -1,7 +1,12 @@
 use crate::{
-    app::window::{self, WINDOW_X_OFFSET, WINDOW_Y_OFFSET},
-    color::{NamedPalette, PaletteFormat},
+    app::{
+        self,
+        window::{self, WINDOW_X_OFFSET, WINDOW_Y_OFFSET},
+    },
+    color::{NamedPalette, PaletteFormat, SlotMapping, TerminalSlot},
     context::FrameCtx,
+    settings::ColorDisplayFmtEnum,
+    swatch::{SwatchFormat, SwatchSheet},
 };
 
 use anyhow::Result;
@@ -16,6 +21,11 @@ pub struct ExportWindow {
     pub format: PaletteFormat,
     pub export_path_editable: bool,
     pub export_palette: Option<NamedPalette>,
+    pub slot_mapping: SlotMapping,
+    /// When set the palette is exported as an image instead of text `format`
+    pub swatch_format: Option<SwatchFormat>,
+    pub swatch_sheet: SwatchSheet,
+    pub swatch_label_format: ColorDisplayFmtEnum,
 }
 
 impl Default for ExportWindow {
@@ -29,6 +39,10 @@ impl Default for ExportWindow {
                 .unwrap_or_default(),
             export_path_editable: true,
             export_palette: None,
+            slot_mapping: SlotMapping::default(),
+            swatch_format: None,
+            swatch_sheet: SwatchSheet::default(),
+            swatch_label_format: ColorDisplayFmtEnum::default(),
         }
     }
 }
@@ -47,27 +61,45 @@ impl ExportWindow {
                     window::apply_default_style(ui, is_dark_mode);
                     ui.vertical(|ui| {
                         ui.horizontal(|ui| {
+                            let selected = match &self.swatch_format {
+                                Some(format) => format.as_ref(),
+                                None => self.format.as_ref(),
+                            };
                             ComboBox::from_label("format")
-                                .selected_text(self.format.as_ref())
+                                .selected_text(selected)
                                 .show_ui(ui, |ui| {
-                                    ui.selectable_value(
-                                        &mut self.format,
-                                        PaletteFormat::Gimp,
-                                        PaletteFormat::Gimp.as_ref(),
-                                    );
-                                    ui.selectable_value(
-                                        &mut self.format,
-                                        PaletteFormat::HexList,
-                                        PaletteFormat::HexList.as_ref(),
-                                    );
+                                    if app::palette_format_selection_fill(&mut self.format, ui) {
+                                        self.swatch_format = None;
+                                    }
+                                    ui.separator();
+                                    for format in [SwatchFormat::Png, SwatchFormat::Svg] {
+                                        ui.selectable_value(
+                                            &mut self.swatch_format,
+                                            Some(format),
+                                            format.as_ref(),
+                                        );
+                                    }
                                 });
                         });
+                        if self.swatch_format.is_some() {
+                            self.swatch_options_ui(ctx, ui);
+                        } else {
+                            app::palette_notation_combobox(&mut self.format, ui);
+                        }
                         if let Some(palette) = &self.export_palette {
                             ui.scope(|ui| {
                                 ui.label("Name: ");
                                 ui.label(egui::RichText::new(&palette.name).italics());
                             });
 
+                            if let (PaletteFormat::Terminal(_), None) =
+                                (&self.format, self.swatch_format)
+                            {
+                                ui.collapsing("Slot mapping", |ui| {
+                                    slot_mapping_ui(&mut self.slot_mapping, palette, ui);
+                                });
+                            }
+
                             ui.label("Export path:");
                             ui.add(
                                 TextEdit::singleline(&mut self.path)
@@ -84,15 +116,38 @@ impl ExportWindow {
                                 .on_hover_cursor(CursorIcon::PointingHand)
                                 .clicked()
                             {
-                                let generated_palette = palette.display(
-                                    &self.format,
-                                    ctx.app.settings.rgb_working_space,
-                                    ctx.app.settings.illuminant,
-                                );
+                                let (generated_palette, extension) =
+                                    match (self.swatch_format, &self.format) {
+                                        (Some(format), _) => (
+                                            self.swatch_sheet.render(palette, format, |color| {
+                                                ctx.app
+                                                    .format_color(color, &self.swatch_label_format)
+                                            }),
+                                            format.extension(),
+                                        ),
+                                        (None, PaletteFormat::Terminal(format)) => (
+                                            Ok(palette
+                                                .as_terminal_theme(*format, &self.slot_mapping)
+                                                .into_bytes()),
+                                            format.extension(),
+                                        ),
+                                        (None, format) => (
+                                            Ok(palette
+                                                .display(
+                                                    format,
+                                                    ctx.app.settings.rgb_working_space,
+                                                    ctx.app.settings.illuminant,
+                                                )
+                                                .into_bytes()),
+                                            format.extension(),
+                                        ),
+                                    };
                                 let p = PathBuf::from(&self.path);
-                                let filename =
-                                    format!("{}.{}", &palette.name, self.format.extension());
-                                if let Err(e) = fs::write(p.join(filename), generated_palette) {
+                                let filename = format!("{}.{}", &palette.name, extension);
+                                let result = generated_palette.and_then(|data| {
+                                    fs::write(p.join(filename), data).map_err(Into::into)
+                                });
+                                if let Err(e) = result {
                                     self.export_status = Err(e.to_string());
                                 } else {
                                     self.export_status = Ok("export succesful".to_string());
@@ -109,4 +164,59 @@ impl ExportWindow {
 
         Ok(())
     }
+
+    fn swatch_options_ui(&mut self, ctx: &FrameCtx, ui: &mut egui::Ui) {
+        let sheet = &mut self.swatch_sheet;
+        ui.add(egui::Slider::new(&mut sheet.swatch_size, 8..=256).text("swatch size"));
+        ui.add(egui::Slider::new(&mut sheet.columns, 1..=32).text("columns"));
+        ui.checkbox(&mut sheet.names, "Display names");
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut sheet.labels, "Display labels");
+            ui.add_enabled_ui(sheet.labels, |ui| {
+                ComboBox::from_id_salt("swatch_label_format")
+                    .selected_text(self.swatch_label_format.as_ref())
+                    .show_ui(ui, |ui| {
+                        app::color_format_selection_fill(
+                            &mut self.swatch_label_format,
+                            ctx.app.settings.saved_color_formats.keys(),
+                            ui,
+                        );
+                    });
+            });
+        });
+    }
+}
+
+fn slot_mapping_ui(mapping: &mut SlotMapping, palette: &NamedPalette, ui: &mut egui::Ui) {
+    let entry_label = |idx: Option<usize>| match idx.and_then(|i| palette.palette.0.get(i)) {
+        Some(entry) => match entry.name() {
+            Some(name) => format!("{name} ({})", entry.color.as_hex()),
+            None => entry.color.as_hex(),
+        },
+        None => "none".to_string(),
+    };
+    egui::Grid::new("terminal_slot_mapping").show(ui, |ui| {
+        for slot in TerminalSlot::ALL {
+            ui.label(slot.as_ref());
+            let current = mapping.get_mut(slot);
+            if let Some(color) = current.and_then(|i| palette.palette.0.get(i)) {
+                let (rect, _) = ui.allocate_exact_size((16., 16.).into(), egui::Sense::hover());
+                ui.painter().rect_filled(rect, 2., color.color.color32());
+            } else {
+                ui.label("");
+            }
+            ComboBox::from_id_salt(slot.as_ref())
+                .selected_text(entry_label(*current))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(current, None, "none");
+                    for i in 0..palette.palette.0.len() {
+                        ui.selectable_value(current, Some(i), entry_label(Some(i)));
+                    }
+                });
+            ui.end_row();
+        }
+    });
+    if ui.button("Reset").clicked() {
+        *mapping = SlotMapping::auto(palette);
+    }
 }
@@ -0,0 +1,277 @@
+use crate::{
+    app::window::{self, WINDOW_X_OFFSET, WINDOW_Y_OFFSET},
+    color::{
+        Color, ColorVisionDeficiency, HueSpread, NamedPalette, Palette, PaletteConstraints,
+        min_pairwise_delta_e,
+    },
+    context::FrameCtx,
+    ui::{
+        colorbox::{COLORBOX_PICK_TOOLTIP, ColorBox},
+        icon,
+    },
+};
+
+use egui::{Button, Color32, ComboBox, CursorIcon, DragValue, Grid, TextEdit, Ui, Window};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const GENERATED_COLOR_SIZE: f32 = 40.;
+
+#[derive(Debug)]
+pub struct GeneratorWindow {
+    pub is_open: bool,
+    pub constraints: PaletteConstraints,
+    pub seed: u64,
+    /// Edited seed text, a `DragValue` would round seeds above 2^53
+    seed_text: String,
+    pub name: String,
+    /// Generated colors and whether they are locked
+    colors: Vec<(Color, bool)>,
+    satisfied: bool,
+    status: Result<String, String>,
+}
+
+impl Default for GeneratorWindow {
+    fn default() -> Self {
+        let seed = random_seed();
+        Self {
+            is_open: false,
+            constraints: PaletteConstraints::default(),
+            seed,
+            seed_text: seed.to_string(),
+            name: "generated".into(),
+            colors: vec![],
+            satisfied: true,
+            status: Ok("".into()),
+        }
+    }
+}
+
+fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(1)
+}
+
+impl GeneratorWindow {
+    pub fn toggle_window(&mut self) {
+        self.is_open = !self.is_open;
+    }
+
+    fn generate(&mut self) {
+        let locked: Vec<_> = self
+            .colors
+            .iter()
+            .map(|(color, locked)| locked.then_some(*color))
+            .collect();
+        let generated = self.constraints.generate(&locked, self.seed);
+        self.colors = generated
+            .colors
+            .into_iter()
+            .zip(
+                locked
+                    .into_iter()
+                    .map(|c| c.is_some())
+                    .chain(std::iter::repeat(false)),
+            )
+            .collect();
+        self.satisfied = generated.satisfied;
+    }
+
+    pub fn display(&mut self, ctx: &mut FrameCtx<'_>) {
+        if !self.is_open {
+            return;
+        }
+        let offset = ctx.egui.style().spacing.slider_width * WINDOW_X_OFFSET;
+        let mut is_open = true;
+        let is_dark_mode = ctx.egui.style().visuals.dark_mode;
+
+        Window::new("Generate palette")
+            .collapsible(false)
+            .frame(window::default_frame(is_dark_mode))
+            .default_pos((offset, WINDOW_Y_OFFSET))
+            .open(&mut is_open)
+            .show(ctx.egui, |ui| {
+                window::apply_default_style(ui, is_dark_mode);
+                self.constraints_ui(ui);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Seed");
+                    let parsed = self.seed_text.trim().parse::<u64>();
+                    let mut text_edit =
+                        TextEdit::singleline(&mut self.seed_text).desired_width(160.);
+                    if parsed.is_err() {
+                        text_edit = text_edit.text_color(Color32::RED);
+                    }
+                    if ui.add(text_edit).changed()
+                        && let Ok(seed) = self.seed_text.trim().parse()
+                    {
+                        self.seed = seed;
+                    }
+                    if ui
+                        .button("generate")
+                        .on_hover_text("Regenerate all unlocked colors from this seed")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        self.generate();
+                    }
+                    if ui
+                        .button("reroll")
+                        .on_hover_text("Pick a new seed and regenerate all unlocked colors")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        self.seed = random_seed();
+                        self.seed_text = self.seed.to_string();
+                        self.generate();
+                    }
+                });
+                self.colors_ui(ctx, ui);
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Name");
+                    ui.text_edit_singleline(&mut self.name);
+                });
+                match &self.status {
+                    Ok(msg) => ui.colored_label(Color32::GREEN, msg),
+                    Err(msg) => ui.colored_label(Color32::RED, msg),
+                };
+                let can_add = !self.colors.is_empty() && !self.name.is_empty();
+                if ui
+                    .add_enabled(can_add, Button::new("add palette"))
+                    .on_hover_cursor(CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    let mut palette = Palette::default();
+                    for (color, _) in &self.colors {
+                        palette.add(*color);
+                    }
+                    let palette = NamedPalette {
+                        name: self.name.clone(),
+                        palette,
+                    };
+                    self.status = if ctx.app.palettes.add(palette) {
+                        Ok(format!("created palette {}", self.name))
+                    } else {
+                        Err(format!("palette {} already exists", self.name))
+                    };
+                }
+            });
+
+        if !is_open {
+            self.is_open = false;
+        }
+    }
+
+    fn constraints_ui(&mut self, ui: &mut Ui) {
+        let constraints = &mut self.constraints;
+        Grid::new("generator_constraints").show(ui, |ui| {
+            ui.label("Colors");
+            ui.add(DragValue::new(&mut constraints.count).range(1..=64));
+            ui.end_row();
+            ui.label("Minimum ΔE");
+            ui.add(
+                DragValue::new(&mut constraints.min_delta_e)
+                    .range(0.0..=100.)
+                    .speed(0.1),
+            )
+            .on_hover_text("Smallest CIEDE2000 difference between any two colors");
+            ui.end_row();
+            ui.label("Lightness");
+            ui.horizontal(|ui| {
+                ui.add(DragValue::new(&mut constraints.lightness.0).range(0.0..=100.));
+                ui.add(DragValue::new(&mut constraints.lightness.1).range(0.0..=100.));
+            });
+            ui.end_row();
+            ui.label("Chroma");
+            ui.horizontal(|ui| {
+                ui.add(DragValue::new(&mut constraints.chroma.0).range(0.0..=150.));
+                ui.add(DragValue::new(&mut constraints.chroma.1).range(0.0..=150.));
+            });
+            ui.end_row();
+            ui.label("Hues");
+            ComboBox::from_id_salt("generator_hue_spread")
+                .selected_text(constraints.hue_spread.as_ref())
+                .show_ui(ui, |ui| {
+                    for spread in HueSpread::ALL {
+                        ui.selectable_value(&mut constraints.hue_spread, spread, spread.as_ref());
+                    }
+                });
+            ui.end_row();
+        });
+        if constraints.hue_spread == HueSpread::Fixed {
+            ui.horizontal_wrapped(|ui| {
+                let mut remove = None;
+                for (i, hue) in constraints.fixed_hues.iter_mut().enumerate() {
+                    ui.add(DragValue::new(hue).range(0.0..=360.).suffix("°"));
+                    if ui
+                        .small_button(icon::DELETE)
+                        .on_hover_text("Remove this hue")
+                        .clicked()
+                    {
+                        remove = Some(i);
+                    }
+                }
+                if let Some(i) = remove {
+                    constraints.fixed_hues.remove(i);
+                }
+                if ui.button(icon::ADD).on_hover_text("Add a hue").clicked() {
+                    constraints.fixed_hues.push(0.);
+                }
+            });
+        }
+        ui.checkbox(
+            &mut constraints.check_cvd,
+            "distinguishable with color blindness",
+        )
+        .on_hover_text(
+            "Also require the minimum difference with simulated protanopia, deuteranopia \
+                 and tritanopia",
+        );
+    }
+
+    fn colors_ui(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        if self.colors.is_empty() {
+            return;
+        }
+        Grid::new("generated_colors")
+            .spacing((2., 2.))
+            .show(ui, |ui| {
+                for (color, _) in &self.colors {
+                    let cb = ColorBox::builder()
+                        .size((GENERATED_COLOR_SIZE, GENERATED_COLOR_SIZE))
+                        .color(*color)
+                        .hover_help(COLORBOX_PICK_TOOLTIP)
+                        .build();
+                    cb.display(ctx, ui);
+                }
+                ui.end_row();
+                for (_, locked) in &mut self.colors {
+                    ui.checkbox(locked, "")
+                        .on_hover_text("Keep this color when regenerating");
+                }
+            });
+
+        let colors: Vec<_> = self.colors.iter().map(|(c, _)| *c).collect();
+        if !self.satisfied {
+            ui.colored_label(
+                Color32::YELLOW,
+                "Could not satisfy all constraints, showing the most distinct colors found",
+            );
+        }
+        if let Some(delta_e) = min_pairwise_delta_e(&colors, None) {
+            ui.label(format!("Minimum ΔE {delta_e:.1}"));
+            let cvd = ColorVisionDeficiency::ALL
+                .iter()
+                .filter_map(|d| {
+                    min_pairwise_delta_e(&colors, Some(*d))
+                        .map(|delta_e| format!("{} {delta_e:.1}", d.as_ref()))
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            ui.label(cvd);
+        }
+    }
+}
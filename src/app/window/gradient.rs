@@ -0,0 +1,42 @@
+use egui::{DragValue, Ui};
+use std::env;
+
+/// Number of pixels of the rendered gradient preview.
+pub const GRADIENT_PREVIEW_WIDTH: usize = 256;
+
+#[derive(Debug)]
+pub struct GradientWindow {
+    pub num_of_steps: u8,
+    /// Direction of the exported CSS gradient in degrees
+    pub css_angle: f32,
+    pub export_path: String,
+    pub export_status: Result<String, String>,
+}
+
+impl Default for GradientWindow {
+    fn default() -> Self {
+        Self {
+            num_of_steps: 8,
+            css_angle: 90.,
+            export_path: env::current_dir()
+                .map(|d| d.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            export_status: Ok("".to_string()),
+        }
+    }
+}
+
+impl GradientWindow {
+    pub fn export_options(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Steps");
+            ui.add(DragValue::new(&mut self.num_of_steps).range(2..=64));
+            ui.label("CSS angle");
+            ui.add(
+                DragValue::new(&mut self.css_angle)
+                    .range(0.0..=360.)
+                    .suffix("°"),
+            );
+        });
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    app::window::{self, WINDOW_X_OFFSET, WINDOW_Y_OFFSET},
+    context::FrameCtx,
+};
+
+use egui::{Color32, CursorIcon, RichText, ScrollArea, Window};
+
+enum HistoryAction {
+    Undo(usize),
+    Redo(usize),
+}
+
+#[derive(Debug, Default)]
+pub struct HistoryWindow {
+    pub is_open: bool,
+}
+
+impl HistoryWindow {
+    pub fn toggle_window(&mut self) {
+        self.is_open = !self.is_open;
+    }
+
+    pub fn display(&mut self, ctx: &mut FrameCtx<'_>) {
+        if self.is_open {
+            let offset = ctx.egui.style().spacing.slider_width * WINDOW_X_OFFSET;
+            let mut is_open = true;
+            let is_dark_mode = ctx.egui.style().visuals.dark_mode;
+            let mut action = None;
+
+            Window::new("History")
+                .collapsible(false)
+                .frame(window::default_frame(is_dark_mode))
+                .default_pos((offset, WINDOW_Y_OFFSET))
+                .open(&mut is_open)
+                .show(ctx.egui, |ui| {
+                    window::apply_default_style(ui, is_dark_mode);
+                    let history = &ctx.app.history;
+                    let undo_len = history.undo_entries().count();
+                    let redo_len = history.redo_entries().count();
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(undo_len > 0, egui::Button::new("undo"))
+                            .on_hover_text("ctrl+z")
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            action = Some(HistoryAction::Undo(1));
+                        }
+                        if ui
+                            .add_enabled(redo_len > 0, egui::Button::new("redo"))
+                            .on_hover_text("ctrl+shift+z")
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            action = Some(HistoryAction::Redo(1));
+                        }
+                        if ui
+                            .button("clear")
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            ctx.app.history.clear();
+                        }
+                    });
+                    ui.separator();
+                    let history = &ctx.app.history;
+                    ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+                        for (i, entry) in history.undo_entries().enumerate() {
+                            if ui
+                                .selectable_label(false, &entry.description)
+                                .on_hover_text("Undo up to this change")
+                                .clicked()
+                            {
+                                action = Some(HistoryAction::Undo(undo_len - i));
+                            }
+                        }
+                        ui.label(RichText::new("current state").strong());
+                        for (i, entry) in history.redo_entries().enumerate() {
+                            let label = RichText::new(&entry.description).color(Color32::GRAY);
+                            if ui
+                                .selectable_label(false, label)
+                                .on_hover_text("Redo up to this change")
+                                .clicked()
+                            {
+                                action = Some(HistoryAction::Redo(i + 1));
+                            }
+                        }
+                    });
+                });
+
+            match action {
+                Some(HistoryAction::Undo(n)) => (0..n).for_each(|_| ctx.app.undo()),
+                Some(HistoryAction::Redo(n)) => (0..n).for_each(|_| ctx.app.redo()),
+                None => {}
+            }
+
+            if !is_open {
+                self.is_open = false;
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{
+    app::window::{self, WINDOW_X_OFFSET, WINDOW_Y_OFFSET},
+    color::DEFAULT_NEAR_DUPLICATE_TOLERANCE,
+    context::FrameCtx,
+};
+
+use egui::{Color32, CursorIcon, DragValue, ScrollArea, Window};
+use std::collections::BTreeSet;
+
+#[derive(Debug)]
+pub struct MergeWindow {
+    pub is_open: bool,
+    pub name: String,
+    pub tolerance: f32,
+    /// Names of the selected palettes, indices would point to other palettes after the palettes
+    /// are reordered or removed while the window is open
+    selected: BTreeSet<String>,
+    status: Result<String, String>,
+}
+
+impl Default for MergeWindow {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            name: "merged".into(),
+            tolerance: DEFAULT_NEAR_DUPLICATE_TOLERANCE,
+            selected: BTreeSet::new(),
+            status: Ok("".into()),
+        }
+    }
+}
+
+impl MergeWindow {
+    pub fn toggle_window(&mut self) {
+        self.is_open = !self.is_open;
+    }
+
+    pub fn display(&mut self, ctx: &mut FrameCtx<'_>) {
+        if !self.is_open {
+            return;
+        }
+        let offset = ctx.egui.style().spacing.slider_width * WINDOW_X_OFFSET;
+        let mut is_open = true;
+        let is_dark_mode = ctx.egui.style().visuals.dark_mode;
+        let palettes = &ctx.app.palettes;
+        self.selected
+            .retain(|name| palettes.iter().any(|palette| &palette.name == name));
+
+        Window::new("Merge palettes")
+            .collapsible(false)
+            .frame(window::default_frame(is_dark_mode))
+            .default_pos((offset, WINDOW_Y_OFFSET))
+            .open(&mut is_open)
+            .show(ctx.egui, |ui| {
+                window::apply_default_style(ui, is_dark_mode);
+                ScrollArea::vertical().max_height(200.).show(ui, |ui| {
+                    for palette in ctx.app.palettes.iter() {
+                        let mut checked = self.selected.contains(&palette.name);
+                        let label = format!("{} ({})", palette.name, palette.palette.0.len());
+                        if ui.checkbox(&mut checked, label).changed() {
+                            if checked {
+                                self.selected.insert(palette.name.clone());
+                            } else {
+                                self.selected.remove(&palette.name);
+                            }
+                        }
+                    }
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Name");
+                    ui.text_edit_singleline(&mut self.name);
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Tolerance");
+                    ui.add(
+                        DragValue::new(&mut self.tolerance)
+                            .range(0.0..=50.)
+                            .speed(0.1)
+                            .prefix("ΔE "),
+                    )
+                    .on_hover_text("Skip colors closer than this to an already merged color");
+                });
+                match &self.status {
+                    Ok(msg) => ui.colored_label(Color32::GREEN, msg),
+                    Err(msg) => ui.colored_label(Color32::RED, msg),
+                };
+                let can_merge = self.selected.len() > 1 && !self.name.is_empty();
+                if ui
+                    .add_enabled(can_merge, egui::Button::new("merge"))
+                    .on_hover_cursor(CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    let indices: Vec<_> = ctx
+                        .app
+                        .palettes
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, palette)| self.selected.contains(&palette.name))
+                        .map(|(i, _)| i)
+                        .collect();
+                    self.status =
+                        if ctx
+                            .app
+                            .palettes
+                            .merge(&indices, self.name.clone(), self.tolerance)
+                        {
+                            self.selected.clear();
+                            Ok(format!("created palette {}", self.name))
+                        } else {
+                            Err(format!("palette {} already exists", self.name))
+                        };
+                }
+            });
+
+        if !is_open {
+            self.is_open = false;
+        }
+    }
+}
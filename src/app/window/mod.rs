@@ -1,18 +1,39 @@
+mod chromaticity;
 mod custom_formats;
+mod custom_spaces;
+mod duplicates;
 mod export;
+mod generator;
+mod gradient;
 mod help;
+mod history;
+mod merge;
 mod palette_formats;
 mod settings;
+mod spectral;
+mod tonal_scale;
 
-use crate::ui::colors::*;
+use crate::{
+    color::{PerceptualSpace, ToneMode},
+    ui::colors::*,
+};
 use egui::CornerRadius;
 
+pub use chromaticity::ChromaticityWindow;
 pub use custom_formats::CustomFormatsWindow;
-use egui::{Frame, Margin, Slider, Stroke, Ui, epaint::Shadow};
+pub use custom_spaces::CustomSpacesWindow;
+pub use duplicates::DuplicatesWindow;
+use egui::{ComboBox, Frame, Margin, Slider, Stroke, Ui, epaint::Shadow};
 pub use export::ExportWindow;
+pub use generator::GeneratorWindow;
+pub use gradient::{GRADIENT_PREVIEW_WIDTH, GradientWindow};
 pub use help::HelpWindow;
+pub use history::HistoryWindow;
+pub use merge::MergeWindow;
 pub use palette_formats::PaletteFormatsWindow;
 pub use settings::SettingsWindow;
+pub use spectral::SpectralWindow;
+pub use tonal_scale::TonalScaleWindow;
 
 pub const WINDOW_X_OFFSET: f32 = 10.;
 pub const WINDOW_Y_OFFSET: f32 = 30.;
@@ -59,10 +80,32 @@ pub fn apply_default_style(ui: &mut Ui, is_dark_mode: bool) {
     }
 }
 
+fn perceptual_options(id: &str, space: &mut PerceptualSpace, mode: &mut ToneMode, ui: &mut Ui) {
+    ui.horizontal(|ui| {
+        ComboBox::from_id_salt((id, "space"))
+            .selected_text(space.as_ref())
+            .show_ui(ui, |ui| {
+                for s in PerceptualSpace::ALL {
+                    ui.selectable_value(space, s, s.as_ref());
+                }
+            });
+        ComboBox::from_id_salt((id, "mode"))
+            .selected_text(mode.as_ref())
+            .show_ui(ui, |ui| {
+                for m in ToneMode::ALL {
+                    ui.selectable_value(mode, m, m.as_ref());
+                }
+            });
+        ui.label("perceptual variants");
+    });
+}
+
 #[derive(Debug)]
 pub struct ShadesWindow {
     pub num_of_shades: u8,
     pub shade_color_size: f32,
+    pub perceptual_space: PerceptualSpace,
+    pub tone_mode: ToneMode,
 }
 
 impl Default for ShadesWindow {
@@ -70,6 +113,8 @@ impl Default for ShadesWindow {
         Self {
             num_of_shades: 6,
             shade_color_size: DEFAULT_COLOR_SIZE,
+            perceptual_space: PerceptualSpace::default(),
+            tone_mode: ToneMode::default(),
         }
     }
 }
@@ -86,6 +131,12 @@ impl ShadesWindow {
                 .clamping(egui::SliderClamping::Always)
                 .text("color size"),
         );
+        perceptual_options(
+            "shades",
+            &mut self.perceptual_space,
+            &mut self.tone_mode,
+            ui,
+        );
     }
 }
 
@@ -93,6 +144,8 @@ impl ShadesWindow {
 pub struct TintsWindow {
     pub num_of_tints: u8,
     pub tint_color_size: f32,
+    pub perceptual_space: PerceptualSpace,
+    pub tone_mode: ToneMode,
 }
 
 impl Default for TintsWindow {
@@ -100,6 +153,8 @@ impl Default for TintsWindow {
         Self {
             num_of_tints: 6,
             tint_color_size: DEFAULT_COLOR_SIZE,
+            perceptual_space: PerceptualSpace::default(),
+            tone_mode: ToneMode::default(),
         }
     }
 }
@@ -116,6 +171,7 @@ impl TintsWindow {
                 .clamping(egui::SliderClamping::Always)
                 .text("color size"),
         );
+        perceptual_options("tints", &mut self.perceptual_space, &mut self.tone_mode, ui);
     }
 }
 
@@ -1,18 +1,21 @@
 use crate::{
     app::AppCtx,
-    color::{ChromaticAdaptationMethod, ColorHarmony, Illuminant, RgbWorkingSpace},
+    color::{
+        ChromaticAdaptationMethod, ColorHarmony, GamutMapping, HarmonySpace, IccProfile,
+        Illuminant, RgbWorkingSpace,
+    },
     context::FrameCtx,
     settings::Settings,
     ui::{DOUBLE_SPACE, HALF_SPACE},
 };
 
-use egui::{Color32, ComboBox, Ui};
+use egui::{Color32, ComboBox, Slider, Ui};
 use std::fmt::Display;
 
 use egui::CursorIcon;
 use std::fs;
 
-use crate::app::window::{CustomFormatsWindow, PaletteFormatsWindow};
+use crate::app::window::{CustomFormatsWindow, CustomSpacesWindow, PaletteFormatsWindow};
 
 const UI_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.25..=5.0;
 
@@ -21,7 +24,10 @@ pub struct SettingsWindow {
     pub error: Option<String>,
     pub message: Option<String>,
     pub custom_formats_window: CustomFormatsWindow,
+    pub custom_spaces_window: CustomSpacesWindow,
     pub palette_formats_window: PaletteFormatsWindow,
+    /// Path of the display profile being edited
+    profile_path: Option<String>,
     tab: Tab,
 }
 
@@ -84,6 +90,36 @@ impl SettingsWindow {
 
     fn general_settings_ui(&mut self, ui: &mut Ui, ctx: &mut FrameCtx<'_>) {
         self.ui_scale_slider(ctx.app, ui);
+        ui.add_space(HALF_SPACE);
+        ui.horizontal(|ui| {
+            ui.label("Undo history limit");
+            if ui
+                .add(egui::Slider::new(
+                    &mut ctx.app.settings.undo_history_limit,
+                    1..=1000,
+                ))
+                .changed()
+            {
+                ctx.app
+                    .history
+                    .truncate(ctx.app.settings.undo_history_limit);
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Pick history limit");
+            ui.add(egui::Slider::new(
+                &mut ctx.app.settings.pick_history_limit,
+                1..=500,
+            ));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Duplicate color tolerance (ΔE)");
+            ui.add(egui::Slider::new(
+                &mut ctx.app.settings.duplicate_tolerance,
+                0.0..=10.,
+            ))
+            .on_hover_text("Colors closer than this to a saved color won't be added to a palette");
+        });
     }
 
     fn color_settings_ui(&mut self, ui: &mut Ui, ctx: &mut FrameCtx<'_>) {
@@ -93,9 +129,15 @@ impl SettingsWindow {
         ui.add_space(HALF_SPACE);
         self.chromatic_adaptation_method(ctx.app, ui);
         ui.add_space(HALF_SPACE);
+        self.gamut_mapping(ctx.app, ui);
+        ui.add_space(HALF_SPACE);
+        self.display_profile(ctx.app, ui);
+        ui.add_space(HALF_SPACE);
         self.color_harmony(ctx.app, ui);
         ui.add_space(HALF_SPACE);
         ui.checkbox(&mut ctx.app.settings.cache_colors, "Cache colors");
+        ui.add_space(HALF_SPACE);
+        self.custom_spaces_window.display(&mut ctx.app.settings, ui);
         ui.add_space(DOUBLE_SPACE);
     }
 
@@ -124,47 +166,30 @@ impl SettingsWindow {
         ComboBox::from_label("Color harmony")
             .selected_text(app_ctx.settings.harmony.as_ref())
             .show_ui(ui, |ui| {
-                ui.selectable_value(
-                    &mut app_ctx.settings.harmony,
-                    ColorHarmony::Complementary,
-                    ColorHarmony::Complementary.as_ref(),
-                );
-                ui.selectable_value(
-                    &mut app_ctx.settings.harmony,
-                    ColorHarmony::Triadic,
-                    ColorHarmony::Triadic.as_ref(),
-                );
-                ui.selectable_value(
-                    &mut app_ctx.settings.harmony,
-                    ColorHarmony::Tetradic,
-                    ColorHarmony::Tetradic.as_ref(),
-                );
-                ui.selectable_value(
-                    &mut app_ctx.settings.harmony,
-                    ColorHarmony::Analogous,
-                    ColorHarmony::Analogous.as_ref(),
-                );
-                ui.selectable_value(
-                    &mut app_ctx.settings.harmony,
-                    ColorHarmony::SplitComplementary,
-                    ColorHarmony::SplitComplementary.as_ref(),
-                );
-                ui.selectable_value(
-                    &mut app_ctx.settings.harmony,
-                    ColorHarmony::Square,
-                    ColorHarmony::Square.as_ref(),
-                );
-                ui.selectable_value(
-                    &mut app_ctx.settings.harmony,
-                    ColorHarmony::Monochromatic,
-                    ColorHarmony::Monochromatic.as_ref(),
-                );
+                for harmony in ColorHarmony::ALL {
+                    ui.selectable_value(&mut app_ctx.settings.harmony, harmony, harmony.as_ref());
+                }
+            });
+        ComboBox::from_label("Harmony color space")
+            .selected_text(app_ctx.settings.harmony_space.as_ref())
+            .show_ui(ui, |ui| {
+                for space in HarmonySpace::ALL {
+                    ui.selectable_value(&mut app_ctx.settings.harmony_space, space, space.as_ref());
+                }
             });
     }
 
     fn illuminant(&mut self, app_ctx: &mut AppCtx, ui: &mut Ui) {
+        let selected = app_ctx.settings.illuminant_name().to_string();
+        let mut customs: Vec<_> = app_ctx
+            .settings
+            .custom_illuminants
+            .keys()
+            .cloned()
+            .collect();
+        customs.sort();
         ComboBox::from_label("Illuminant")
-            .selected_text(app_ctx.settings.illuminant.as_ref())
+            .selected_text(selected)
             .show_ui(ui, |ui| {
                 ui.selectable_value(
                     &mut app_ctx.settings.illuminant,
@@ -191,6 +216,11 @@ impl SettingsWindow {
                     Illuminant::D55,
                     Illuminant::D55.as_ref(),
                 );
+                ui.selectable_value(
+                    &mut app_ctx.settings.illuminant,
+                    Illuminant::D60,
+                    Illuminant::D60.as_ref(),
+                );
                 ui.selectable_value(
                     &mut app_ctx.settings.illuminant,
                     Illuminant::D65,
@@ -221,35 +251,180 @@ impl SettingsWindow {
                     Illuminant::F11,
                     Illuminant::F11.as_ref(),
                 );
+                ui.selectable_value(
+                    &mut app_ctx.settings.illuminant,
+                    Illuminant::Dci,
+                    Illuminant::Dci.as_ref(),
+                );
+                for name in customs {
+                    let is_selected = app_ctx.settings.custom_illuminant.as_ref() == Some(&name);
+                    if ui
+                        .selectable_label(is_selected, format!("*{name}"))
+                        .clicked()
+                    {
+                        app_ctx.settings.select_custom_illuminant(&name);
+                    }
+                }
             });
+        app_ctx.settings.sync_custom_spaces();
     }
 
     fn chromatic_adaptation_method(&mut self, app_ctx: &mut AppCtx, ui: &mut Ui) {
+        let method = &mut app_ctx.settings.chromatic_adaptation_method;
         ComboBox::from_label("Chromatic adaptation method")
-            .selected_text(app_ctx.settings.chromatic_adaptation_method.as_ref())
+            .selected_text(method.as_ref())
             .show_ui(ui, |ui| {
-                ui.selectable_value(
-                    &mut app_ctx.settings.chromatic_adaptation_method,
-                    ChromaticAdaptationMethod::Bradford,
-                    ChromaticAdaptationMethod::Bradford.as_ref(),
-                );
-                ui.selectable_value(
-                    &mut app_ctx.settings.chromatic_adaptation_method,
-                    ChromaticAdaptationMethod::VonKries,
-                    ChromaticAdaptationMethod::VonKries.as_ref(),
-                );
-                ui.selectable_value(
-                    &mut app_ctx.settings.chromatic_adaptation_method,
-                    ChromaticAdaptationMethod::XYZScaling,
-                    ChromaticAdaptationMethod::XYZScaling.as_ref(),
-                );
+                for m in ChromaticAdaptationMethod::ALL {
+                    if ui
+                        .selectable_label(method.same_method(&m), m.as_ref())
+                        .clicked()
+                        && !method.same_method(&m)
+                    {
+                        *method = m;
+                    }
+                }
+            });
+        if let Some(degree) = method.degree_mut() {
+            ui.horizontal(|ui| {
+                let mut partial = degree.is_some();
+                ui.checkbox(&mut partial, "Degree of adaptation")
+                    .on_hover_text("Adapt colors only partially to the destination white point");
+                match (partial, degree.as_mut()) {
+                    (true, Some(d)) => {
+                        ui.add(Slider::new(d, 0.0..=1.).fixed_decimals(2));
+                    }
+                    (true, None) => *degree = Some(1.),
+                    (false, _) => *degree = None,
+                }
             });
+        }
+    }
+
+    fn gamut_mapping(&mut self, app_ctx: &mut AppCtx, ui: &mut Ui) {
+        ComboBox::from_label("Gamut mapping")
+            .selected_text(app_ctx.settings.gamut_mapping.as_ref())
+            .show_ui(ui, |ui| {
+                for mapping in GamutMapping::ALL {
+                    ui.selectable_value(
+                        &mut app_ctx.settings.gamut_mapping,
+                        mapping,
+                        mapping.as_ref(),
+                    );
+                }
+            })
+            .response
+            .on_hover_text("How colors outside of the RGB working space are brought into it");
+    }
+
+    fn display_profile(&mut self, app_ctx: &mut AppCtx, ui: &mut Ui) {
+        let path = self
+            .profile_path
+            .get_or_insert_with(|| app_ctx.settings.display_profile.clone().unwrap_or_default());
+        let mut load = false;
+        ui.horizontal(|ui| {
+            ui.label("Display profile");
+            ui.text_edit_singleline(path)
+                .on_hover_text("Path of the ICC profile of the monitor");
+            load = ui
+                .button("load")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked();
+        });
+        if load {
+            let path = path.clone();
+            match IccProfile::load(&path) {
+                Ok(profile) => {
+                    self.set_message(format!("loaded display profile {}", profile.description));
+                    app_ctx.settings.display_profile = Some(path);
+                    app_ctx.display_profile = Some(profile);
+                }
+                Err(e) => self.set_error(format!("{e:#}")),
+            }
+        }
+
+        let Some(profile) = &app_ctx.display_profile else {
+            return;
+        };
+        let (mut add, mut remove) = (false, false);
+        ui.horizontal(|ui| {
+            ui.label(&profile.description);
+            add = ui
+                .button("add as working space")
+                .on_hover_text(
+                    "Add the primaries, white point and tone curve of the profile as a custom \
+                     working space",
+                )
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked();
+            remove = ui
+                .button("remove")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked();
+        });
+        if add {
+            let ws = profile.working_space();
+            let spaces = &mut app_ctx.settings.custom_working_spaces;
+            if !ws.is_valid() {
+                self.set_error(format!(
+                    "{} has no valid primaries and white point",
+                    profile.description
+                ));
+            } else if spaces.get(&profile.description) == Some(&ws) {
+                self.set_message(format!(
+                    "{} is already a working space",
+                    profile.description
+                ));
+            } else {
+                let name = (1..)
+                    .map(|i| match i {
+                        1 => profile.description.clone(),
+                        i => format!("{} ({i})", profile.description),
+                    })
+                    .find(|name| !spaces.contains_key(name))
+                    .unwrap_or_default();
+                self.set_message(format!("added working space {name}"));
+                spaces.insert(name, ws);
+            }
+        }
+        if remove {
+            app_ctx.display_profile = None;
+            app_ctx.settings.display_profile = None;
+            app_ctx.settings.use_display_profile = false;
+            self.profile_path = None;
+            return;
+        }
+        ui.checkbox(
+            &mut app_ctx.settings.use_display_profile,
+            "Convert picked colors from the display profile",
+        )
+        .on_hover_text(
+            "Screen pixels are encoded in the display profile, converting them gives the \
+             colors actually shown on a wide gamut monitor",
+        );
     }
 
     fn rgb_working_space(&mut self, app_ctx: &mut AppCtx, ui: &mut Ui) {
+        let selected = app_ctx.settings.working_space_name().to_string();
+        let mut customs: Vec<_> = app_ctx
+            .settings
+            .custom_working_spaces
+            .keys()
+            .cloned()
+            .collect();
+        customs.sort();
         ComboBox::from_label("RGB Working Space")
-            .selected_text(app_ctx.settings.rgb_working_space.as_ref())
+            .selected_text(selected)
             .show_ui(ui, |ui| {
+                ui.selectable_value(
+                    &mut app_ctx.settings.rgb_working_space,
+                    RgbWorkingSpace::ACES2065,
+                    RgbWorkingSpace::ACES2065.as_ref(),
+                );
+                ui.selectable_value(
+                    &mut app_ctx.settings.rgb_working_space,
+                    RgbWorkingSpace::ACEScg,
+                    RgbWorkingSpace::ACEScg.as_ref(),
+                );
                 ui.selectable_value(
                     &mut app_ctx.settings.rgb_working_space,
                     RgbWorkingSpace::Adobe,
@@ -265,6 +440,16 @@ impl SettingsWindow {
                     RgbWorkingSpace::CIE,
                     RgbWorkingSpace::CIE.as_ref(),
                 );
+                ui.selectable_value(
+                    &mut app_ctx.settings.rgb_working_space,
+                    RgbWorkingSpace::DCIP3,
+                    RgbWorkingSpace::DCIP3.as_ref(),
+                );
+                ui.selectable_value(
+                    &mut app_ctx.settings.rgb_working_space,
+                    RgbWorkingSpace::DisplayP3,
+                    RgbWorkingSpace::DisplayP3.as_ref(),
+                );
                 ui.selectable_value(
                     &mut app_ctx.settings.rgb_working_space,
                     RgbWorkingSpace::ECI,
@@ -285,6 +470,16 @@ impl SettingsWindow {
                     RgbWorkingSpace::ProPhoto,
                     RgbWorkingSpace::ProPhoto.as_ref(),
                 );
+                ui.selectable_value(
+                    &mut app_ctx.settings.rgb_working_space,
+                    RgbWorkingSpace::Rec709,
+                    RgbWorkingSpace::Rec709.as_ref(),
+                );
+                ui.selectable_value(
+                    &mut app_ctx.settings.rgb_working_space,
+                    RgbWorkingSpace::Rec2020,
+                    RgbWorkingSpace::Rec2020.as_ref(),
+                );
                 ui.selectable_value(
                     &mut app_ctx.settings.rgb_working_space,
                     RgbWorkingSpace::SRGB,
@@ -295,7 +490,17 @@ impl SettingsWindow {
                     RgbWorkingSpace::WideGamut,
                     RgbWorkingSpace::WideGamut.as_ref(),
                 );
+                for name in customs {
+                    let is_selected = app_ctx.settings.custom_working_space.as_ref() == Some(&name);
+                    if ui
+                        .selectable_label(is_selected, format!("*{name}"))
+                        .clicked()
+                    {
+                        app_ctx.settings.select_custom_working_space(&name);
+                    }
+                }
             });
+        app_ctx.settings.sync_custom_spaces();
     }
 
     fn ui_scale_slider(&mut self, app_ctx: &mut AppCtx, ui: &mut Ui) {
@@ -0,0 +1,236 @@
+use crate::{
+    app::window::{self, WINDOW_X_OFFSET, WINDOW_Y_OFFSET},
+    color::{
+        ChromaticAdaptationMethod, Color, GamutMapping, Illuminant, NamedPalette, Observer,
+        Palette, PaletteEntry, RgbWorkingSpace, SpectralConversion, Spectrum, SpectrumKind,
+        parse_spectra,
+    },
+    context::FrameCtx,
+    ui::colorbox::{COLORBOX_PICK_TOOLTIP, ColorBox},
+};
+
+use egui::{Button, Color32, ComboBox, CursorIcon, Grid, ScrollArea, Ui, Window};
+use std::{fs, path::Path};
+
+const SWATCH_SIZE: f32 = 32.;
+const SWATCH_COLUMNS: usize = 8;
+
+#[derive(Debug)]
+pub struct SpectralWindow {
+    pub is_open: bool,
+    pub path: String,
+    pub conversion: SpectralConversion,
+    /// Name of the palette created from the loaded spectra
+    pub name: String,
+    spectra: Vec<Spectrum>,
+    /// Colors of the loaded spectra together with the settings they were converted with
+    colors: Option<(ConversionKey, Vec<Color>)>,
+    status: Result<String, String>,
+}
+
+type ConversionKey = (
+    SpectralConversion,
+    RgbWorkingSpace,
+    ChromaticAdaptationMethod,
+    GamutMapping,
+);
+
+impl Default for SpectralWindow {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            path: String::new(),
+            conversion: SpectralConversion::default(),
+            name: String::new(),
+            spectra: vec![],
+            colors: None,
+            status: Ok("".into()),
+        }
+    }
+}
+
+impl SpectralWindow {
+    pub fn toggle_window(&mut self) {
+        self.is_open = !self.is_open;
+    }
+
+    fn load(&mut self) {
+        let path = Path::new(&self.path);
+        let loaded = fs::read_to_string(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|text| parse_spectra(&text));
+        match loaded {
+            Ok(spectra) => {
+                self.status = Ok(format!("loaded {} spectra", spectra.len()));
+                self.spectra = spectra;
+                self.colors = None;
+                self.name = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+            }
+            Err(e) => self.status = Err(format!("{e:#}")),
+        }
+    }
+
+    pub fn display(&mut self, ctx: &mut FrameCtx<'_>) {
+        if !self.is_open {
+            return;
+        }
+        let offset = ctx.egui.style().spacing.slider_width * WINDOW_X_OFFSET;
+        let mut is_open = true;
+        let is_dark_mode = ctx.egui.style().visuals.dark_mode;
+
+        Window::new("Spectral data")
+            .collapsible(false)
+            .frame(window::default_frame(is_dark_mode))
+            .default_pos((offset, WINDOW_Y_OFFSET))
+            .open(&mut is_open)
+            .show(ctx.egui, |ui| {
+                window::apply_default_style(ui, is_dark_mode);
+                ui.label("CGATS or CSV file with reflectance or emission spectra:");
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.path);
+                    if ui
+                        .button("load")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        self.load();
+                    }
+                });
+                self.conversion_ui(ui);
+                ui.separator();
+
+                let settings = &ctx.app.settings;
+                let key = (
+                    self.conversion,
+                    settings.rgb_working_space,
+                    settings.chromatic_adaptation_method,
+                    settings.gamut_mapping,
+                );
+                let outdated = !matches!(&self.colors, Some((cached, _)) if *cached == key);
+                if outdated {
+                    let (conversion, ws, method, mapping) = key;
+                    let colors = self
+                        .spectra
+                        .iter()
+                        .map(|spectrum| conversion.color(spectrum, ws, method, mapping))
+                        .collect();
+                    self.colors = Some((key, colors));
+                }
+                self.swatches_ui(ctx, ui);
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Name");
+                    ui.text_edit_singleline(&mut self.name);
+                });
+                match &self.status {
+                    Ok(msg) => ui.colored_label(Color32::GREEN, msg),
+                    Err(msg) => ui.colored_label(Color32::RED, msg),
+                };
+                let can_add = !self.spectra.is_empty() && !self.name.is_empty();
+                if ui
+                    .add_enabled(can_add, Button::new("add palette"))
+                    .on_hover_cursor(CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    let mut palette = Palette::default();
+                    for (spectrum, color) in self.spectra.iter().zip(self.cached_colors()) {
+                        palette.add_entry(PaletteEntry::named(*color, &spectrum.name));
+                    }
+                    let palette = NamedPalette {
+                        name: self.name.clone(),
+                        palette,
+                    };
+                    self.status = if ctx.app.palettes.add(palette) {
+                        Ok(format!("created palette {}", self.name))
+                    } else {
+                        Err(format!("palette {} already exists", self.name))
+                    };
+                }
+            });
+
+        if !is_open {
+            self.is_open = false;
+        }
+    }
+
+    fn conversion_ui(&mut self, ui: &mut Ui) {
+        let conversion = &mut self.conversion;
+        Grid::new("spectral_conversion").show(ui, |ui| {
+            ui.label("Spectra");
+            ComboBox::from_id_salt("spectrum_kind")
+                .selected_text(conversion.kind.as_ref())
+                .show_ui(ui, |ui| {
+                    for kind in SpectrumKind::ALL {
+                        ui.selectable_value(&mut conversion.kind, kind, kind.as_ref());
+                    }
+                });
+            ui.end_row();
+            ui.label("Observer");
+            ComboBox::from_id_salt("spectral_observer")
+                .selected_text(conversion.observer.as_ref())
+                .show_ui(ui, |ui| {
+                    for observer in Observer::ALL {
+                        ui.selectable_value(&mut conversion.observer, observer, observer.as_ref());
+                    }
+                });
+            ui.end_row();
+            ui.add_enabled_ui(conversion.kind == SpectrumKind::Reflectance, |ui| {
+                ui.label("Illuminant");
+            });
+            ui.add_enabled_ui(conversion.kind == SpectrumKind::Reflectance, |ui| {
+                ComboBox::from_id_salt("spectral_illuminant")
+                    .selected_text(conversion.illuminant.as_ref())
+                    .show_ui(ui, |ui| {
+                        for illuminant in Illuminant::ALL {
+                            ui.selectable_value(
+                                &mut conversion.illuminant,
+                                illuminant,
+                                illuminant.as_ref(),
+                            );
+                        }
+                    })
+                    .response
+                    .on_hover_text(
+                        "A, D50, D55, D65, D75 and E use their spectral power distribution, \
+                         other illuminants are approximated by adapting from E",
+                    );
+            });
+            ui.end_row();
+        });
+    }
+
+    fn cached_colors(&self) -> &[Color] {
+        self.colors
+            .as_ref()
+            .map(|(_, colors)| colors.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn swatches_ui(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        let colors = self.cached_colors();
+        if colors.is_empty() {
+            return;
+        }
+        ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+            Grid::new("spectral_swatches")
+                .spacing((2., 2.))
+                .show(ui, |ui| {
+                    for (i, (spectrum, color)) in self.spectra.iter().zip(colors).enumerate() {
+                        let cb = ColorBox::builder()
+                            .size((SWATCH_SIZE, SWATCH_SIZE))
+                            .color(*color)
+                            .hover_help(format!("{}\n{COLORBOX_PICK_TOOLTIP}", spectrum.name))
+                            .build();
+                        cb.display(ctx, ui);
+                        if (i + 1) % SWATCH_COLUMNS == 0 {
+                            ui.end_row();
+                        }
+                    }
+                });
+        });
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{
+    app::window::{self, WINDOW_X_OFFSET, WINDOW_Y_OFFSET},
+    color::{NamedPalette, TonalScale},
+    context::FrameCtx,
+    ui::colorbox::{COLORBOX_PICK_TOOLTIP, ColorBox},
+};
+
+use egui::{Color32, ComboBox, CursorIcon, Grid, RichText, Window};
+
+const PREVIEW_COLOR_SIZE: f32 = 32.;
+
+#[derive(Debug)]
+pub struct TonalScaleWindow {
+    pub is_open: bool,
+    pub scale: TonalScale,
+    /// Use the seed color itself for the step closest to it
+    pub snap_seed: bool,
+    /// Name of the created palette, the nearest named color of the seed is used when empty
+    pub name: String,
+    status: Result<String, String>,
+}
+
+impl Default for TonalScaleWindow {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            scale: TonalScale::default(),
+            snap_seed: true,
+            name: String::new(),
+            status: Ok("".into()),
+        }
+    }
+}
+
+impl TonalScaleWindow {
+    pub fn toggle_window(&mut self) {
+        self.is_open = !self.is_open;
+    }
+
+    pub fn display(&mut self, ctx: &mut FrameCtx<'_>) {
+        if !self.is_open {
+            return;
+        }
+        let offset = ctx.egui.style().spacing.slider_width * WINDOW_X_OFFSET;
+        let mut is_open = true;
+        let is_dark_mode = ctx.egui.style().visuals.dark_mode;
+        let seed = ctx.app.picker.current_color;
+
+        Window::new("Tonal scale")
+            .collapsible(false)
+            .frame(window::default_frame(is_dark_mode))
+            .default_pos((offset, WINDOW_Y_OFFSET))
+            .open(&mut is_open)
+            .show(ctx.egui, |ui| {
+                window::apply_default_style(ui, is_dark_mode);
+                ui.horizontal(|ui| {
+                    ComboBox::from_label("scale")
+                        .selected_text(self.scale.as_ref())
+                        .show_ui(ui, |ui| {
+                            for scale in TonalScale::ALL {
+                                ui.selectable_value(&mut self.scale, scale, scale.as_ref());
+                            }
+                        });
+                    ui.checkbox(&mut self.snap_seed, "snap seed")
+                        .on_hover_text("Use the current color for the step closest to it");
+                });
+                let default_name = seed.nearest_name().name;
+                ui.horizontal(|ui| {
+                    ui.label("Name");
+                    ui.add(egui::TextEdit::singleline(&mut self.name).hint_text(default_name));
+                });
+
+                let steps = seed.tonal_scale(self.scale, self.snap_seed);
+                Grid::new("tonal_scale_preview")
+                    .spacing((2., 2.))
+                    .show(ui, |ui| {
+                        for (_, color) in &steps {
+                            let cb = ColorBox::builder()
+                                .size((PREVIEW_COLOR_SIZE, PREVIEW_COLOR_SIZE))
+                                .color(*color)
+                                .hover_help(COLORBOX_PICK_TOOLTIP)
+                                .build();
+                            cb.display(ctx, ui);
+                        }
+                        ui.end_row();
+                        for (step, _) in &steps {
+                            ui.label(RichText::new(step.to_string()).small());
+                        }
+                    });
+
+                match &self.status {
+                    Ok(msg) => ui.colored_label(Color32::GREEN, msg),
+                    Err(msg) => ui.colored_label(Color32::RED, msg),
+                };
+                if ui
+                    .button("add palette")
+                    .on_hover_cursor(CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    let name = if self.name.is_empty() {
+                        default_name
+                    } else {
+                        &self.name
+                    };
+                    let palette =
+                        NamedPalette::tonal_scale(name, &seed, self.scale, self.snap_seed);
+                    self.status = if ctx.app.palettes.add(palette) {
+                        Ok(format!("created palette {name}"))
+                    } else {
+                        Err(format!("palette {name} already exists"))
+                    };
+                }
+            });
+
+        if !is_open {
+            self.is_open = false;
+        }
+    }
+}
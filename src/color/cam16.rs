@@ -0,0 +1,382 @@
+use crate::{
+    color::{CIE_E, CIE_K, Illuminant, Xyz},
+    math::{Matrix1x3, Matrix3},
+};
+
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+/// CAM16 matrix from XYZ to sharpened cone responses.
+#[rustfmt::skip]
+const M16: Matrix3 = Matrix3([
+    [ 0.401288, 0.650173, -0.051461],
+    [-0.250268, 1.204414,  0.045854],
+    [-0.002079, 0.048952,  0.953127],
+]);
+/// Compressed cone responses approach this value for infinitely bright stimuli.
+const COMPRESSION_LIMIT: f32 = 400.;
+static M16_INVERSE: LazyLock<Matrix3> =
+    LazyLock::new(|| M16.inverse().expect("CAM16 matrix is invertible"));
+
+/// Relative luminance of the surround of a stimulus.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum Surround {
+    /// Viewing surface colors, like prints or a screen in a lit room
+    #[default]
+    Average,
+    /// Viewing a television or a screen in a dim room
+    Dim,
+    /// Viewing a projector in a dark room
+    Dark,
+}
+
+impl Surround {
+    pub const ALL: [Surround; 3] = [Surround::Average, Surround::Dim, Surround::Dark];
+
+    /// Returns the degree of adaptation factor F, the impact of surround c and the chromatic
+    /// induction factor Nc.
+    fn factors(&self) -> (f32, f32, f32) {
+        match self {
+            Surround::Average => (1., 0.69, 1.),
+            Surround::Dim => (0.9, 0.59, 0.9),
+            Surround::Dark => (0.8, 0.525, 0.8),
+        }
+    }
+}
+
+impl AsRef<str> for Surround {
+    fn as_ref(&self) -> &str {
+        match self {
+            Surround::Average => "average",
+            Surround::Dim => "dim",
+            Surround::Dark => "dark",
+        }
+    }
+}
+
+/// Viewing conditions under which CAM16 predicts the appearance of a color.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ViewingConditions {
+    /// Luminance of the adapting field in cd/m²
+    pub adapting_luminance: f32,
+    /// Relative luminance of the background in the range 0.0 ..= 100.0
+    pub background_luminance: f32,
+    pub surround: Surround,
+}
+
+impl Default for ViewingConditions {
+    /// Conditions used by Material HCT, a mid gray background with L* 50 lit at 200 lux.
+    fn default() -> Self {
+        let background = y_from_lstar(50.) * 100.;
+        Self {
+            adapting_luminance: 200. / std::f32::consts::PI * background / 100.,
+            background_luminance: background,
+            surround: Surround::Average,
+        }
+    }
+}
+
+/// Returns the relative luminance in the range 0.0 ..= 1.0 of CIE L* `lstar`.
+pub fn y_from_lstar(lstar: f32) -> f32 {
+    let fy = (lstar + 16.) / 116.;
+    if lstar > CIE_E * CIE_K {
+        fy.powi(3)
+    } else {
+        lstar / CIE_K
+    }
+}
+
+/// Returns CIE L* of the relative luminance `y` in the range 0.0 ..= 1.0.
+pub fn lstar_from_y(y: f32) -> f32 {
+    if y > CIE_E {
+        116. * y.cbrt() - 16.
+    } else {
+        y * CIE_K
+    }
+}
+
+/// Parameters of the model derived from viewing conditions and the adopted white.
+pub(super) struct Environment {
+    /// Luminance level adaptation factor
+    fl: f32,
+    n: f32,
+    z: f32,
+    nbb: f32,
+    c: f32,
+    nc: f32,
+    /// Achromatic response of the white
+    aw: f32,
+    /// Cone response gains discounting the adopted white
+    rgb_d: [f32; 3],
+}
+
+impl Environment {
+    pub(super) fn new(white: Illuminant, conditions: &ViewingConditions) -> Self {
+        let (f, c, nc) = conditions.surround.factors();
+        let la = conditions.adapting_luminance.max(0.);
+        let white = white.xyz();
+        let white = [white.x() * 100., white.y() * 100., white.z() * 100.];
+        let yw = white[1];
+
+        let k = 1. / (5. * la + 1.);
+        let k4 = k.powi(4);
+        let fl = 0.2 * k4 * 5. * la + 0.1 * (1. - k4).powi(2) * (5. * la).cbrt();
+        let n = (conditions.background_luminance / yw).clamp(1e-4, 1.);
+        let z = 1.48 + n.sqrt();
+        let nbb = 0.725 / n.powf(0.2);
+        let d = (f * (1. - (-(la + 42.) / 92.).exp() / 3.6)).clamp(0., 1.);
+
+        let rgb_w = M16 * Matrix1x3::from(white);
+        let rgb_d = [0, 1, 2].map(|i| d * yw / rgb_w[i] + 1. - d);
+        let [ra, ga, ba] = [0, 1, 2].map(|i| compress(fl, rgb_d[i] * rgb_w[i]));
+        let aw = (2. * ra + ga + ba / 20.) * nbb;
+
+        Self {
+            fl,
+            n,
+            z,
+            nbb,
+            c,
+            nc,
+            aw,
+            rgb_d,
+        }
+    }
+
+    /// Returns the factor converting chroma to colorfulness.
+    fn colorfulness_scale(&self) -> f32 {
+        self.fl.powf(0.25)
+    }
+
+    /// Returns lightness J, chroma C and hue angle h in degrees of a color.
+    fn jch(&self, xyz: Xyz) -> (f32, f32, f32) {
+        let rgb = M16 * Matrix1x3::from([xyz.x() * 100., xyz.y() * 100., xyz.z() * 100.]);
+        let [ra, ga, ba] = [0, 1, 2].map(|i| compress(self.fl, self.rgb_d[i] * rgb[i]));
+
+        let a = ra - 12. * ga / 11. + ba / 11.;
+        let b = (ra + ga - 2. * ba) / 9.;
+        let h = b.atan2(a).to_degrees().rem_euclid(360.);
+
+        let achromatic = (2. * ra + ga + ba / 20.) * self.nbb;
+        if achromatic <= 0. {
+            return (0., 0., h);
+        }
+        let j = 100. * (achromatic / self.aw).powf(self.c * self.z);
+
+        let et = 0.25 * ((h.to_radians() + 2.).cos() + 3.8);
+        // The offset accounts for the 0.1 added to each compressed response in CAM16
+        let t = 50000. / 13. * self.nc * self.nbb * et * a.hypot(b)
+            / (ra + ga + 21. * ba / 20. + 0.305);
+        let c = t.powf(0.9) * (j / 100.).sqrt() * (1.64 - 0.29f32.powf(self.n)).powf(0.73);
+        (j, c, h)
+    }
+
+    /// Returns the compressed cone responses of a color with lightness J, chroma C and hue angle
+    /// h in degrees.
+    fn responses(&self, j: f32, c: f32, h: f32) -> [f32; 3] {
+        let alpha = c.max(0.) / (j / 100.).sqrt();
+        let t = (alpha / (1.64 - 0.29f32.powf(self.n)).powf(0.73)).powf(1. / 0.9);
+        let (sin, cos) = h.to_radians().sin_cos();
+
+        let et = 0.25 * ((h.to_radians() + 2.).cos() + 3.8);
+        let achromatic = self.aw * (j / 100.).powf(1. / (self.c * self.z));
+        let p1 = 50000. / 13. * self.nc * self.nbb * et;
+        let p2 = achromatic / self.nbb;
+        let gamma = 23. * (p2 + 0.305) * t / (23. * p1 + 11. * t * cos + 108. * t * sin);
+        let (a, b) = (gamma * cos, gamma * sin);
+
+        let ra = (460. * p2 + 451. * a + 288. * b) / 1403.;
+        let ga = (460. * p2 - 891. * a - 261. * b) / 1403.;
+        let ba = (460. * p2 - 220. * a - 6300. * b) / 1403.;
+        [ra, ga, ba]
+    }
+
+    /// Whether some cone responses produce lightness J, chroma C and hue angle h in degrees.
+    pub(super) fn is_realizable(&self, j: f32, c: f32, h: f32) -> bool {
+        if j <= 0. {
+            return c <= 0.;
+        }
+        self.responses(j, c, h)
+            .iter()
+            .all(|x| x.abs() < COMPRESSION_LIMIT)
+    }
+
+    /// Returns the color with lightness J, chroma C and hue angle h in degrees.
+    pub(super) fn xyz(&self, j: f32, c: f32, h: f32) -> Xyz {
+        if j <= 0. {
+            return Xyz::new(0., 0., 0.);
+        }
+        let rgb = self.responses(j, c, h);
+        let rgb = [0, 1, 2].map(|i| decompress(self.fl, rgb[i]) / self.rgb_d[i] / 100.);
+        Xyz::from(*M16_INVERSE * Matrix1x3::from(rgb))
+    }
+}
+
+/// Applies the nonlinear response compression to a cone response.
+fn compress(fl: f32, x: f32) -> f32 {
+    let p = (fl * x.abs() / 100.).powf(0.42);
+    x.signum() * COMPRESSION_LIMIT * p / (p + 27.13)
+}
+
+fn decompress(fl: f32, x: f32) -> f32 {
+    let base = (27.13 * x.abs() / (COMPRESSION_LIMIT - x.abs())).max(0.);
+    x.signum() * 100. / fl * base.powf(1. / 0.42)
+}
+
+//####################################################################################################
+
+/// Lightness, chroma and hue of the CAM16 color appearance model.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Cam16 {
+    j: f32,
+    c: f32,
+    h: f32,
+}
+
+impl Cam16 {
+    pub fn new(j: f32, c: f32, h: f32) -> Self {
+        let j = if j.is_nan() { 0. } else { j };
+        let c = if c.is_nan() { 0. } else { c };
+        let h = if h.is_nan() { 0. } else { h };
+        Self { j, c, h }
+    }
+
+    #[inline(always)]
+    /// Returns Lightness J in the range of 0.0 ..= 100.0
+    pub fn j(&self) -> f32 {
+        self.j
+    }
+
+    #[inline(always)]
+    /// Returns Chroma C
+    pub fn c(&self) -> f32 {
+        self.c
+    }
+
+    #[inline(always)]
+    /// Returns Hue angle in the range of 0.0 ..= 360.0
+    pub fn h(&self) -> f32 {
+        self.h
+    }
+
+    /// Predicts the appearance of `xyz` relative to the `white` it is adapted to.
+    pub fn from_xyz(xyz: Xyz, white: Illuminant, conditions: &ViewingConditions) -> Self {
+        let (j, c, h) = Environment::new(white, conditions).jch(xyz);
+        Self::new(j, c, h)
+    }
+
+    pub fn to_xyz(self, white: Illuminant, conditions: &ViewingConditions) -> Xyz {
+        Environment::new(white, conditions).xyz(self.j, self.c, self.h)
+    }
+
+    /// Whether some cone responses produce this appearance. Too much chroma for the lightness
+    /// requires responses beyond the saturation of the response compression.
+    pub fn is_realizable(&self, white: Illuminant, conditions: &ViewingConditions) -> bool {
+        Environment::new(white, conditions).is_realizable(self.j, self.c, self.h)
+    }
+}
+
+/// CAM16 uniform color space, lightness J' and the a' b' coordinates of colorfulness M'.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Cam16Ucs {
+    j: f32,
+    a: f32,
+    b: f32,
+}
+
+impl Cam16Ucs {
+    pub fn new(j: f32, a: f32, b: f32) -> Self {
+        let j = if j.is_nan() { 0. } else { j };
+        let a = if a.is_nan() { 0. } else { a };
+        let b = if b.is_nan() { 0. } else { b };
+        Self { j, a, b }
+    }
+
+    #[inline(always)]
+    /// Returns Lightness J' in the range of 0.0 ..= 100.0
+    pub fn j(&self) -> f32 {
+        self.j
+    }
+
+    #[inline(always)]
+    /// Returns a' coordinate
+    pub fn a(&self) -> f32 {
+        self.a
+    }
+
+    #[inline(always)]
+    /// Returns b' coordinate
+    pub fn b(&self) -> f32 {
+        self.b
+    }
+
+    pub fn from_xyz(xyz: Xyz, white: Illuminant, conditions: &ViewingConditions) -> Self {
+        let env = Environment::new(white, conditions);
+        let (j, c, h) = env.jch(xyz);
+        let m = c * env.colorfulness_scale();
+        let m = (1. + 0.0228 * m).ln() / 0.0228;
+        let (sin, cos) = h.to_radians().sin_cos();
+        Self::new(1.7 * j / (1. + 0.007 * j), m * cos, m * sin)
+    }
+
+    pub fn to_xyz(self, white: Illuminant, conditions: &ViewingConditions) -> Xyz {
+        let env = Environment::new(white, conditions);
+        let j = self.j / (1.7 - 0.007 * self.j);
+        let m = ((0.0228 * self.a.hypot(self.b)).exp() - 1.) / 0.0228;
+        let h = self.b.atan2(self.a).to_degrees().rem_euclid(360.);
+        env.xyz(j, m / env.colorfulness_scale(), h)
+    }
+}
+
+//####################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{CIEColor, Rgb, RgbWorkingSpace};
+
+    #[test]
+    fn converts_srgb() {
+        macro_rules! test_case {
+            ($r:expr, $g:expr, $b:expr; $j:expr, $c:expr, $h:expr) => {
+                let conditions = ViewingConditions::default();
+                let xyz = Xyz::from_rgb(Rgb::new_scaled($r, $g, $b), RgbWorkingSpace::SRGB);
+                let got = Cam16::from_xyz(xyz, Illuminant::D65, &conditions);
+                assert!((got.j() - $j).abs() < 0.1, "{got:?}");
+                assert!((got.c() - $c).abs() < 0.1, "{got:?}");
+                assert!((got.h() - $h).abs() < 0.1, "{got:?}");
+
+                let back = got.to_xyz(Illuminant::D65, &conditions);
+                assert!((back.x() - xyz.x()).abs() < 1e-3, "{back:?} {xyz:?}");
+                assert!((back.y() - xyz.y()).abs() < 1e-3, "{back:?} {xyz:?}");
+                assert!((back.z() - xyz.z()).abs() < 1e-3, "{back:?} {xyz:?}");
+            };
+        }
+
+        // Reference values from Material color utilities
+        test_case!(255, 0, 0; 46.445, 113.357, 27.408);
+        test_case!(0, 255, 0; 79.332, 108.410, 142.139);
+        test_case!(0, 0, 255; 25.466, 87.230, 282.788);
+        test_case!(255, 255, 255; 100., 2.869, 209.492);
+    }
+
+    #[test]
+    fn ucs_roundtrip() {
+        let conditions = ViewingConditions {
+            adapting_luminance: 318.31,
+            background_luminance: 20.,
+            surround: Surround::Dim,
+        };
+        for white in [Illuminant::D65, Illuminant::D50] {
+            let xyz = Xyz::new(0.3, 0.2, 0.1);
+            let ucs = Cam16Ucs::from_xyz(xyz, white, &conditions);
+            let back = ucs.to_xyz(white, &conditions);
+            assert!((back.x() - xyz.x()).abs() < 1e-4, "{back:?}");
+            assert!((back.y() - xyz.y()).abs() < 1e-4, "{back:?}");
+            assert!((back.z() - xyz.z()).abs() < 1e-4, "{back:?}");
+        }
+
+        let black = Cam16Ucs::from_xyz(Xyz::new(0., 0., 0.), Illuminant::D65, &conditions);
+        assert_eq!((black.j(), black.a(), black.b()), (0., 0., 0.));
+    }
+}
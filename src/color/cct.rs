@@ -0,0 +1,228 @@
+use crate::color::{
+    WhitePoint, Xyz,
+    observer::{CMF_END, CMF_START, Observer},
+    xyY,
+};
+
+use serde::{Deserialize, Serialize};
+use std::{ops::RangeInclusive, sync::LazyLock};
+
+/// Range of temperatures in kelvin offered by the temperature slider.
+pub const KELVIN_RANGE: RangeInclusive<f32> = 1000.0..=25000.;
+/// Range of distances from the Planckian locus offered by the Duv slider.
+pub const DUV_RANGE: RangeInclusive<f32> = -0.05..=0.05;
+
+/// Second radiation constant in nanometer kelvin.
+const C2: f64 = 1.4388e7;
+/// Wavelength step in nanometers used to integrate the blackbody spectrum.
+const INTEGRATION_STEP: f64 = 5.;
+/// Temperatures of the Planckian locus lookup table grow by this ratio from `TABLE_START`.
+const TABLE_RATIO: f64 = 1.01;
+const TABLE_START: f64 = 1000.;
+const TABLE_END: f64 = 100_000.;
+/// Largest Duv for which the triangular solution of Ohno's method is used.
+const TRIANGULAR_MAX_DUV: f64 = 0.002;
+
+/// CIE 1960 uv coordinates of the Planckian locus at temperatures growing by `TABLE_RATIO`.
+static PLANCKIAN_TABLE: LazyLock<Vec<(f64, f64, f64)>> = LazyLock::new(|| {
+    std::iter::successors(Some(TABLE_START), |t| Some(t * TABLE_RATIO))
+        .take_while(|t| *t <= TABLE_END)
+        .map(|t| {
+            let (u, v) = planckian_uv(t);
+            (t, u, v)
+        })
+        .collect()
+});
+
+/// Locus of light sources used to pick a color by its temperature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum CctLocus {
+    /// Light emitted by a blackbody radiator
+    #[default]
+    Blackbody,
+    /// CIE daylight, defined from 4000 K
+    Daylight,
+}
+
+impl CctLocus {
+    pub const ALL: [CctLocus; 2] = [CctLocus::Blackbody, CctLocus::Daylight];
+
+    /// Returns the xy chromaticity of a light source with temperature `kelvin` moved `duv`
+    /// perpendicularly away from the Planckian locus, positive values towards green.
+    pub fn xy(&self, kelvin: f32, duv: f32) -> (f32, f32) {
+        let t = kelvin as f64;
+        let (u, v) = match self {
+            CctLocus::Blackbody => planckian_uv(t),
+            CctLocus::Daylight => {
+                let white = WhitePoint::from_cct(kelvin.max(4000.));
+                xy_to_uv(white.x as f64, white.y as f64)
+            }
+        };
+        let (u1, v1) = planckian_uv(t * 0.999);
+        let (u2, v2) = planckian_uv(t * 1.001);
+        let length = (u2 - u1).hypot(v2 - v1);
+        // Temperatures grow towards lower u and v so the normal above the locus is on the right
+        let (nu, nv) = ((v2 - v1) / length, -(u2 - u1) / length);
+        let duv = duv as f64;
+        let (x, y) = uv_to_xy(u + nu * duv, v + nv * duv);
+        (x as f32, y as f32)
+    }
+}
+
+impl AsRef<str> for CctLocus {
+    fn as_ref(&self) -> &str {
+        match self {
+            CctLocus::Blackbody => "blackbody",
+            CctLocus::Daylight => "daylight",
+        }
+    }
+}
+
+/// Correlated color temperature of a color and its distance from the Planckian locus.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Cct {
+    pub kelvin: f32,
+    /// Distance from the Planckian locus in the CIE 1960 uv plane, positive above the locus
+    pub duv: f32,
+}
+
+fn xy_to_uv(x: f64, y: f64) -> (f64, f64) {
+    let d = -2. * x + 12. * y + 3.;
+    (4. * x / d, 6. * y / d)
+}
+
+fn uv_to_xy(u: f64, v: f64) -> (f64, f64) {
+    let d = 2. * u - 8. * v + 4.;
+    (3. * u / d, 2. * v / d)
+}
+
+/// Returns the relative spectral radiance of a blackbody at temperature `t` in kelvin at
+/// `wavelength` in nanometers.
+pub fn planck_radiance(wavelength: f64, t: f64) -> f64 {
+    wavelength.powi(-5) / ((C2 / (wavelength * t)).exp() - 1.)
+}
+
+/// Returns the CIE 1960 uv chromaticity of a blackbody at temperature `t` in kelvin.
+fn planckian_uv(t: f64) -> (f64, f64) {
+    let steps = ((CMF_END - CMF_START) as f64 / INTEGRATION_STEP) as u32;
+    let (x, y, z) = (0..=steps)
+        .map(|i| {
+            let wavelength = CMF_START as f64 + i as f64 * INTEGRATION_STEP;
+            let radiance = planck_radiance(wavelength, t);
+            let cmf = Observer::Cie1931.cmf(wavelength as f32);
+            (
+                radiance * cmf.x() as f64,
+                radiance * cmf.y() as f64,
+                radiance * cmf.z() as f64,
+            )
+        })
+        .fold((0., 0., 0.), |acc, c| {
+            (acc.0 + c.0, acc.1 + c.1, acc.2 + c.2)
+        });
+    let d = x + 15. * y + 3. * z;
+    (4. * x / d, 6. * y / d)
+}
+
+impl Xyz {
+    /// Returns the correlated color temperature in kelvin using McCamy's cubic approximation,
+    /// which is only accurate close to the Planckian locus between about 2000 and 12500 K.
+    pub fn cct_mccamy(&self) -> Option<f32> {
+        let xyy = xyY::from(*self);
+        if !xyy.x().is_finite() || self.y() <= 0. {
+            return None;
+        }
+        let n = (xyy.x() - 0.3320) / (xyy.y() - 0.1858);
+        Some(-449. * n.powi(3) + 3525. * n.powi(2) - 6823.3 * n + 5520.33)
+    }
+
+    /// Returns the correlated color temperature and Duv of this color using the combined
+    /// triangular and parabolic method from Ohno, "Practical Use and Calculation of CCT and Duv"
+    /// (2014). Returns `None` for black and for temperatures outside of 1000 to 100000 K.
+    pub fn cct(&self) -> Option<Cct> {
+        if self.x() + self.y() + self.z() <= 0. {
+            return None;
+        }
+        let xyy = xyY::from(*self);
+        let (u, v) = xy_to_uv(xyy.x() as f64, xyy.y() as f64);
+
+        let table = &*PLANCKIAN_TABLE;
+        let distance = |i: usize| {
+            let (_, tu, tv) = table[i];
+            (u - tu).hypot(v - tv)
+        };
+        let m = (0..table.len())
+            .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+            .filter(|m| (1..table.len() - 1).contains(m))?;
+
+        let (t0, u0, v0) = table[m - 1];
+        let (t1, _, v1) = table[m];
+        let (t2, u2, v2) = table[m + 1];
+        let (d0, d1, d2) = (distance(m - 1), distance(m), distance(m + 1));
+
+        // Triangular solution
+        let l = (u2 - u0).hypot(v2 - v0);
+        let x = (d0 * d0 - d2 * d2 + l * l) / (2. * l);
+        let kelvin = t0 + (t2 - t0) * x / l;
+        let sign = (v - (v0 + (v2 - v0) * x / l)).signum();
+        let duv = (d0 * d0 - x * x).max(0.).sqrt() * sign;
+        if duv.abs() < TRIANGULAR_MAX_DUV {
+            return Some(Cct {
+                kelvin: kelvin as f32,
+                duv: duv as f32,
+            });
+        }
+
+        // Parabolic solution
+        let x = (t2 - t1) * (t0 - t2) * (t1 - t0);
+        let a = (t0 * (d2 - d1) + t1 * (d0 - d2) + t2 * (d1 - d0)) / x;
+        let b = -(t0 * t0 * (d2 - d1) + t1 * t1 * (d0 - d2) + t2 * t2 * (d1 - d0)) / x;
+        let c =
+            -(d0 * (t2 - t1) * t1 * t2 + d1 * (t0 - t2) * t0 * t2 + d2 * (t1 - t0) * t0 * t1) / x;
+        let kelvin = -b / (2. * a);
+        let sign = (v - v1).signum();
+        Some(Cct {
+            kelvin: (kelvin * 0.99991) as f32,
+            duv: ((a * kelvin * kelvin + b * kelvin + c) * sign) as f32,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Illuminant;
+
+    #[test]
+    fn cct_of_illuminants() {
+        let d65 = Illuminant::D65.xyz().cct().unwrap();
+        assert!((d65.kelvin - 6504.).abs() < 15., "{d65:?}");
+        assert!((d65.duv - 0.0032).abs() < 5e-4, "{d65:?}");
+
+        let a = Illuminant::A.xyz().cct().unwrap();
+        assert!((a.kelvin - 2856.).abs() < 10., "{a:?}");
+        assert!(a.duv.abs() < 5e-4, "{a:?}");
+
+        let mccamy = Illuminant::D65.xyz().cct_mccamy().unwrap();
+        assert!((mccamy - 6505.).abs() < 5., "{mccamy}");
+        assert_eq!(Xyz::new(0., 0., 0.).cct(), None);
+    }
+
+    #[test]
+    fn locus_roundtrip() {
+        for (kelvin, duv) in [(1500., 0.), (3000., 0.), (5000., 0.01), (9000., -0.02)] {
+            let (x, y) = CctLocus::Blackbody.xy(kelvin, duv);
+            let cct = Xyz::from(xyY::new(x, y, 1.)).cct().unwrap();
+            assert!(
+                (cct.kelvin - kelvin).abs() / kelvin < 2e-3,
+                "{kelvin} {cct:?}"
+            );
+            assert!((cct.duv - duv).abs() < 5e-4, "{duv} {cct:?}");
+        }
+
+        let (x, y) = CctLocus::Daylight.xy(6504., 0.);
+        assert!(
+            (x - 0.3127).abs() < 1e-3 && (y - 0.3291).abs() < 1e-3,
+            "{x} {y}"
+        );
+    }
+}
@@ -2,15 +2,57 @@ use crate::math::Matrix3;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize, Default)]
 pub enum ChromaticAdaptationMethod {
     #[default]
     Bradford,
     VonKries,
     XYZScaling,
+    /// CIECAM02 transform, `degree` is the degree of adaptation D in `0..=1`, full adaptation if
+    /// not set
+    Cat02 {
+        degree: Option<f32>,
+    },
+    /// CAM16 transform, `degree` is the degree of adaptation D in `0..=1`, full adaptation if not
+    /// set
+    Cat16 {
+        degree: Option<f32>,
+    },
 }
 
 impl ChromaticAdaptationMethod {
+    pub const ALL: [ChromaticAdaptationMethod; 5] = [
+        ChromaticAdaptationMethod::Bradford,
+        ChromaticAdaptationMethod::VonKries,
+        ChromaticAdaptationMethod::XYZScaling,
+        ChromaticAdaptationMethod::Cat02 { degree: None },
+        ChromaticAdaptationMethod::Cat16 { degree: None },
+    ];
+
+    /// Returns the degree of adaptation D, 1 meaning the colors are fully adapted to the
+    /// destination white.
+    pub fn degree(&self) -> f32 {
+        match self {
+            ChromaticAdaptationMethod::Cat02 { degree: Some(d) }
+            | ChromaticAdaptationMethod::Cat16 { degree: Some(d) } => d.clamp(0., 1.),
+            _ => 1.,
+        }
+    }
+
+    /// Returns a mutable reference to the degree of adaptation if this method supports it.
+    pub fn degree_mut(&mut self) -> Option<&mut Option<f32>> {
+        match self {
+            ChromaticAdaptationMethod::Cat02 { degree }
+            | ChromaticAdaptationMethod::Cat16 { degree } => Some(degree),
+            _ => None,
+        }
+    }
+
+    /// Whether both methods use the same transform regardless of the degree of adaptation.
+    pub fn same_method(&self, other: &ChromaticAdaptationMethod) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
     #[rustfmt::skip]
     pub fn adaptation_matrix(&self) -> Matrix3 {
         match &self {
@@ -35,6 +77,20 @@ impl ChromaticAdaptationMethod {
                     [0., 0., 1.],
                 ]
             },
+            ChromaticAdaptationMethod::Cat02 { .. } => {
+                [
+                    [ 0.7328, 0.4296, -0.1624],
+                    [-0.7036, 1.6975,  0.0061],
+                    [ 0.003 , 0.0136,  0.9834],
+                ]
+            },
+            ChromaticAdaptationMethod::Cat16 { .. } => {
+                [
+                    [ 0.401288, 0.650173, -0.051461],
+                    [-0.250268, 1.204414,  0.045854],
+                    [-0.002079, 0.048952,  0.953127],
+                ]
+            },
         }.into()
     }
 }
@@ -45,6 +101,8 @@ impl AsRef<str> for ChromaticAdaptationMethod {
             ChromaticAdaptationMethod::Bradford => "Bradford",
             ChromaticAdaptationMethod::VonKries => "Von Kries",
             ChromaticAdaptationMethod::XYZScaling => "XYZ Scaling",
+            ChromaticAdaptationMethod::Cat02 { .. } => "CAT02",
+            ChromaticAdaptationMethod::Cat16 { .. } => "CAT16",
         }
     }
 }
@@ -0,0 +1,132 @@
+use crate::color::{Rgb, RgbWorkingSpace, Xyz, observer::Observer, xyY};
+
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+/// Wavelengths in nanometers of the spectral locus. Above 700 nm the chromaticity of
+/// monochromatic light barely changes.
+pub const LOCUS_WAVELENGTHS: RangeInclusive<u32> = 380..=700;
+/// Distance in nanometers between points of the spectral locus.
+pub const LOCUS_STEP: usize = 5;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum ChromaticityDiagram {
+    /// CIE 1931 xy
+    #[default]
+    Xy,
+    /// CIE 1976 u'v', perceptually more uniform than xy
+    UvPrime,
+}
+
+impl ChromaticityDiagram {
+    pub const ALL: [ChromaticityDiagram; 2] =
+        [ChromaticityDiagram::Xy, ChromaticityDiagram::UvPrime];
+
+    /// Returns the coordinates of the xy chromaticity in this diagram.
+    pub fn project(&self, x: f32, y: f32) -> (f32, f32) {
+        match self {
+            ChromaticityDiagram::Xy => (x, y),
+            ChromaticityDiagram::UvPrime => {
+                let d = -2. * x + 12. * y + 3.;
+                (4. * x / d, 9. * y / d)
+            }
+        }
+    }
+
+    /// Returns the xy chromaticity of the coordinates in this diagram.
+    pub fn unproject(&self, a: f32, b: f32) -> (f32, f32) {
+        match self {
+            ChromaticityDiagram::Xy => (a, b),
+            ChromaticityDiagram::UvPrime => {
+                let d = 6. * a - 16. * b + 12.;
+                (9. * a / d, 4. * b / d)
+            }
+        }
+    }
+
+    /// Returns the largest values of both axes needed to show the whole spectral locus.
+    pub fn max_coordinates(&self) -> (f32, f32) {
+        match self {
+            ChromaticityDiagram::Xy => (0.8, 0.9),
+            ChromaticityDiagram::UvPrime => (0.65, 0.6),
+        }
+    }
+
+    /// Returns the chromaticity of `xyz` in this diagram, `None` for black.
+    pub fn coordinates(&self, xyz: Xyz) -> Option<(f32, f32)> {
+        if xyz.x() + xyz.y() + xyz.z() <= 0. {
+            return None;
+        }
+        let xyy = xyY::from(xyz);
+        Some(self.project(xyy.x(), xyy.y()))
+    }
+}
+
+impl AsRef<str> for ChromaticityDiagram {
+    fn as_ref(&self) -> &str {
+        match self {
+            ChromaticityDiagram::Xy => "CIE 1931 xy",
+            ChromaticityDiagram::UvPrime => "CIE 1976 u'v'",
+        }
+    }
+}
+
+/// Returns the brightest color with the xy chromaticity in `ws`. Negative components of
+/// chromaticities outside of the working space gamut are clipped.
+pub fn brightest_rgb(x: f32, y: f32, ws: RgbWorkingSpace) -> Rgb {
+    let [r, g, b] = Xyz::from(xyY::new(x, y, 1.))
+        .linear_rgb(ws)
+        .map(|c| c.max(0.));
+    let max = r.max(g).max(b).max(f32::EPSILON);
+    ws.compand_channels(Rgb::new(r / max, g / max, b / max))
+}
+
+/// Returns the wavelengths and xy chromaticities of monochromatic light along the visible
+/// spectrum.
+pub fn spectral_locus() -> Vec<(u32, f32, f32)> {
+    LOCUS_WAVELENGTHS
+        .step_by(LOCUS_STEP)
+        .map(|wavelength| {
+            let xyy = xyY::from(Observer::Cie1931.cmf(wavelength as f32));
+            (wavelength, xyy.x(), xyy.y())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Illuminant;
+
+    #[test]
+    fn locus_matches_cie_table() {
+        let locus = spectral_locus();
+        for (wavelength, x, y) in [
+            (450, 0.1566, 0.0177),
+            (520, 0.0743, 0.8338),
+            (560, 0.3731, 0.6245),
+            (600, 0.6270, 0.3725),
+        ] {
+            let (_, got_x, got_y) = locus.iter().find(|(w, _, _)| *w == wavelength).unwrap();
+            assert!((got_x - x).abs() < 1e-3, "{wavelength} {got_x} {x}");
+            assert!((got_y - y).abs() < 1e-3, "{wavelength} {got_y} {y}");
+        }
+    }
+
+    #[test]
+    fn uv_prime_coordinates() {
+        let d65 = ChromaticityDiagram::UvPrime
+            .coordinates(Illuminant::D65.xyz())
+            .unwrap();
+        assert!((d65.0 - 0.1978).abs() < 1e-3, "{d65:?}");
+        assert!((d65.1 - 0.4683).abs() < 1e-3, "{d65:?}");
+
+        let (x, y) = ChromaticityDiagram::UvPrime.unproject(d65.0, d65.1);
+        assert!((x - 0.3127).abs() < 1e-3);
+        assert!((y - 0.329).abs() < 1e-3);
+        assert_eq!(
+            ChromaticityDiagram::Xy.coordinates(Xyz::new(0., 0., 0.)),
+            None
+        );
+    }
+}
@@ -0,0 +1,445 @@
+use crate::color::{
+    Color, Gradient, Lab, LchAB, NamedPalette, Oklab, Oklch, Palette, Rgb,
+    difference::{from_reference_lab, reference_lab},
+};
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+/// Number of GIMP gradient segments used to approximate a single stop to stop transition in
+/// spaces other than sRGB, which is the only one GIMP can blend in.
+const GGR_SUBDIVISIONS: usize = 16;
+
+/// Hues of colors with a chroma below this value are ignored when interpolating.
+const POWERLESS_HUE_CHROMA: f32 = 1e-4;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum InterpolationSpace {
+    #[default]
+    Srgb,
+    LinearRgb,
+    Lab,
+    LchShorter,
+    LchLonger,
+    Oklab,
+    Oklch,
+}
+
+impl InterpolationSpace {
+    pub const ALL: [InterpolationSpace; 7] = [
+        InterpolationSpace::Srgb,
+        InterpolationSpace::LinearRgb,
+        InterpolationSpace::Lab,
+        InterpolationSpace::LchShorter,
+        InterpolationSpace::LchLonger,
+        InterpolationSpace::Oklab,
+        InterpolationSpace::Oklch,
+    ];
+
+    /// Returns the CSS color interpolation method matching this space.
+    pub fn css_method(&self) -> Option<&'static str> {
+        match self {
+            InterpolationSpace::Srgb => None,
+            InterpolationSpace::LinearRgb => Some("srgb-linear"),
+            InterpolationSpace::Lab => Some("lab"),
+            InterpolationSpace::LchShorter => Some("lch"),
+            InterpolationSpace::LchLonger => Some("lch longer hue"),
+            InterpolationSpace::Oklab => Some("oklab"),
+            InterpolationSpace::Oklch => Some("oklch"),
+        }
+    }
+
+    /// Returns the color between `start` and `end` at `t` in the range 0.0 ..= 1.0.
+    pub fn interpolate(&self, start: &Color, end: &Color, t: f32) -> Color {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        match self {
+            InterpolationSpace::Srgb => {
+                let (a, b) = (start.rgb(), end.rgb());
+                Rgb::new(lerp(a.r(), b.r()), lerp(a.g(), b.g()), lerp(a.b(), b.b())).into()
+            }
+            InterpolationSpace::LinearRgb => {
+                let a = start.rgb().inverse_srgb_compand();
+                let b = end.rgb().inverse_srgb_compand();
+                Rgb::new(lerp(a.r(), b.r()), lerp(a.g(), b.g()), lerp(a.b(), b.b()))
+                    .srgb_compand()
+                    .into()
+            }
+            InterpolationSpace::Lab => {
+                let (a, b) = (reference_lab(start), reference_lab(end));
+                from_reference_lab(Lab::new(
+                    lerp(a.l(), b.l()),
+                    lerp(a.a(), b.a()),
+                    lerp(a.b(), b.b()),
+                ))
+            }
+            InterpolationSpace::LchShorter | InterpolationSpace::LchLonger => {
+                let a = LchAB::from(reference_lab(start));
+                let b = LchAB::from(reference_lab(end));
+                let longer = *self == InterpolationSpace::LchLonger;
+                let h = lerp_hue((a.c(), a.h()), (b.c(), b.h()), t, longer);
+                from_reference_lab(LchAB::new(lerp(a.l(), b.l()), lerp(a.c(), b.c()), h).into())
+            }
+            InterpolationSpace::Oklab => {
+                let (a, b) = (start.oklab(), end.oklab());
+                Oklab::new(lerp(a.l(), b.l()), lerp(a.a(), b.a()), lerp(a.b(), b.b()))
+                    .to_rgb()
+                    .into()
+            }
+            InterpolationSpace::Oklch => {
+                let (a, b) = (start.oklch(), end.oklch());
+                let h = lerp_hue((a.c(), a.h()), (b.c(), b.h()), t, false);
+                Oklch::new(lerp(a.l(), b.l()), lerp(a.c(), b.c()), h)
+                    .to_rgb()
+                    .into()
+            }
+        }
+    }
+}
+
+impl AsRef<str> for InterpolationSpace {
+    fn as_ref(&self) -> &str {
+        match self {
+            InterpolationSpace::Srgb => "sRGB",
+            InterpolationSpace::LinearRgb => "Linear RGB",
+            InterpolationSpace::Lab => "Lab",
+            InterpolationSpace::LchShorter => "LCh (shorter hue)",
+            InterpolationSpace::LchLonger => "LCh (longer hue)",
+            InterpolationSpace::Oklab => "OKLab",
+            InterpolationSpace::Oklch => "OKLCH",
+        }
+    }
+}
+
+/// Interpolates between two hues following CSS Color 4 rules, `(chroma, hue)` pairs are taken so
+/// that the hue of an achromatic color can be ignored.
+fn lerp_hue(start: (f32, f32), end: (f32, f32), t: f32, longer: bool) -> f32 {
+    let (c1, mut h1) = start;
+    let (c2, mut h2) = end;
+    let powerless = c1 < POWERLESS_HUE_CHROMA || c2 < POWERLESS_HUE_CHROMA;
+    if c1 < POWERLESS_HUE_CHROMA {
+        h1 = h2;
+    } else if c2 < POWERLESS_HUE_CHROMA {
+        h2 = h1;
+    }
+    let mut diff = h2 - h1;
+    if longer {
+        if diff > 0. && diff < 180. {
+            diff -= 360.;
+        } else if diff > -180. && diff <= 0. && !powerless {
+            diff += 360.;
+        }
+    } else if diff > 180. {
+        diff -= 360.;
+    } else if diff < -180. {
+        diff += 360.;
+    }
+    (h1 + diff * t).rem_euclid(360.)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GradientStop {
+    pub color: Color,
+    /// Position of this stop in the range 0.0 ..= 1.0
+    pub position: f32,
+}
+
+impl GradientStop {
+    pub fn new(color: Color, position: f32) -> Self {
+        Self {
+            color,
+            position: position.clamp(0., 1.),
+        }
+    }
+}
+
+/// A gradient made of arbitrary many color stops blended in a selectable color space.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ColorGradient {
+    pub name: String,
+    pub space: InterpolationSpace,
+    stops: Vec<GradientStop>,
+}
+
+impl Default for ColorGradient {
+    fn default() -> Self {
+        Self::new("gradient", Color::black(), Color::white())
+    }
+}
+
+impl ColorGradient {
+    pub fn new(name: impl Into<String>, start: Color, end: Color) -> Self {
+        Self {
+            name: name.into(),
+            space: InterpolationSpace::default(),
+            stops: vec![GradientStop::new(start, 0.), GradientStop::new(end, 1.)],
+        }
+    }
+
+    /// Returns the stops of this gradient ordered by position.
+    pub fn stops(&self) -> &[GradientStop] {
+        &self.stops
+    }
+
+    pub fn stop_mut(&mut self, i: usize) -> Option<&mut GradientStop> {
+        self.stops.get_mut(i)
+    }
+
+    /// Restores the ordering of stops by position, should be called after modifying them.
+    pub fn sort_stops(&mut self) {
+        for stop in &mut self.stops {
+            stop.position = stop.position.clamp(0., 1.);
+        }
+        self.stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+    }
+
+    /// Adds a stop in the middle of the widest gap between existing stops. Without a color the
+    /// stop is initialized with the color the gradient already has at that position.
+    pub fn split_widest_gap(&mut self, color: Option<Color>) {
+        let position = self
+            .stops
+            .windows(2)
+            .max_by(|a, b| {
+                (a[1].position - a[0].position).total_cmp(&(b[1].position - b[0].position))
+            })
+            .map(|pair| (pair[0].position + pair[1].position) / 2.)
+            .unwrap_or(0.5);
+        let color = color.unwrap_or_else(|| self.sample(position));
+        self.add_stop(GradientStop::new(color, position));
+    }
+
+    pub fn add_stop(&mut self, stop: GradientStop) {
+        self.stops.push(stop);
+        self.sort_stops();
+    }
+
+    /// Removes the stop at index `i` as long as at least two stops remain.
+    pub fn remove_stop(&mut self, i: usize) -> Option<GradientStop> {
+        if self.stops.len() > 2 && i < self.stops.len() {
+            Some(self.stops.remove(i))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the color of this gradient at position `t` in the range 0.0 ..= 1.0.
+    pub fn sample(&self, t: f32) -> Color {
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Color::black(),
+        };
+        if t <= first.position {
+            return first.color;
+        }
+        if t >= last.position {
+            return last.color;
+        }
+        self.stops
+            .windows(2)
+            .find(|pair| t >= pair[0].position && t <= pair[1].position)
+            .map(|pair| {
+                let width = pair[1].position - pair[0].position;
+                let u = if width > 0. {
+                    (t - pair[0].position) / width
+                } else {
+                    1.
+                };
+                self.space.interpolate(&pair[0].color, &pair[1].color, u)
+            })
+            .unwrap_or(last.color)
+    }
+
+    /// Returns `n` colors evenly spaced along the gradient, including both ends.
+    pub fn samples(&self, n: usize) -> Vec<Color> {
+        match n {
+            0 => vec![],
+            1 => vec![self.sample(0.)],
+            n => (0..n)
+                .map(|i| self.sample(i as f32 / (n - 1) as f32))
+                .collect(),
+        }
+    }
+
+    /// Returns a texture gradient `width` pixels wide for rendering.
+    pub fn to_gradient(&self, width: usize) -> Gradient {
+        Gradient::from_colors(self.samples(width))
+    }
+
+    /// Returns a palette of `n` colors sampled from this gradient. Repeated colors are only
+    /// added once.
+    pub fn as_palette(&self, n: usize) -> NamedPalette {
+        let mut palette = Palette::default();
+        for color in self.samples(n) {
+            palette.add(color);
+        }
+        NamedPalette {
+            name: format!("{} {n} steps", self.name),
+            palette,
+        }
+    }
+
+    /// Formats this gradient as a CSS `linear-gradient` going in the direction of `angle` degrees.
+    pub fn as_css_linear_gradient(&self, angle: f32) -> String {
+        let mut css = format!("linear-gradient({angle}deg");
+        if let Some(method) = self.space.css_method() {
+            let _ = write!(css, " in {method}");
+        }
+        for stop in &self.stops {
+            let percent = (stop.position * 1000.).round() / 10.;
+            let _ = write!(css, ", {} {percent}%", stop.color.as_hex());
+        }
+        css.push(')');
+        css
+    }
+
+    /// Formats this gradient as a GIMP gradient (ggr) file.
+    pub fn as_gimp_gradient(&self) -> String {
+        let mut segments = vec![];
+        if let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) {
+            if first.position > 0. {
+                segments.push((0., first.position, first.color, first.color));
+            }
+            let subdivisions = match self.space {
+                InterpolationSpace::Srgb => 1,
+                _ => GGR_SUBDIVISIONS,
+            };
+            for pair in self.stops.windows(2) {
+                let (start, end) = (pair[0], pair[1]);
+                let width = end.position - start.position;
+                if width <= 0. {
+                    continue;
+                }
+                for k in 0..subdivisions {
+                    let u0 = k as f32 / subdivisions as f32;
+                    let u1 = (k + 1) as f32 / subdivisions as f32;
+                    segments.push((
+                        start.position + width * u0,
+                        start.position + width * u1,
+                        self.space.interpolate(&start.color, &end.color, u0),
+                        self.space.interpolate(&start.color, &end.color, u1),
+                    ));
+                }
+            }
+            if last.position < 1. {
+                segments.push((last.position, 1., last.color, last.color));
+            }
+        }
+
+        let mut ggr = format!("GIMP Gradient\nName: {}\n{}\n", self.name, segments.len());
+        for (left, right, start, end) in segments {
+            let (a, b) = (start.rgb(), end.rgb());
+            let _ = writeln!(
+                ggr,
+                "{left:.6} {:.6} {right:.6} {:.6} {:.6} {:.6} 1.000000 {:.6} {:.6} {:.6} 1.000000 0 0 0 0",
+                (left + right) / 2.,
+                a.r(),
+                a.g(),
+                a.b(),
+                b.r(),
+                b.g(),
+                b.b(),
+            );
+        }
+        ggr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(color: Color) -> String {
+        color.as_hex()
+    }
+
+    fn red_blue() -> ColorGradient {
+        ColorGradient::new(
+            "rb",
+            Rgb::new_scaled(255, 0, 0).into(),
+            Rgb::new_scaled(0, 0, 255).into(),
+        )
+    }
+
+    #[test]
+    fn interpolates_in_spaces() {
+        let mut gradient = red_blue();
+        assert_eq!(hex(gradient.sample(0.5)), "#7f007f");
+        gradient.space = InterpolationSpace::LinearRgb;
+        assert_eq!(hex(gradient.sample(0.5)), "#bb00bb");
+        for space in InterpolationSpace::ALL {
+            gradient.space = space;
+            assert_eq!(hex(gradient.sample(0.)), "#ff0000");
+            assert_eq!(hex(gradient.sample(1.)), "#0000ff");
+        }
+    }
+
+    #[test]
+    fn hue_interpolation() {
+        assert_eq!(lerp_hue((1., 350.), (1., 10.), 0.5, false), 0.);
+        assert_eq!(lerp_hue((1., 350.), (1., 10.), 0.5, true), 180.);
+        assert_eq!(lerp_hue((1., 10.), (1., 90.), 0.5, false), 50.);
+        assert_eq!(lerp_hue((1., 10.), (1., 90.), 0.5, true), 230.);
+        assert_eq!(lerp_hue((0., 0.), (1., 90.), 0.5, false), 90.);
+        assert_eq!(lerp_hue((1., 90.), (0., 0.), 0.5, true), 90.);
+    }
+
+    #[test]
+    fn multiple_stops() {
+        let mut gradient = red_blue();
+        gradient.add_stop(GradientStop::new(Rgb::new_scaled(0, 255, 0).into(), 0.25));
+        assert_eq!(hex(gradient.sample(0.25)), "#00ff00");
+        assert_eq!(hex(gradient.stops()[1].color), "#00ff00");
+        let samples: Vec<_> = gradient.samples(5).into_iter().map(hex).collect();
+        assert_eq!(
+            samples,
+            ["#ff0000", "#00ff00", "#00a955", "#0054aa", "#0000ff"]
+        );
+
+        gradient.split_widest_gap(None);
+        assert_eq!(gradient.stops().len(), 4);
+        assert_eq!(gradient.stops()[2].position, 0.625);
+
+        assert!(gradient.remove_stop(0).is_some());
+        assert!(gradient.remove_stop(0).is_some());
+        assert!(gradient.remove_stop(0).is_none());
+    }
+
+    #[test]
+    fn exports_css() {
+        let mut gradient = red_blue();
+        assert_eq!(
+            gradient.as_css_linear_gradient(90.),
+            "linear-gradient(90deg, #ff0000 0%, #0000ff 100%)"
+        );
+        gradient.space = InterpolationSpace::LchLonger;
+        gradient.stop_mut(1).unwrap().position = 0.755;
+        assert_eq!(
+            gradient.as_css_linear_gradient(45.),
+            "linear-gradient(45deg in lch longer hue, #ff0000 0%, #0000ff 75.5%)"
+        );
+    }
+
+    #[test]
+    fn exports_gimp_gradient() {
+        let mut gradient = red_blue();
+        gradient.stop_mut(0).unwrap().position = 0.5;
+        let want = "GIMP Gradient
+Name: rb
+2
+0.000000 0.250000 0.500000 1.000000 0.000000 0.000000 1.000000 1.000000 0.000000 0.000000 1.000000 0 0 0 0
+0.500000 0.750000 1.000000 1.000000 0.000000 0.000000 1.000000 0.000000 0.000000 1.000000 1.000000 0 0 0 0
+";
+        assert_eq!(gradient.as_gimp_gradient(), want);
+
+        gradient.space = InterpolationSpace::Oklab;
+        let ggr = gradient.as_gimp_gradient();
+        assert_eq!(ggr.lines().nth(2), Some("17"));
+    }
+
+    #[test]
+    fn samples_palette() {
+        let palette = red_blue().as_palette(3);
+        assert_eq!(palette.name, "rb 3 steps");
+        let colors: Vec<_> = palette.palette.iter().map(|c| c.as_hex()).collect();
+        assert_eq!(colors, ["#ff0000", "#7f007f", "#0000ff"]);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::color::{Color, Rgb};
+
+use serde::{Deserialize, Serialize};
+
+/// Simulation matrices for linear sRGB at full severity from Machado, Oliveira and Fernandes,
+/// "A Physiologically-based Model for Simulation of Color Vision Deficiency" (2009).
+const PROTANOPIA: [[f32; 3]; 3] = [
+    [0.152286, 1.052583, -0.204868],
+    [0.114503, 0.786281, 0.099216],
+    [-0.003882, -0.048116, 1.051998],
+];
+const DEUTERANOPIA: [[f32; 3]; 3] = [
+    [0.367322, 0.860646, -0.227968],
+    [0.280085, 0.672501, 0.047413],
+    [-0.011820, 0.042940, 0.968881],
+];
+const TRITANOPIA: [[f32; 3]; 3] = [
+    [1.255528, -0.076749, -0.178779],
+    [-0.078411, 0.930809, 0.147602],
+    [0.004733, 0.691367, 0.303900],
+];
+
+/// Color vision deficiency.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ColorVisionDeficiency {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl ColorVisionDeficiency {
+    pub const ALL: [ColorVisionDeficiency; 3] = [
+        ColorVisionDeficiency::Protanopia,
+        ColorVisionDeficiency::Deuteranopia,
+        ColorVisionDeficiency::Tritanopia,
+    ];
+
+    fn matrix(&self) -> &'static [[f32; 3]; 3] {
+        match self {
+            ColorVisionDeficiency::Protanopia => &PROTANOPIA,
+            ColorVisionDeficiency::Deuteranopia => &DEUTERANOPIA,
+            ColorVisionDeficiency::Tritanopia => &TRITANOPIA,
+        }
+    }
+}
+
+impl AsRef<str> for ColorVisionDeficiency {
+    fn as_ref(&self) -> &str {
+        match self {
+            ColorVisionDeficiency::Protanopia => "protanopia",
+            ColorVisionDeficiency::Deuteranopia => "deuteranopia",
+            ColorVisionDeficiency::Tritanopia => "tritanopia",
+        }
+    }
+}
+
+impl Color {
+    /// Returns how this color is perceived by someone with the `deficiency`.
+    pub fn simulate_cvd(&self, deficiency: ColorVisionDeficiency) -> Color {
+        let rgb = self.rgb().inverse_srgb_compand();
+        let rgb = [rgb.r(), rgb.g(), rgb.b()];
+        let [r, g, b] = deficiency
+            .matrix()
+            .map(|row| row.iter().zip(rgb).map(|(m, c)| m * c).sum::<f32>());
+        Rgb::new(r, g, b).srgb_compand().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_grays() {
+        for deficiency in ColorVisionDeficiency::ALL {
+            for gray in [0, 128, 255] {
+                let color: Color = Rgb::new_scaled(gray, gray, gray).into();
+                let simulated = color.simulate_cvd(deficiency);
+                assert!(color.delta_e(&simulated) < 1., "{deficiency:?} {gray}");
+            }
+        }
+    }
+
+    #[test]
+    fn confuses_red_and_green() {
+        let red: Color = Rgb::new_scaled(200, 60, 40).into();
+        let green: Color = Rgb::new_scaled(90, 130, 40).into();
+        let normal = red.delta_e(&green);
+        let deutan = red
+            .simulate_cvd(ColorVisionDeficiency::Deuteranopia)
+            .delta_e(&green.simulate_cvd(ColorVisionDeficiency::Deuteranopia));
+        assert!(deutan < normal / 2., "{deutan} {normal}");
+    }
+}
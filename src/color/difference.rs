@@ -0,0 +1,152 @@
+use crate::color::{CIEColor, ChromaticAdaptationMethod, Color, Illuminant, Lab, RgbWorkingSpace};
+
+/// Returns the CIE Lab coordinates of `color` used for measuring perceptual differences. They are
+/// always relative to sRGB and D65 so that results don't depend on the selected working space.
+pub fn reference_lab(color: &Color) -> Lab {
+    color.lab(
+        RgbWorkingSpace::SRGB,
+        Illuminant::D65,
+        ChromaticAdaptationMethod::default(),
+    )
+}
+
+/// Inverse of [`reference_lab`], colors outside of the sRGB gamut are clipped.
+pub fn from_reference_lab(lab: Lab) -> Color {
+    lab.to_xyz(Illuminant::D65)
+        .to_rgb(RgbWorkingSpace::SRGB)
+        .into()
+}
+
+/// Returns the CIE76 color difference, the euclidean distance between two Lab colors.
+pub fn delta_e76(lab1: &Lab, lab2: &Lab) -> f32 {
+    ((lab1.l() - lab2.l()).powi(2) + (lab1.a() - lab2.a()).powi(2) + (lab1.b() - lab2.b()).powi(2))
+        .sqrt()
+}
+
+/// Returns the CIEDE2000 color difference between two Lab colors.
+#[allow(clippy::many_single_char_names)]
+pub fn delta_e2000(lab1: &Lab, lab2: &Lab) -> f32 {
+    let (l1, a1, b1) = (lab1.l() as f64, lab1.a() as f64, lab1.b() as f64);
+    let (l2, a2, b2) = (lab2.l() as f64, lab2.a() as f64, lab2.b() as f64);
+
+    let c1 = a1.hypot(b1);
+    let c2 = a2.hypot(b2);
+    let c_mean = (c1 + c2) / 2.;
+    let c_mean7 = c_mean.powi(7);
+    let g = 0.5 * (1. - (c_mean7 / (c_mean7 + 25f64.powi(7))).sqrt());
+
+    let a1 = a1 * (1. + g);
+    let a2 = a2 * (1. + g);
+    let c1 = a1.hypot(b1);
+    let c2 = a2.hypot(b2);
+
+    let hue = |a: f64, b: f64| {
+        if a == 0. && b == 0. {
+            0.
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.)
+        }
+    };
+    let h1 = hue(a1, b1);
+    let h2 = hue(a2, b2);
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if c1 * c2 == 0. {
+        0.
+    } else if (h2 - h1).abs() <= 180. {
+        h2 - h1
+    } else if h2 - h1 > 180. {
+        h2 - h1 - 360.
+    } else {
+        h2 - h1 + 360.
+    };
+    let dh = 2. * (c1 * c2).sqrt() * (dh / 2.).to_radians().sin();
+
+    let l_mean = (l1 + l2) / 2.;
+    let c_mean = (c1 + c2) / 2.;
+    let h_mean = if c1 * c2 == 0. {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180. {
+        (h1 + h2) / 2.
+    } else if h1 + h2 < 360. {
+        (h1 + h2 + 360.) / 2.
+    } else {
+        (h1 + h2 - 360.) / 2.
+    };
+
+    let t = 1. - 0.17 * (h_mean - 30.).to_radians().cos()
+        + 0.24 * (2. * h_mean).to_radians().cos()
+        + 0.32 * (3. * h_mean + 6.).to_radians().cos()
+        - 0.20 * (4. * h_mean - 63.).to_radians().cos();
+    let d_theta = 30. * (-((h_mean - 275.) / 25.).powi(2)).exp();
+    let c_mean7 = c_mean.powi(7);
+    let rc = 2. * (c_mean7 / (c_mean7 + 25f64.powi(7))).sqrt();
+    let sl = 1. + (0.015 * (l_mean - 50.).powi(2)) / (20. + (l_mean - 50.).powi(2)).sqrt();
+    let sc = 1. + 0.045 * c_mean;
+    let sh = 1. + 0.015 * c_mean * t;
+    let rt = -(2. * d_theta).to_radians().sin() * rc;
+
+    let dl = dl / sl;
+    let dc = dc / sc;
+    let dh = dh / sh;
+    (dl.powi(2) + dc.powi(2) + dh.powi(2) + rt * dc * dh).sqrt() as f32
+}
+
+impl Color {
+    /// Returns the CIEDE2000 difference between this color and `other`.
+    pub fn delta_e(&self, other: &Color) -> f32 {
+        delta_e2000(&reference_lab(self), &reference_lab(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Rgb;
+
+    macro_rules! assert_delta_e {
+        ($lab1:expr, $lab2:expr, $want:expr) => {
+            let got = delta_e2000(&$lab1, &$lab2);
+            assert!((got - $want).abs() < 1e-4, "got {got}, want {}", $want);
+            let got = delta_e2000(&$lab2, &$lab1);
+            assert!((got - $want).abs() < 1e-4, "got {got}, want {}", $want);
+        };
+    }
+
+    #[test]
+    fn ciede2000_reference_pairs() {
+        // Test data from Sharma, Wu, Dalal - "The CIEDE2000 Color-Difference Formula"
+        assert_delta_e!(
+            Lab::new(50., 2.6772, -79.7751),
+            Lab::new(50., 0., -82.7485),
+            2.0425
+        );
+        assert_delta_e!(Lab::new(50., 0., 0.), Lab::new(50., -1., 2.), 2.3669);
+        assert_delta_e!(
+            Lab::new(50., 2.49, -0.001),
+            Lab::new(50., -2.49, 0.0011),
+            7.2195
+        );
+        assert_delta_e!(Lab::new(50., 2.5, 0.), Lab::new(73., 25., -18.), 27.1492);
+        assert_delta_e!(
+            Lab::new(2.0776, 0.0795, -1.135),
+            Lab::new(0.9033, -0.0636, -0.5514),
+            0.9082
+        );
+    }
+
+    #[test]
+    fn cie76() {
+        let got = delta_e76(&Lab::new(50., 0., 0.), &Lab::new(53., 4., 0.));
+        assert_eq!(got, 5.);
+    }
+
+    #[test]
+    fn color_delta_e() {
+        let red: Color = Rgb::new_scaled(255, 0, 0).into();
+        let black: Color = Rgb::new_scaled(0, 0, 0).into();
+        assert_eq!(red.delta_e(&red), 0.);
+        assert!(red.delta_e(&black) > 40.);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::color::{
+    Color, NamedPalette, Palette, Palettes,
+    difference::{delta_e2000, reference_lab},
+};
+
+/// Colors closer than this (CIEDE2000) are hard to tell apart side by side.
+pub const DEFAULT_NEAR_DUPLICATE_TOLERANCE: f32 = 2.3;
+
+/// A pair of palette colors that are perceptually very close to each other.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NearDuplicate {
+    pub first: usize,
+    pub second: usize,
+    pub delta_e: f32,
+}
+
+impl Palette {
+    /// Returns the first entry of this palette that is the same color as `color` or differs from
+    /// it by less than `tolerance` (CIEDE2000). A tolerance of `0` only matches exact duplicates.
+    pub fn find_similar(&self, color: &Color, tolerance: f32) -> Option<usize> {
+        let triplet = color.as_rgb_triplet_scaled();
+        if let Some(i) = self
+            .iter()
+            .position(|clr| clr.as_rgb_triplet_scaled() == triplet)
+        {
+            return Some(i);
+        }
+        if tolerance <= 0. {
+            return None;
+        }
+        let lab = reference_lab(color);
+        self.iter()
+            .position(|clr| delta_e2000(&reference_lab(clr), &lab) < tolerance)
+    }
+
+    /// Returns all pairs of colors closer than `tolerance` sorted from the most similar.
+    pub fn near_duplicates(&self, tolerance: f32) -> Vec<NearDuplicate> {
+        let labs: Vec<_> = self.iter().map(reference_lab).collect();
+        let mut duplicates = Vec::new();
+        for (first, lab1) in labs.iter().enumerate() {
+            for (second, lab2) in labs.iter().enumerate().skip(first + 1) {
+                let delta_e = delta_e2000(lab1, lab2);
+                if delta_e < tolerance {
+                    duplicates.push(NearDuplicate {
+                        first,
+                        second,
+                        delta_e,
+                    });
+                }
+            }
+        }
+        duplicates.sort_by(|a, b| a.delta_e.total_cmp(&b.delta_e));
+        duplicates
+    }
+
+    /// Appends the entries of `other` that don't have a similar color in this palette yet.
+    ///
+    /// Returns the number of added entries.
+    pub fn merge(&mut self, other: &Palette, tolerance: f32) -> usize {
+        let mut added = 0;
+        for entry in other.entries() {
+            if self.find_similar(&entry.color, tolerance).is_none() {
+                self.0.push(entry.clone());
+                added += 1;
+            }
+        }
+        added
+    }
+}
+
+impl Palettes {
+    /// Combines the palettes at `indices` into a new palette called `name`, skipping colors that
+    /// are within `tolerance` of an already merged one. Returns `false` if a palette with this name
+    /// already exists.
+    pub fn merge(&mut self, indices: &[usize], name: impl Into<String>, tolerance: f32) -> bool {
+        let mut merged = NamedPalette {
+            name: name.into(),
+            palette: Palette::default(),
+        };
+        for palette in indices.iter().filter_map(|&i| self.palettes.get(i)) {
+            merged.palette.merge(&palette.palette, tolerance);
+        }
+        self.add(merged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{PaletteEntry, Rgb};
+
+    fn color(r: u8, g: u8, b: u8) -> Color {
+        Rgb::new_scaled(r, g, b).into()
+    }
+
+    #[test]
+    fn finds_similar_colors() {
+        let palette = Palette::from_iter([color(255, 0, 0), color(0, 0, 255)]);
+        assert_eq!(palette.find_similar(&color(255, 0, 0), 0.), Some(0));
+        assert_eq!(palette.find_similar(&color(254, 1, 0), 0.), None);
+        assert_eq!(
+            palette.find_similar(&color(254, 1, 0), DEFAULT_NEAR_DUPLICATE_TOLERANCE),
+            Some(0)
+        );
+        assert_eq!(palette.find_similar(&color(0, 255, 0), 10.), None);
+    }
+
+    #[test]
+    fn reports_near_duplicates() {
+        let palette = Palette::from_iter([
+            color(255, 0, 0),
+            color(0, 0, 255),
+            color(254, 1, 0),
+            color(0, 0, 250),
+        ]);
+        let duplicates = palette.near_duplicates(DEFAULT_NEAR_DUPLICATE_TOLERANCE);
+        assert_eq!(duplicates.len(), 2);
+        assert_eq!((duplicates[0].first, duplicates[0].second), (0, 2));
+        assert_eq!((duplicates[1].first, duplicates[1].second), (1, 3));
+        assert!(duplicates[0].delta_e < duplicates[1].delta_e);
+    }
+
+    #[test]
+    fn merges_palettes() {
+        let mut palettes = Palettes::new(NamedPalette {
+            name: "a".into(),
+            palette: Palette::from_iter([
+                PaletteEntry::named(color(255, 0, 0), "red"),
+                PaletteEntry::new(color(0, 255, 0)),
+            ]),
+        });
+        palettes.add(NamedPalette {
+            name: "b".into(),
+            palette: Palette::from_iter([color(254, 1, 0), color(0, 0, 255)]),
+        });
+
+        assert!(palettes.merge(&[0, 1], "merged", DEFAULT_NEAR_DUPLICATE_TOLERANCE));
+        assert!(!palettes.merge(&[0, 1], "merged", 0.));
+        let merged = &palettes[2];
+        assert_eq!(merged.name, "merged");
+        let hex: Vec<_> = merged.palette.iter().map(|c| c.as_hex()).collect();
+        assert_eq!(hex, ["#ff0000", "#00ff00", "#0000ff"]);
+        assert_eq!(merged.palette.entries().next().unwrap().name(), Some("red"));
+    }
+}
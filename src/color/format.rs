@@ -1,6 +1,7 @@
 use crate::color::{
-    CIEColor, Cmyk, Color, Hsl, Hsv, Illuminant, Lab, LchAB, LchUV, Luv, Palette, RgbWorkingSpace,
-    Xyz, xyY,
+    CIEColor, Cam16, Cam16Ucs, Cmyk, Color, Hct, Hsi, Hsl, Hsv, Hwb, Illuminant, Lab, LchAB, LchUV,
+    Luv, Palette, PaletteEntry, RgbWorkingSpace, ViewingConditions, Xyz, YCbCrRange, YCbCrStandard,
+    Yiq, Yuv, xyY,
 };
 
 use anyhow::{Error, Result};
@@ -15,7 +16,7 @@ use nom::{
     sequence::{delimited, preceded},
 };
 use serde::{Deserialize, Serialize};
-use std::{fmt::Write, num::ParseIntError};
+use std::{cell::OnceCell, fmt::Write, num::ParseIntError};
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CustomPaletteFormat {
@@ -33,8 +34,8 @@ impl CustomPaletteFormat {
     ) -> Result<String> {
         let mut s = self.prefix.clone();
         let entry_format = CustomColorFormat::parse(&self.entry_format)?;
-        for entry in palette.iter() {
-            s.push_str(&entry_format.format_color(entry, ws, illuminant)?);
+        for entry in palette.entries() {
+            s.push_str(&entry_format.format_entry(entry, ws, illuminant)?);
         }
         s.push_str(&self.suffix);
         Ok(s)
@@ -57,6 +58,28 @@ impl<'a> CustomColorFormat<'a> {
         color: &Color,
         ws: RgbWorkingSpace,
         illuminant: Illuminant,
+    ) -> Result<String> {
+        self.format(color, None, None, ws, illuminant)
+    }
+
+    /// Same as [`format_color`](Self::format_color) but also fills in `{name}` and `{note}`
+    /// fields with the metadata of the palette entry.
+    pub fn format_entry(
+        &self,
+        entry: &PaletteEntry,
+        ws: RgbWorkingSpace,
+        illuminant: Illuminant,
+    ) -> Result<String> {
+        self.format(&entry.color, entry.name(), entry.note(), ws, illuminant)
+    }
+
+    fn format(
+        &self,
+        color: &Color,
+        name: Option<&str>,
+        note: Option<&str>,
+        ws: RgbWorkingSpace,
+        illuminant: Illuminant,
     ) -> Result<String> {
         use ColorSymbol::*;
 
@@ -64,18 +87,34 @@ impl<'a> CustomColorFormat<'a> {
         let cmyk = Cmyk::from(rgb);
         let hsl = Hsl::from(rgb);
         let hsv = Hsv::from(rgb);
+        let hwb = Hwb::from(rgb);
+        let hsi = Hsi::from(rgb);
+        let yuv = Yuv::from(rgb);
+        let yiq = Yiq::from(rgb);
         let xyz = Xyz::from_rgb(rgb, ws);
         let xyy = xyY::from(xyz);
         let lab = Lab::from_xyz(xyz, illuminant);
         let luv = Luv::from(xyz);
         let lch_ab = LchAB::from(lab);
         let lch_uv = LchUV::from(luv);
+        let white = ws.reference_illuminant();
+        let conditions = ViewingConditions::default();
+        // Color appearance models are expensive, only compute them for fields using them
+        let cam16 = OnceCell::new();
+        let cam16 = || *cam16.get_or_init(|| Cam16::from_xyz(xyz, white, &conditions));
+        let cam16_ucs = OnceCell::new();
+        let cam16_ucs = || *cam16_ucs.get_or_init(|| Cam16Ucs::from_xyz(xyz, white, &conditions));
+        let hct = OnceCell::new();
+        let hct = || *hct.get_or_init(|| Hct::from_xyz(xyz, white));
 
         let mut s = String::new();
 
         for token in &self.0 {
             match &token {
                 FormatToken::Text(text) => s.push_str(text),
+                FormatToken::Name => s.push_str(name.unwrap_or_default()),
+                FormatToken::NearestName => s.push_str(color.nearest_name().name),
+                FormatToken::Note => s.push_str(note.unwrap_or_default()),
                 FormatToken::Color(ColorField {
                     symbol,
                     digit_format,
@@ -85,7 +124,11 @@ impl<'a> CustomColorFormat<'a> {
                     | HSVSaturation | HSVValue | LabL | LabA | LabB | LCHabL | LCHabC | LCHabH
                     | LuvL | LuvU | LuvV | LCHuvL | LCHuvC | LCHuvH | xyYx | xyYy | xyYY | XYZx
                     | XYZy | XYZz | HSLHue360 | HSLSaturation100 | HSLLight100 | HSVHue360
-                    | HSVSaturation100 | HSVValue100 => {
+                    | HSVSaturation100 | HSVValue100 | HWBHue | HWBWhiteness | HWBBlackness
+                    | HWBHue360 | HWBWhiteness100 | HWBBlackness100 | HSIHue | HSISaturation
+                    | HSIIntensity | HSIHue360 | HSISaturation100 | HSIIntensity100 | YUVy
+                    | YUVu | YUVv | YIQy | YIQi | YIQq | Cam16J | Cam16C | Cam16H | Cam16UcsJ
+                    | Cam16UcsA | Cam16UcsB | HCTHue | HCTChroma | HCTTone => {
                         let num = match symbol {
                             Red => rgb.r(),
                             Green => rgb.g(),
@@ -117,6 +160,30 @@ impl<'a> CustomColorFormat<'a> {
                             HSVSaturation100 => hsv.s_scaled(),
                             HSVValue100 => hsv.v_scaled(),
 
+                            HWBHue => hwb.h(),
+                            HWBWhiteness => hwb.w(),
+                            HWBBlackness => hwb.b(),
+
+                            HWBHue360 => hwb.h_scaled(),
+                            HWBWhiteness100 => hwb.w_scaled(),
+                            HWBBlackness100 => hwb.b_scaled(),
+
+                            HSIHue => hsi.h(),
+                            HSISaturation => hsi.s(),
+                            HSIIntensity => hsi.i(),
+
+                            HSIHue360 => hsi.h_scaled(),
+                            HSISaturation100 => hsi.s_scaled(),
+                            HSIIntensity100 => hsi.i_scaled(),
+
+                            YUVy => yuv.y(),
+                            YUVu => yuv.u(),
+                            YUVv => yuv.v(),
+
+                            YIQy => yiq.y(),
+                            YIQi => yiq.i(),
+                            YIQq => yiq.q(),
+
                             LabL => lab.l(),
                             LabA => lab.a(),
                             LabB => lab.b(),
@@ -140,29 +207,22 @@ impl<'a> CustomColorFormat<'a> {
                             XYZx => xyz.x(),
                             XYZy => xyz.y(),
                             XYZz => xyz.z(),
+
+                            Cam16J => cam16().j(),
+                            Cam16C => cam16().c(),
+                            Cam16H => cam16().h(),
+
+                            Cam16UcsJ => cam16_ucs().j(),
+                            Cam16UcsA => cam16_ucs().a(),
+                            Cam16UcsB => cam16_ucs().b(),
+
+                            HCTHue => hct().h(),
+                            HCTChroma => hct().c(),
+                            HCTTone => hct().t(),
                             _ => unreachable!(),
                         };
 
-                        match digit_format {
-                            Some(DigitFormat::Decimal) => {
-                                write!(&mut s, "{}", num.abs() as u32)?;
-                            }
-                            Some(DigitFormat::Hex) => {
-                                write!(&mut s, "{:x}", num.abs() as u32)?;
-                            }
-                            Some(DigitFormat::UppercaseHex) => {
-                                write!(&mut s, "{:X}", num.abs() as u32)?;
-                            }
-                            Some(DigitFormat::Octal) => {
-                                write!(&mut s, "{:o}", num.abs() as u32)?;
-                            }
-                            Some(DigitFormat::Float { precision }) => {
-                                write!(&mut s, "{:.*}", *precision as usize, num)?;
-                            }
-                            None => {
-                                write!(&mut s, "{num:.1}")?;
-                            }
-                        }
+                        write_float(&mut s, num, digit_format)?;
                     }
                     Red255 | Green255 | Blue255 => {
                         let num = match symbol {
@@ -172,12 +232,24 @@ impl<'a> CustomColorFormat<'a> {
                             _ => unreachable!(),
                         } as u32;
 
-                        match digit_format.unwrap_or_default() {
-                            DigitFormat::Decimal => write!(&mut s, "{num}")?,
-                            DigitFormat::Hex => write!(&mut s, "{num:x}")?,
-                            DigitFormat::UppercaseHex => write!(&mut s, "{num:X}")?,
-                            DigitFormat::Octal => write!(&mut s, "{num:o}")?,
-                            DigitFormat::Float { precision: _ } => write!(&mut s, "{num}")?,
+                        write_integer(&mut s, num, digit_format)?;
+                    }
+                    YCbCr(standard, component, coding) => {
+                        let ycbcr = color.ycbcr(*standard);
+                        let index = match component {
+                            YCbCrComponent::Y => 0,
+                            YCbCrComponent::Cb => 1,
+                            YCbCrComponent::Cr => 2,
+                        };
+                        match coding {
+                            Some((bits, range)) => {
+                                let num = ycbcr.quantize(*bits, *range)[index];
+                                write_integer(&mut s, num, digit_format)?;
+                            }
+                            None => {
+                                let num = [ycbcr.y(), ycbcr.cb(), ycbcr.cr()][index];
+                                write_float(&mut s, num, digit_format)?;
+                            }
                         }
                     }
                 },
@@ -188,6 +260,29 @@ impl<'a> CustomColorFormat<'a> {
     }
 }
 
+fn write_float(s: &mut String, num: f32, digit_format: &Option<DigitFormat>) -> Result<()> {
+    match digit_format {
+        Some(DigitFormat::Decimal) => write!(s, "{}", num.abs() as u32)?,
+        Some(DigitFormat::Hex) => write!(s, "{:x}", num.abs() as u32)?,
+        Some(DigitFormat::UppercaseHex) => write!(s, "{:X}", num.abs() as u32)?,
+        Some(DigitFormat::Octal) => write!(s, "{:o}", num.abs() as u32)?,
+        Some(DigitFormat::Float { precision }) => write!(s, "{:.*}", *precision as usize, num)?,
+        None => write!(s, "{num:.1}")?,
+    }
+    Ok(())
+}
+
+fn write_integer(s: &mut String, num: u32, digit_format: &Option<DigitFormat>) -> Result<()> {
+    match digit_format.unwrap_or_default() {
+        DigitFormat::Decimal => write!(s, "{num}")?,
+        DigitFormat::Hex => write!(s, "{num:x}")?,
+        DigitFormat::UppercaseHex => write!(s, "{num:X}")?,
+        DigitFormat::Octal => write!(s, "{num:o}")?,
+        DigitFormat::Float { precision: _ } => write!(s, "{num}")?,
+    }
+    Ok(())
+}
+
 impl<'a> From<Vec<FormatToken<'a>>> for CustomColorFormat<'a> {
     fn from(vec: Vec<FormatToken<'a>>) -> Self {
         Self(vec)
@@ -220,6 +315,9 @@ impl FromExternalError<&str, ParseIntError> for ColorParseError<&str> {
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum FormatToken<'a> {
     Color(ColorField),
+    Name,
+    NearestName,
+    Note,
     Text(&'a str),
 }
 
@@ -238,6 +336,7 @@ enum DigitFormat {
     Float { precision: u8 },
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for DigitFormat {
     fn default() -> Self {
         Self::Decimal
@@ -282,6 +381,33 @@ enum ColorSymbol {
     HSVSaturation100,
     HSVValue100,
 
+    HWBHue,
+    HWBWhiteness,
+    HWBBlackness,
+
+    HWBHue360,
+    HWBWhiteness100,
+    HWBBlackness100,
+
+    HSIHue,
+    HSISaturation,
+    HSIIntensity,
+
+    HSIHue360,
+    HSISaturation100,
+    HSIIntensity100,
+
+    /// Normalized components or, with a bit depth and range, code values
+    YCbCr(YCbCrStandard, YCbCrComponent, Option<(u8, YCbCrRange)>),
+
+    YUVy,
+    YUVu,
+    YUVv,
+
+    YIQy,
+    YIQi,
+    YIQq,
+
     LabL,
     LabA,
     LabB,
@@ -305,6 +431,25 @@ enum ColorSymbol {
     XYZx,
     XYZy,
     XYZz,
+
+    Cam16J,
+    Cam16C,
+    Cam16H,
+
+    Cam16UcsJ,
+    Cam16UcsA,
+    Cam16UcsB,
+
+    HCTHue,
+    HCTChroma,
+    HCTTone,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum YCbCrComponent {
+    Y,
+    Cb,
+    Cr,
 }
 
 fn parse_rgb_symbol(i: &str) -> IResult<&str, ColorSymbol, ColorParseError<&str>> {
@@ -357,6 +502,81 @@ fn parse_hsv_symbol(i: &str) -> IResult<&str, ColorSymbol, ColorParseError<&str>
     .parse(i)
 }
 
+fn parse_hwb_symbol(i: &str) -> IResult<&str, ColorSymbol, ColorParseError<&str>> {
+    alt((
+        tag("hwb_h360").map(|_| ColorSymbol::HWBHue360),
+        tag("hwb_w100").map(|_| ColorSymbol::HWBWhiteness100),
+        tag("hwb_b100").map(|_| ColorSymbol::HWBBlackness100),
+        tag("hwb_h").map(|_| ColorSymbol::HWBHue),
+        tag("hwb_w").map(|_| ColorSymbol::HWBWhiteness),
+        tag("hwb_b").map(|_| ColorSymbol::HWBBlackness),
+    ))
+    .parse(i)
+}
+
+fn parse_hsi_symbol(i: &str) -> IResult<&str, ColorSymbol, ColorParseError<&str>> {
+    alt((
+        tag("hsi_h360").map(|_| ColorSymbol::HSIHue360),
+        tag("hsi_s100").map(|_| ColorSymbol::HSISaturation100),
+        tag("hsi_i100").map(|_| ColorSymbol::HSIIntensity100),
+        tag("hsi_h").map(|_| ColorSymbol::HSIHue),
+        tag("hsi_s").map(|_| ColorSymbol::HSISaturation),
+        tag("hsi_i").map(|_| ColorSymbol::HSIIntensity),
+    ))
+    .parse(i)
+}
+
+/// Parses symbols like `ycbcr709_y` for normalized components and `ycbcr2020_cb10l` for code
+/// values with a bit depth of 8, 10 or 12 and a full `f` or limited `l` range.
+fn parse_ycbcr_symbol(i: &str) -> IResult<&str, ColorSymbol, ColorParseError<&str>> {
+    let standard = alt((
+        tag("601").map(|_| YCbCrStandard::Bt601),
+        tag("709").map(|_| YCbCrStandard::Bt709),
+        tag("2020").map(|_| YCbCrStandard::Bt2020),
+    ));
+    let component = alt((
+        tag("cb").map(|_| YCbCrComponent::Cb),
+        tag("cr").map(|_| YCbCrComponent::Cr),
+        char('y').map(|_| YCbCrComponent::Y),
+    ));
+    let bits = alt((
+        tag("10").map(|_| 10),
+        tag("12").map(|_| 12),
+        char('8').map(|_| 8),
+    ));
+    let range = alt((
+        char('f').map(|_| YCbCrRange::Full),
+        char('l').map(|_| YCbCrRange::Limited),
+    ));
+    map(
+        (
+            preceded(tag("ycbcr"), standard),
+            preceded(char('_'), component),
+            opt((bits, range)),
+        ),
+        |(standard, component, coding)| ColorSymbol::YCbCr(standard, component, coding),
+    )
+    .parse(i)
+}
+
+fn parse_yuv_symbol(i: &str) -> IResult<&str, ColorSymbol, ColorParseError<&str>> {
+    alt((
+        tag("yuv_y").map(|_| ColorSymbol::YUVy),
+        tag("yuv_u").map(|_| ColorSymbol::YUVu),
+        tag("yuv_v").map(|_| ColorSymbol::YUVv),
+    ))
+    .parse(i)
+}
+
+fn parse_yiq_symbol(i: &str) -> IResult<&str, ColorSymbol, ColorParseError<&str>> {
+    alt((
+        tag("yiq_y").map(|_| ColorSymbol::YIQy),
+        tag("yiq_i").map(|_| ColorSymbol::YIQi),
+        tag("yiq_q").map(|_| ColorSymbol::YIQq),
+    ))
+    .parse(i)
+}
+
 fn parse_lab_symbol(i: &str) -> IResult<&str, ColorSymbol, ColorParseError<&str>> {
     alt((
         tag("lab_l").map(|_| ColorSymbol::LabL),
@@ -411,18 +631,47 @@ fn parse_xyz_symbol(i: &str) -> IResult<&str, ColorSymbol, ColorParseError<&str>
     .parse(i)
 }
 
+fn parse_cam16_symbol(i: &str) -> IResult<&str, ColorSymbol, ColorParseError<&str>> {
+    alt((
+        tag("cam16_j").map(|_| ColorSymbol::Cam16J),
+        tag("cam16_c").map(|_| ColorSymbol::Cam16C),
+        tag("cam16_h").map(|_| ColorSymbol::Cam16H),
+        tag("cam16ucs_j").map(|_| ColorSymbol::Cam16UcsJ),
+        tag("cam16ucs_a").map(|_| ColorSymbol::Cam16UcsA),
+        tag("cam16ucs_b").map(|_| ColorSymbol::Cam16UcsB),
+    ))
+    .parse(i)
+}
+
+fn parse_hct_symbol(i: &str) -> IResult<&str, ColorSymbol, ColorParseError<&str>> {
+    alt((
+        tag("hct_h").map(|_| ColorSymbol::HCTHue),
+        tag("hct_c").map(|_| ColorSymbol::HCTChroma),
+        tag("hct_t").map(|_| ColorSymbol::HCTTone),
+    ))
+    .parse(i)
+}
+
 fn parse_color_symbol(i: &str) -> IResult<&str, ColorSymbol, ColorParseError<&str>> {
+    // Symbols starting with a letter of a single character symbol come first
     alt((
+        parse_ycbcr_symbol,
+        parse_yuv_symbol,
+        parse_yiq_symbol,
+        parse_cam16_symbol,
         parse_rgb_symbol,
         parse_cmyk_symbol,
         parse_hsl_symbol,
         parse_hsv_symbol,
+        parse_hwb_symbol,
+        parse_hsi_symbol,
         parse_lab_symbol,
         parse_lch_ab_symbol,
         parse_luv_symbol,
         parse_lch_uv_symbol,
         parse_xyy_symbol,
         parse_xyz_symbol,
+        parse_hct_symbol,
     ))
     .parse(i)
 }
@@ -483,6 +732,22 @@ fn parse_color_field(i: &str) -> IResult<&str, ColorField, ColorParseError<&str>
     .parse(i)
 }
 
+fn parse_entry_field(i: &str) -> IResult<&str, FormatToken<'_>, ColorParseError<&str>> {
+    delimited(
+        char('{'),
+        preceded(
+            space0,
+            alt((
+                tag("name").map(|_| FormatToken::Name),
+                tag("nearest_name").map(|_| FormatToken::NearestName),
+                tag("note").map(|_| FormatToken::Note),
+            )),
+        ),
+        preceded(space0, char('}')),
+    )
+    .parse(i)
+}
+
 #[inline]
 fn is_not_variable_start(chr: char) -> bool {
     chr != '{'
@@ -503,6 +768,7 @@ fn parse_brace(i: &str) -> IResult<&str, FormatToken<'_>, ColorParseError<&str>>
 fn parse_format_token(i: &str) -> IResult<&str, FormatToken<'_>, ColorParseError<&str>> {
     alt((
         map(parse_color_field, FormatToken::Color),
+        parse_entry_field,
         parse_brace,
         map(parse_text, FormatToken::Text),
     ))
@@ -578,6 +844,26 @@ mod tests {
             "{hsv_h360:d} {hsv_s100:X} {hsv_v100:x}" => "326 4B 2f",
             Color::Rgb(Rgb::new_scaled(120, 30, 80))
         );
+        test_case!(
+            "{nearest_name}: #{r255:x}{g255:x}{b255:x}" => "tomato: #ff6347",
+            Color::Rgb(Rgb::new_scaled(255, 99, 71))
+        );
+        test_case!(
+            "{hwb_h360:.0} {hwb_w100:.0} {hwb_b100:.0}" => "90 20 40",
+            Color::Rgb(Rgb::new_scaled(102, 153, 51))
+        );
+        test_case!(
+            "{ycbcr709_y8l} {ycbcr709_cb8l} {ycbcr709_cr8l} {ycbcr709_y:.3}" => "63 102 240 0.213",
+            Color::Rgb(Rgb::new_scaled(255, 0, 0))
+        );
+        test_case!(
+            "{ycbcr2020_y10f:X} {yuv_u:.3} {yiq_q:.3}" => "2B6 -0.289 -0.523",
+            Color::Rgb(Rgb::new_scaled(0, 255, 0))
+        );
+        test_case!(
+            "hct({hct_h:.0} {hct_c:.0} {hct_t:.0}) {cam16_j:.0} {cam16ucs_j:.0} {c:.0}" => "hct(27 113 53) 46 60 0",
+            Color::Rgb(Rgb::new_scaled(255, 0, 0))
+        );
     }
 
     #[test]
@@ -622,6 +908,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn formats_palette_entry() {
+        use crate::color::{CustomPaletteFormat, Palette, PaletteEntry};
+
+        let mut entry = PaletteEntry::named(Rgb::new_scaled(255, 0, 0).into(), "danger");
+        entry.note = Some("errors".into());
+        let blue = PaletteEntry::new(Rgb::new_scaled(0, 0, 255).into());
+        let palette = Palette::from_iter([entry, blue]);
+        let format = CustomPaletteFormat {
+            prefix: "[".into(),
+            entry_format: "{name}={r255};{ note }|".into(),
+            suffix: "]".into(),
+        };
+        let formatted = format
+            .format_palette(&palette, RgbWorkingSpace::SRGB, Illuminant::D65)
+            .unwrap();
+        assert_eq!(formatted, "[danger=255;errors|=0;|]");
+
+        test_case!(
+            "{name} {r}",
+            vec![FormatToken::Name, FormatToken::Text(" "), field!(Red)].into()
+        );
+    }
+
+    #[test]
+    fn parses_ycbcr_symbol() {
+        use crate::color::{
+            YCbCrRange, YCbCrStandard,
+            format::{YCbCrComponent, parse_color_format},
+        };
+
+        test_case!(
+            "{ycbcr601_cr} {ycbcr2020_y12f:x}",
+            vec![
+                FormatToken::Color(ColorField {
+                    symbol: ColorSymbol::YCbCr(YCbCrStandard::Bt601, YCbCrComponent::Cr, None),
+                    digit_format: None,
+                }),
+                FormatToken::Text(" "),
+                FormatToken::Color(ColorField {
+                    symbol: ColorSymbol::YCbCr(
+                        YCbCrStandard::Bt2020,
+                        YCbCrComponent::Y,
+                        Some((12, YCbCrRange::Full)),
+                    ),
+                    digit_format: Some(DigitFormat::Hex),
+                }),
+            ]
+            .into()
+        );
+        test_case!("{yuv_v}{y}", vec![field!(YUVv), field!(Yellow)].into());
+        // A bit depth without a range is not a valid field
+        assert_eq!(
+            parse_color_format("{ycbcr709_y8}").unwrap().1,
+            vec![FormatToken::Text("{"), FormatToken::Text("ycbcr709_y8}")].into()
+        );
+    }
+
     #[test]
     fn parses_digit_format() {
         test_case!(
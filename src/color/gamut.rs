@@ -0,0 +1,307 @@
+use crate::{
+    color::{
+        ChromaticAdaptationMethod, Illuminant, Lab, Oklab, Rgb, RgbWorkingSpace, Xyz,
+        difference::delta_e2000,
+    },
+    math::{Matrix1x3, Matrix3},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Tolerance of linear RGB components still considered to be in gamut.
+const GAMUT_EPSILON: f32 = 1e-4;
+/// Just noticeable difference in Oklab used by the CSS Color 4 gamut mapping.
+const JND: f32 = 0.02;
+/// Precision of the chroma search of the CSS Color 4 gamut mapping.
+const CHROMA_EPSILON: f32 = 1e-4;
+/// Smallest step of the closest ΔE search in linear RGB.
+const MIN_STEP: f32 = 1e-4;
+
+/// How colors outside of the working space are brought into its gamut.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum GamutMapping {
+    /// Clamp each RGB component
+    #[default]
+    Clip,
+    /// Reduce OKLCH chroma keeping lightness and hue as described in CSS Color 4
+    OklchChroma,
+    /// Pick the in gamut color with the smallest CIEDE2000 difference
+    ClosestDeltaE,
+}
+
+impl GamutMapping {
+    pub const ALL: [GamutMapping; 3] = [
+        GamutMapping::Clip,
+        GamutMapping::OklchChroma,
+        GamutMapping::ClosestDeltaE,
+    ];
+
+    /// Converts `xyz` to RGB in `ws` bringing it into the gamut of the working space if needed.
+    pub fn map(&self, xyz: Xyz, ws: RgbWorkingSpace) -> Rgb {
+        let linear = xyz.linear_rgb(ws);
+        let mapped = if in_gamut(linear) {
+            linear
+        } else {
+            match self {
+                GamutMapping::Clip => clip(linear),
+                GamutMapping::OklchChroma => oklch_chroma_reduction(xyz, ws),
+                GamutMapping::ClosestDeltaE => closest_delta_e(xyz, ws),
+            }
+        };
+        ws.compand_channels(Rgb::from(Matrix1x3::from(mapped)))
+    }
+}
+
+impl AsRef<str> for GamutMapping {
+    fn as_ref(&self) -> &str {
+        match self {
+            GamutMapping::Clip => "clip",
+            GamutMapping::OklchChroma => "OKLCH chroma reduction",
+            GamutMapping::ClosestDeltaE => "closest ΔE",
+        }
+    }
+}
+
+/// Gamut of a working space for checking many colors, like the cells of a slider, with the
+/// RGB matrix inverted only once.
+#[derive(Debug, Copy, Clone)]
+pub struct Gamut(Matrix3);
+
+impl Gamut {
+    pub fn new(ws: RgbWorkingSpace) -> Self {
+        Self(ws.inverse_rgb_matrix())
+    }
+
+    /// Whether `xyz` can be represented in the working space without clipping.
+    pub fn contains(&self, xyz: Xyz) -> bool {
+        let rgb = self.0 * Matrix1x3::from(xyz);
+        in_gamut([rgb[0], rgb[1], rgb[2]])
+    }
+}
+
+impl Xyz {
+    /// Returns unclamped linear RGB components of this color in `ws`.
+    pub fn linear_rgb(&self, ws: RgbWorkingSpace) -> [f32; 3] {
+        let rgb = ws.inverse_rgb_matrix() * Matrix1x3::from(*self);
+        [rgb[0], rgb[1], rgb[2]]
+    }
+
+    /// Whether this color can be represented in `ws` without clipping.
+    pub fn in_gamut(&self, ws: RgbWorkingSpace) -> bool {
+        Gamut::new(ws).contains(*self)
+    }
+}
+
+fn in_gamut(linear: [f32; 3]) -> bool {
+    linear
+        .iter()
+        .all(|c| (-GAMUT_EPSILON..=1. + GAMUT_EPSILON).contains(c))
+}
+
+fn clip(linear: [f32; 3]) -> [f32; 3] {
+    linear.map(|c| c.clamp(0., 1.))
+}
+
+fn linear_to_xyz(linear: [f32; 3], ws: RgbWorkingSpace) -> Xyz {
+    Xyz::from(ws.rgb_matrix() * Matrix1x3::from(linear))
+}
+
+/// Oklab is defined for D65 so colors of working spaces with other white points are adapted
+/// first.
+fn to_oklab(xyz: Xyz, ws: RgbWorkingSpace) -> Oklab {
+    let white = ws.reference_illuminant();
+    let xyz = if white != Illuminant::D65 {
+        xyz.chromatic_adaptation_transform(
+            ChromaticAdaptationMethod::Bradford,
+            white,
+            Illuminant::D65,
+        )
+    } else {
+        xyz
+    };
+    let [r, g, b] = xyz.linear_rgb(RgbWorkingSpace::SRGB);
+    Oklab::from_linear_srgb(r, g, b)
+}
+
+fn from_oklab(oklab: Oklab, ws: RgbWorkingSpace) -> Xyz {
+    let xyz = linear_to_xyz(oklab.to_linear_srgb(), RgbWorkingSpace::SRGB);
+    let white = ws.reference_illuminant();
+    if white != Illuminant::D65 {
+        xyz.chromatic_adaptation_transform(
+            ChromaticAdaptationMethod::Bradford,
+            Illuminant::D65,
+            white,
+        )
+    } else {
+        xyz
+    }
+}
+
+fn delta_e_ok(a: Oklab, b: Oklab) -> f32 {
+    ((a.l() - b.l()).powi(2) + (a.a() - b.a()).powi(2) + (a.b() - b.b()).powi(2)).sqrt()
+}
+
+/// Binary search of the largest OKLCH chroma whose clipped color is not noticeably different,
+/// see <https://www.w3.org/TR/css-color-4/#binsearch>.
+fn oklch_chroma_reduction(xyz: Xyz, ws: RgbWorkingSpace) -> [f32; 3] {
+    let origin = to_oklab(xyz, ws);
+    if origin.l() >= 1. {
+        return [1., 1., 1.];
+    }
+    if origin.l() <= 0. {
+        return [0., 0., 0.];
+    }
+
+    let with_chroma = |chroma: f32| {
+        let scale = chroma / origin.a().hypot(origin.b());
+        Oklab::new(origin.l(), origin.a() * scale, origin.b() * scale)
+    };
+    let clipped_with_delta = |oklab: Oklab| {
+        let clipped = clip(from_oklab(oklab, ws).linear_rgb(ws));
+        let delta = delta_e_ok(to_oklab(linear_to_xyz(clipped, ws), ws), oklab);
+        (clipped, delta)
+    };
+
+    let (mut clipped, delta) = clipped_with_delta(origin);
+    if delta < JND {
+        return clipped;
+    }
+
+    let mut min = 0.;
+    let mut max = origin.a().hypot(origin.b());
+    let mut min_in_gamut = true;
+    while max - min > CHROMA_EPSILON {
+        let chroma = (min + max) / 2.;
+        let current = with_chroma(chroma);
+        let linear = from_oklab(current, ws).linear_rgb(ws);
+        if min_in_gamut && in_gamut(linear) {
+            min = chroma;
+            continue;
+        }
+        let delta;
+        (clipped, delta) = clipped_with_delta(current);
+        if delta < JND {
+            if JND - delta < CHROMA_EPSILON {
+                break;
+            }
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+    clipped
+}
+
+/// Searches the RGB cube for the color with the smallest CIEDE2000 difference, starting from the
+/// better of the clipped and chroma reduced colors.
+fn closest_delta_e(xyz: Xyz, ws: RgbWorkingSpace) -> [f32; 3] {
+    let white = ws.reference_illuminant();
+    let target = Lab::from_xyz(xyz, white);
+    let distance =
+        |linear: [f32; 3]| delta_e2000(&target, &Lab::from_xyz(linear_to_xyz(linear, ws), white));
+
+    let (mut best, mut best_distance) = [clip(xyz.linear_rgb(ws)), oklch_chroma_reduction(xyz, ws)]
+        .into_iter()
+        .map(|linear| (linear, distance(linear)))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .expect("two candidates");
+
+    let mut step = 0.05;
+    while step >= MIN_STEP {
+        let mut improved = false;
+        for channel in 0..3 {
+            for direction in [step, -step] {
+                let mut candidate = best;
+                candidate[channel] = (candidate[channel] + direction).clamp(0., 1.);
+                let candidate_distance = distance(candidate);
+                if candidate_distance < best_distance {
+                    best = candidate;
+                    best_distance = candidate_distance;
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            step /= 2.;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{CIEColor, LchAB, Oklch};
+
+    fn out_of_srgb() -> Xyz {
+        LchAB::new(60., 120., 140.).to_xyz(Illuminant::D65)
+    }
+
+    #[test]
+    fn detects_out_of_gamut() {
+        let ws = RgbWorkingSpace::SRGB;
+        assert!(Xyz::from_rgb(Rgb::new(0.2, 0.9, 0.4), ws).in_gamut(ws));
+        assert!(!out_of_srgb().in_gamut(ws));
+
+        let p3_red = Xyz::from_rgb(Rgb::new(1., 0., 0.), RgbWorkingSpace::DisplayP3);
+        assert!(!p3_red.in_gamut(ws));
+        assert!(p3_red.in_gamut(RgbWorkingSpace::DisplayP3));
+    }
+
+    #[test]
+    fn keeps_in_gamut_colors() {
+        let ws = RgbWorkingSpace::SRGB;
+        let rgb = Rgb::new(0.2, 0.9, 0.4);
+        let xyz = Xyz::from_rgb(rgb, ws);
+        for mapping in GamutMapping::ALL {
+            let mapped = mapping.map(xyz, ws);
+            assert!((mapped.r() - rgb.r()).abs() < 1e-3, "{mapping:?}");
+            assert!((mapped.g() - rgb.g()).abs() < 1e-3, "{mapping:?}");
+            assert!((mapped.b() - rgb.b()).abs() < 1e-3, "{mapping:?}");
+        }
+    }
+
+    #[test]
+    fn oklch_mapping_keeps_lightness_and_hue() {
+        let xyz = out_of_srgb();
+        let origin = Oklch::from(to_oklab(xyz, RgbWorkingSpace::SRGB));
+        let mapped = Oklch::from(Oklab::from_rgb(
+            GamutMapping::OklchChroma.map(xyz, RgbWorkingSpace::SRGB),
+        ));
+        assert!(
+            (mapped.l() - origin.l()).abs() < 0.02,
+            "{mapped:?} {origin:?}"
+        );
+        assert!(
+            (mapped.h() - origin.h()).abs() < 6.,
+            "{mapped:?} {origin:?}"
+        );
+        assert!(mapped.c() < origin.c());
+    }
+
+    #[test]
+    fn closest_delta_e_is_closest() {
+        let ws = RgbWorkingSpace::SRGB;
+        let xyz = out_of_srgb();
+        let target = Lab::from_xyz(xyz, Illuminant::D65);
+        let distance = |mapping: GamutMapping| {
+            let mapped = Xyz::from_rgb(mapping.map(xyz, ws), ws);
+            delta_e2000(&target, &Lab::from_xyz(mapped, Illuminant::D65))
+        };
+        let closest = distance(GamutMapping::ClosestDeltaE);
+        assert!(closest <= distance(GamutMapping::Clip) + 1e-3);
+        assert!(closest <= distance(GamutMapping::OklchChroma) + 1e-3);
+    }
+
+    #[test]
+    fn maps_into_other_working_spaces() {
+        let ws = RgbWorkingSpace::ProPhoto;
+        let xyz = LchAB::new(50., 200., 300.).to_xyz(ws.reference_illuminant());
+        assert!(!xyz.in_gamut(ws));
+        for mapping in GamutMapping::ALL {
+            let mapped = Xyz::from_rgb(mapping.map(xyz, ws), ws);
+            assert!(mapped.in_gamut(ws), "{mapping:?}");
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use crate::color::{
+    Color, ColorVisionDeficiency, Lab, LchAB,
+    difference::{delta_e2000, from_reference_lab, reference_lab},
+    tones::fit_lch_chroma,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Hue distance that spreads successive hues as evenly as possible around the hue circle.
+pub const GOLDEN_ANGLE: f32 = 137.50776;
+/// Number of candidates tried for each color before settling for the most distinct one.
+const MAX_ATTEMPTS: usize = 400;
+
+/// Deterministic SplitMix64 random number generator so that palettes can be reproduced from their
+/// seed.
+#[derive(Debug, Clone)]
+pub struct SeededRng(u64);
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in the range `min ..= max`.
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        let t = (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+        min + (max - min) * t
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum HueSpread {
+    /// Each color is rotated by the golden angle from a random starting hue
+    #[default]
+    GoldenAngle,
+    /// Colors cycle through a set of fixed hues
+    Fixed,
+}
+
+impl HueSpread {
+    pub const ALL: [HueSpread; 2] = [HueSpread::GoldenAngle, HueSpread::Fixed];
+}
+
+impl AsRef<str> for HueSpread {
+    fn as_ref(&self) -> &str {
+        match self {
+            HueSpread::GoldenAngle => "golden angle",
+            HueSpread::Fixed => "fixed hues",
+        }
+    }
+}
+
+/// Constraints of a randomly generated palette. Lightness, chroma and hue are CIE LCh values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteConstraints {
+    pub count: usize,
+    /// Minimum CIEDE2000 difference between any two colors
+    pub min_delta_e: f32,
+    pub lightness: (f32, f32),
+    pub chroma: (f32, f32),
+    pub hue_spread: HueSpread,
+    /// Hues in degrees used with [`HueSpread::Fixed`]
+    pub fixed_hues: Vec<f32>,
+    /// Also require `min_delta_e` between colors as seen with each color vision deficiency
+    pub check_cvd: bool,
+}
+
+impl Default for PaletteConstraints {
+    fn default() -> Self {
+        Self {
+            count: 6,
+            min_delta_e: 15.,
+            lightness: (35., 85.),
+            chroma: (30., 80.),
+            hue_spread: HueSpread::default(),
+            fixed_hues: vec![30., 150., 270.],
+            check_cvd: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedPalette {
+    pub colors: Vec<Color>,
+    /// Whether all generated colors satisfy the constraints
+    pub satisfied: bool,
+}
+
+impl PaletteConstraints {
+    /// Generates `count` colors from `seed`. Colors in `locked` keep their positions and the
+    /// remaining ones are generated to be distinct from them and from each other.
+    ///
+    /// When no candidate satisfying all constraints is found for a color the most distinct one is
+    /// used instead and the result is marked as not satisfied.
+    pub fn generate(&self, locked: &[Option<Color>], seed: u64) -> GeneratedPalette {
+        let mut rng = SeededRng::new(seed);
+        let start_hue = rng.range(0., 360.);
+        let (l_min, l_max) = ordered(self.lightness);
+        let (c_min, c_max) = ordered(self.chroma);
+
+        let slots: Vec<_> = (0..self.count)
+            .map(|i| locked.get(i).copied().flatten())
+            .collect();
+        let mut accepted: Vec<_> = slots.iter().flatten().map(|c| self.labs(c)).collect();
+        let mut satisfied = true;
+
+        let colors = slots
+            .iter()
+            .enumerate()
+            .map(|(i, slot)| {
+                if let Some(color) = slot {
+                    return *color;
+                }
+                let hue = match self.hue_spread {
+                    HueSpread::Fixed if !self.fixed_hues.is_empty() => {
+                        self.fixed_hues[i % self.fixed_hues.len()]
+                    }
+                    HueSpread::Fixed => rng.range(0., 360.),
+                    HueSpread::GoldenAngle => {
+                        (start_hue + GOLDEN_ANGLE * i as f32).rem_euclid(360.)
+                    }
+                };
+
+                let mut best: Option<(bool, f32, Color)> = None;
+                for _ in 0..MAX_ATTEMPTS {
+                    let l = rng.range(l_min, l_max);
+                    let c = fit_lch_chroma(l, rng.range(c_min, c_max), hue);
+                    let color = from_reference_lab(LchAB::new(l, c, hue).into());
+                    let labs = self.labs(&color);
+                    let distance = accepted
+                        .iter()
+                        .map(|other| min_distance(&labs, other))
+                        .fold(f32::INFINITY, f32::min);
+                    let chroma_ok = c >= c_min - 0.5;
+                    let is_better = best.is_none_or(|(best_ok, best_distance, _)| {
+                        (chroma_ok, distance) > (best_ok, best_distance)
+                    });
+                    if is_better {
+                        best = Some((chroma_ok, distance, color));
+                    }
+                    if chroma_ok && distance >= self.min_delta_e {
+                        break;
+                    }
+                }
+                let (chroma_ok, distance, color) = best.unwrap_or((false, 0., Color::black()));
+                satisfied &= chroma_ok && distance >= self.min_delta_e;
+                accepted.push(self.labs(&color));
+                color
+            })
+            .collect();
+
+        GeneratedPalette { colors, satisfied }
+    }
+
+    /// Returns the Lab coordinates of `color` followed by its simulations when checking color
+    /// vision deficiencies.
+    fn labs(&self, color: &Color) -> Vec<Lab> {
+        let mut labs = vec![reference_lab(color)];
+        if self.check_cvd {
+            labs.extend(
+                ColorVisionDeficiency::ALL
+                    .iter()
+                    .map(|d| reference_lab(&color.simulate_cvd(*d))),
+            );
+        }
+        labs
+    }
+}
+
+fn ordered((a, b): (f32, f32)) -> (f32, f32) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+fn min_distance(labs: &[Lab], other: &[Lab]) -> f32 {
+    labs.iter()
+        .zip(other)
+        .map(|(a, b)| delta_e2000(a, b))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Returns the smallest CIEDE2000 difference between any two of `colors`, as seen with
+/// `deficiency` if set.
+pub fn min_pairwise_delta_e(
+    colors: &[Color],
+    deficiency: Option<ColorVisionDeficiency>,
+) -> Option<f32> {
+    let labs: Vec<_> = colors
+        .iter()
+        .map(|c| match deficiency {
+            Some(d) => reference_lab(&c.simulate_cvd(d)),
+            None => reference_lab(c),
+        })
+        .collect();
+    labs.iter()
+        .enumerate()
+        .flat_map(|(i, a)| labs[i + 1..].iter().map(move |b| delta_e2000(a, b)))
+        .reduce(f32::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Rgb;
+
+    #[test]
+    fn reproducible_from_seed() {
+        let constraints = PaletteConstraints::default();
+        let a = constraints.generate(&[], 42);
+        let b = constraints.generate(&[], 42);
+        let c = constraints.generate(&[], 43);
+        assert_eq!(a, b);
+        assert_ne!(a.colors, c.colors);
+        assert_eq!(a.colors.len(), 6);
+    }
+
+    #[test]
+    fn satisfies_constraints() {
+        let constraints = PaletteConstraints {
+            count: 8,
+            min_delta_e: 15.,
+            ..Default::default()
+        };
+        let palette = constraints.generate(&[], 7);
+        assert!(palette.satisfied);
+        assert!(min_pairwise_delta_e(&palette.colors, None).unwrap() >= 15.);
+        for color in &palette.colors {
+            let lch = LchAB::from(reference_lab(color));
+            assert!((34.5..=85.5).contains(&lch.l()), "{}", lch.l());
+            assert!(lch.c() >= 29., "{}", lch.c());
+        }
+    }
+
+    #[test]
+    fn checks_cvd() {
+        let constraints = PaletteConstraints {
+            count: 5,
+            min_delta_e: 10.,
+            check_cvd: true,
+            ..Default::default()
+        };
+        let palette = constraints.generate(&[], 1);
+        assert!(palette.satisfied);
+        for d in ColorVisionDeficiency::ALL {
+            assert!(min_pairwise_delta_e(&palette.colors, Some(d)).unwrap() >= 10.);
+        }
+    }
+
+    #[test]
+    fn keeps_locked_colors() {
+        let locked: Color = Rgb::new_scaled(200, 30, 30).into();
+        let constraints = PaletteConstraints {
+            count: 4,
+            hue_spread: HueSpread::Fixed,
+            fixed_hues: vec![250.],
+            ..Default::default()
+        };
+        let palette = constraints.generate(&[None, Some(locked)], 3);
+        assert_eq!(palette.colors[1], locked);
+        for (i, color) in palette.colors.iter().enumerate() {
+            if i != 1 {
+                let hue = LchAB::from(reference_lab(color)).h();
+                assert!((hue - 250.).abs() < 1., "{hue}");
+            }
+        }
+    }
+
+    #[test]
+    fn reports_unsatisfiable_constraints() {
+        let constraints = PaletteConstraints {
+            count: 10,
+            min_delta_e: 60.,
+            lightness: (50., 55.),
+            ..Default::default()
+        };
+        let palette = constraints.generate(&[], 5);
+        assert!(!palette.satisfied);
+        assert_eq!(palette.colors.len(), 10);
+    }
+}
@@ -0,0 +1,240 @@
+use crate::color::{
+    Color, LchAB, Oklch,
+    difference::{from_reference_lab, reference_lab},
+    tones::{fit_lch_chroma, fit_oklch_chroma},
+};
+
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_ANALOGOUS_SPREAD: f32 = 30.;
+pub const DEFAULT_CUSTOM_HARMONY_ANGLES: [f32; 2] = [150., 210.];
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorHarmony {
+    #[default]
+    Complementary,
+    Triadic,
+    Tetradic,
+    Analogous,
+    #[serde(rename = "split-complementary")]
+    SplitComplementary,
+    Square,
+    Monochromatic,
+    /// Hue rotations defined by the user
+    Custom,
+}
+
+impl ColorHarmony {
+    pub const ALL: [ColorHarmony; 8] = [
+        ColorHarmony::Complementary,
+        ColorHarmony::Triadic,
+        ColorHarmony::Tetradic,
+        ColorHarmony::Analogous,
+        ColorHarmony::SplitComplementary,
+        ColorHarmony::Square,
+        ColorHarmony::Monochromatic,
+        ColorHarmony::Custom,
+    ];
+
+    /// Returns hue rotations in degrees of the colors accompanying the base color. Monochromatic
+    /// harmony keeps the hue and has no rotations.
+    pub fn hue_offsets(&self, analogous_spread: f32, custom_angles: &[f32]) -> Vec<f32> {
+        match self {
+            ColorHarmony::Complementary => vec![180.],
+            ColorHarmony::Triadic => vec![120., 240.],
+            ColorHarmony::Tetradic => vec![60., 180., 240.],
+            ColorHarmony::Analogous => vec![-analogous_spread, analogous_spread],
+            ColorHarmony::SplitComplementary => vec![150., 210.],
+            ColorHarmony::Square => vec![90., 180., 270.],
+            ColorHarmony::Monochromatic => vec![],
+            ColorHarmony::Custom => custom_angles.to_vec(),
+        }
+    }
+}
+
+impl AsRef<str> for ColorHarmony {
+    fn as_ref(&self) -> &str {
+        match &self {
+            ColorHarmony::Complementary => "complementary",
+            ColorHarmony::Triadic => "triadic",
+            ColorHarmony::Tetradic => "tetradic",
+            ColorHarmony::Analogous => "analogous",
+            ColorHarmony::SplitComplementary => "split complementary",
+            ColorHarmony::Square => "square",
+            ColorHarmony::Monochromatic => "monochromatic",
+            ColorHarmony::Custom => "custom",
+        }
+    }
+}
+
+/// Color space in which the hue of harmonies is rotated.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum HarmonySpace {
+    #[default]
+    Hsv,
+    /// Lightness is preserved and chroma is reduced only to stay inside of the sRGB gamut
+    Oklch,
+    /// Same as `Oklch` but in CIE LCh
+    LchAB,
+}
+
+impl HarmonySpace {
+    pub const ALL: [HarmonySpace; 3] =
+        [HarmonySpace::Hsv, HarmonySpace::Oklch, HarmonySpace::LchAB];
+}
+
+impl AsRef<str> for HarmonySpace {
+    fn as_ref(&self) -> &str {
+        match self {
+            HarmonySpace::Hsv => "HSV",
+            HarmonySpace::Oklch => "OKLCH",
+            HarmonySpace::LchAB => "CIE LCh",
+        }
+    }
+}
+
+impl Color {
+    /// Returns colors of `harmony` accompanying this color, the color itself is not included.
+    pub fn harmony(
+        &self,
+        harmony: ColorHarmony,
+        space: HarmonySpace,
+        analogous_spread: f32,
+        custom_angles: &[f32],
+    ) -> Vec<Color> {
+        if harmony == ColorHarmony::Monochromatic {
+            return [0.75, 0.5, 0.25]
+                .into_iter()
+                .map(|k| self.scale_chroma(k, space))
+                .collect();
+        }
+        harmony
+            .hue_offsets(analogous_spread, custom_angles)
+            .into_iter()
+            .map(|offset| self.rotate_hue(offset, space))
+            .collect()
+    }
+
+    /// Rotates the hue of this color by `degrees` in `space`.
+    pub fn rotate_hue(&self, degrees: f32, space: HarmonySpace) -> Color {
+        match space {
+            HarmonySpace::Hsv => {
+                // Black and white have no hue, use the opposite one as their complement
+                if (degrees.rem_euclid(360.) - 180.).abs() < f32::EPSILON {
+                    if self == &Color::black() {
+                        return Color::white();
+                    } else if self == &Color::white() {
+                        return Color::black();
+                    }
+                }
+                self.as_hue_offset(degrees / 360.)
+            }
+            HarmonySpace::Oklch => {
+                let lch = self.oklch();
+                let h = (lch.h() + degrees).rem_euclid(360.);
+                let c = fit_oklch_chroma(lch.l(), lch.c(), h);
+                Oklch::new(lch.l(), c, h).to_rgb().into()
+            }
+            HarmonySpace::LchAB => {
+                let lch = LchAB::from(reference_lab(self));
+                let h = (lch.h() + degrees).rem_euclid(360.);
+                let c = fit_lch_chroma(lch.l(), lch.c(), h);
+                from_reference_lab(LchAB::new(lch.l(), c, h).into())
+            }
+        }
+    }
+
+    /// Scales the saturation or chroma of this color by `factor` keeping its hue and lightness.
+    fn scale_chroma(&self, factor: f32, space: HarmonySpace) -> Color {
+        match space {
+            HarmonySpace::Hsv => self.as_saturation_offset(factor - 1.),
+            HarmonySpace::Oklch => {
+                let lch = self.oklch();
+                Oklch::new(lch.l(), lch.c() * factor, lch.h())
+                    .to_rgb()
+                    .into()
+            }
+            HarmonySpace::LchAB => {
+                let lch = LchAB::from(reference_lab(self));
+                from_reference_lab(LchAB::new(lch.l(), lch.c() * factor, lch.h()).into())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Rgb;
+
+    #[test]
+    fn perceptual_harmonies_keep_lightness() {
+        let color: Color = Rgb::new_scaled(230, 200, 40).into();
+        for harmony in ColorHarmony::ALL {
+            let colors = color.harmony(
+                harmony,
+                HarmonySpace::Oklch,
+                DEFAULT_ANALOGOUS_SPREAD,
+                &DEFAULT_CUSTOM_HARMONY_ANGLES,
+            );
+            for c in colors {
+                assert!(
+                    (c.oklch().l() - color.oklch().l()).abs() < 0.01,
+                    "{harmony:?}"
+                );
+            }
+            let colors = color.harmony(harmony, HarmonySpace::LchAB, 30., &[]);
+            for c in colors {
+                let l = reference_lab(&c).l();
+                assert!((l - reference_lab(&color).l()).abs() < 1., "{harmony:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn rotates_hue() {
+        let color: Color = Rgb::new_scaled(150, 120, 110).into();
+        let hue = color.oklch().h();
+        let triadic = color.harmony(ColorHarmony::Triadic, HarmonySpace::Oklch, 30., &[]);
+        assert!(((triadic[0].oklch().h() - hue).rem_euclid(360.) - 120.).abs() < 2.);
+        assert!(((triadic[1].oklch().h() - hue).rem_euclid(360.) - 240.).abs() < 2.);
+
+        let analogous = color.harmony(ColorHarmony::Analogous, HarmonySpace::Oklch, 10., &[]);
+        assert!(((analogous[0].oklch().h() - hue).rem_euclid(360.) - 350.).abs() < 2.);
+        assert!(((analogous[1].oklch().h() - hue).rem_euclid(360.) - 10.).abs() < 2.);
+    }
+
+    #[test]
+    fn custom_angles() {
+        let color: Color = Rgb::new_scaled(40, 120, 200).into();
+        let colors = color.harmony(
+            ColorHarmony::Custom,
+            HarmonySpace::Hsv,
+            30.,
+            &[30., 90., 180., 300.],
+        );
+        assert_eq!(colors.len(), 4);
+        assert_eq!(
+            colors[2].as_hex(),
+            color.rotate_hue(180., HarmonySpace::Hsv).as_hex()
+        );
+        assert!(
+            color
+                .harmony(ColorHarmony::Custom, HarmonySpace::Oklch, 30., &[])
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn hsv_complementary_of_black_and_white() {
+        let black = Color::black();
+        let white = Color::white();
+        assert_eq!(black.rotate_hue(180., HarmonySpace::Hsv), white);
+        assert_eq!(white.rotate_hue(180., HarmonySpace::Hsv), black);
+        assert_eq!(
+            black.rotate_hue(180., HarmonySpace::Oklch).as_hex(),
+            "#000000"
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use crate::color::{
+    Illuminant, RgbWorkingSpace, Xyz,
+    cam16::{Cam16, Environment, ViewingConditions, lstar_from_y, y_from_lstar},
+    gamut::Gamut,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Number of bisection steps used to find the CAM16 lightness of a tone.
+const TONE_SEARCH_STEPS: u32 = 32;
+/// Upper bound of the CAM16 lightness searched for a tone.
+const MAX_LIGHTNESS: f32 = 200.;
+/// Number of bisection steps used to find the highest chroma in gamut for a tone.
+const CHROMA_SEARCH_STEPS: u32 = 24;
+/// Relative luminance error up to which a color is considered to have the requested tone.
+const TONE_TOLERANCE: f32 = 1e-4;
+
+/// Hue, chroma and tone of Material Design, combining CAM16 hue and chroma under the default
+/// viewing conditions with CIE L* as tone.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Hct {
+    h: f32,
+    c: f32,
+    t: f32,
+}
+
+impl Hct {
+    pub fn new(hue: f32, chroma: f32, tone: f32) -> Self {
+        let hue = if hue.is_nan() { 0. } else { hue };
+        let chroma = if chroma.is_nan() { 0. } else { chroma };
+        let tone = if tone.is_nan() { 0. } else { tone };
+        Self {
+            h: hue,
+            c: chroma,
+            t: tone,
+        }
+    }
+
+    #[inline(always)]
+    /// Returns CAM16 Hue angle in the range of 0.0 ..= 360.0
+    pub fn h(&self) -> f32 {
+        self.h
+    }
+
+    #[inline(always)]
+    /// Returns CAM16 Chroma
+    pub fn c(&self) -> f32 {
+        self.c
+    }
+
+    #[inline(always)]
+    /// Returns Tone in the range of 0.0 ..= 100.0
+    pub fn t(&self) -> f32 {
+        self.t
+    }
+
+    pub fn from_xyz(xyz: Xyz, white: Illuminant) -> Self {
+        let cam = Cam16::from_xyz(xyz, white, &ViewingConditions::default());
+        Self::new(cam.h(), cam.c(), lstar_from_y(xyz.y() / white.xyz().y()))
+    }
+
+    /// Returns the color with this hue and tone in the gamut of `ws`. Like the solver of Material
+    /// HCT the tone is kept and chroma is reduced until the color fits into the gamut.
+    pub fn to_xyz(self, ws: RgbWorkingSpace) -> Xyz {
+        if self.t <= 0. {
+            return Xyz::new(0., 0., 0.);
+        }
+        let white = ws.reference_illuminant().xyz();
+        let y = y_from_lstar(self.t.min(100.));
+        let env = Environment::new(ws.reference_illuminant(), &ViewingConditions::default());
+        let gamut = Gamut::new(ws);
+        let solve = |chroma| {
+            let xyz = tone_xyz(&env, self.h, chroma, y * white.y());
+            let has_tone = (xyz.y() - y * white.y()).abs() <= y * white.y() * TONE_TOLERANCE;
+            (has_tone && gamut.contains(xyz)).then_some(xyz)
+        };
+        if let Some(xyz) = solve(self.c) {
+            return xyz;
+        }
+
+        // A gray of the tone is always in gamut, search the highest chroma between it and the
+        // requested one
+        let mut best =
+            solve(0.).unwrap_or_else(|| Xyz::new(white.x() * y, white.y() * y, white.z() * y));
+        let (mut low, mut high) = (0., self.c);
+        for _ in 0..CHROMA_SEARCH_STEPS {
+            let mid = (low + high) / 2.;
+            match solve(mid) {
+                Some(xyz) => {
+                    best = xyz;
+                    low = mid;
+                }
+                None => high = mid,
+            }
+        }
+        best
+    }
+}
+
+/// Returns the color with hue angle `h` and chroma `c` whose luminance is closest to `target`.
+/// Tones too dark for the chroma give the darkest color with this hue and chroma.
+fn tone_xyz(env: &Environment, h: f32, c: f32, target: f32) -> Xyz {
+    // Luminance grows with lightness at a constant hue and chroma, dark colors with too much
+    // chroma can't be produced so the search moves towards lighter colors
+    let (mut low, mut high) = (0., MAX_LIGHTNESS);
+    for _ in 0..TONE_SEARCH_STEPS {
+        let mid = (low + high) / 2.;
+        if !env.is_realizable(mid, c, h) || env.xyz(mid, c, h).y() < target {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    env.xyz(high, c, h)
+}
+
+//####################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{CIEColor, Rgb, RgbWorkingSpace};
+
+    #[test]
+    fn converts_srgb() {
+        macro_rules! test_case {
+            ($r:expr, $g:expr, $b:expr; $h:expr, $c:expr, $t:expr) => {
+                let rgb = Rgb::new_scaled($r, $g, $b);
+                let xyz = Xyz::from_rgb(rgb, RgbWorkingSpace::SRGB);
+                let got = Hct::from_xyz(xyz, Illuminant::D65);
+                assert!((got.h() - $h).abs() < 0.1, "{got:?}");
+                assert!((got.c() - $c).abs() < 0.1, "{got:?}");
+                assert!((got.t() - $t).abs() < 0.1, "{got:?}");
+
+                let back = got
+                    .to_xyz(RgbWorkingSpace::SRGB)
+                    .to_rgb(RgbWorkingSpace::SRGB);
+                assert!((back.r_scaled() - $r as f32).abs() < 0.5, "{back:?}");
+                assert!((back.g_scaled() - $g as f32).abs() < 0.5, "{back:?}");
+                assert!((back.b_scaled() - $b as f32).abs() < 0.5, "{back:?}");
+            };
+        }
+
+        // Reference values from Material color utilities
+        test_case!(255, 0, 0; 27.408, 113.357, 53.237);
+        test_case!(0, 255, 0; 142.139, 108.410, 87.737);
+        test_case!(0, 0, 255; 282.788, 87.230, 32.302);
+    }
+
+    #[test]
+    fn keeps_tone() {
+        let ws = RgbWorkingSpace::SRGB;
+        for tone in [0., 1., 3., 10., 50., 90., 100.] {
+            let xyz = Hct::new(200., 16., tone).to_xyz(ws);
+            let got = Hct::from_xyz(xyz, Illuminant::D65);
+            assert!((got.t() - tone).abs() < 0.01, "{tone} {got:?}");
+        }
+
+        // Out of gamut requests keep hue and tone and reduce chroma
+        for (hue, tone) in [(200., 1.), (200., 50.), (27.408, 53.237), (282.788, 90.)] {
+            let xyz = Hct::new(hue, 150., tone).to_xyz(ws);
+            assert!(xyz.in_gamut(ws), "{xyz:?}");
+            let got = Hct::from_xyz(xyz, Illuminant::D65);
+            assert!((got.t() - tone).abs() < 0.01, "{tone} {got:?}");
+            assert!(got.c() < 150., "{got:?}");
+            if got.c() > 1. {
+                assert!((got.h() - hue).abs() < 0.5, "{hue} {got:?}");
+            }
+        }
+
+        // The most saturated red of sRGB is at the edge of the gamut
+        let got = Hct::from_xyz(Hct::new(27.408, 150., 53.237).to_xyz(ws), Illuminant::D65);
+        assert!((got.c() - 113.357).abs() < 0.5, "{got:?}");
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{color::Rgb, math};
+
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// Hue, saturation and intensity, where intensity is the mean of the RGB components.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Hsi {
+    h: f32,
+    s: f32,
+    i: f32,
+}
+
+impl Hsi {
+    /// Takes in values in the range 0.0 ..= 1.0 and returns an HSI color
+    pub fn new(hue: f32, saturation: f32, intensity: f32) -> Self {
+        let hue = if hue.is_nan() { 0. } else { hue };
+        let saturation = if saturation.is_nan() { 0. } else { saturation };
+        let intensity = if intensity.is_nan() { 0. } else { intensity };
+        Self {
+            h: hue,
+            s: saturation,
+            i: intensity,
+        }
+    }
+
+    #[inline(always)]
+    /// Returns Hue in the range of 0.0 ..= 1.0
+    pub fn h(&self) -> f32 {
+        self.h
+    }
+
+    #[inline(always)]
+    /// Returns Saturation in the range of 0.0 ..= 1.0
+    pub fn s(&self) -> f32 {
+        self.s
+    }
+
+    #[inline(always)]
+    /// Returns Intensity in the range of 0.0 ..= 1.0
+    pub fn i(&self) -> f32 {
+        self.i
+    }
+
+    /// Returns Hue in the range of 0.0 ..= 360.0
+    pub fn h_scaled(&self) -> f32 {
+        self.h * 360.
+    }
+
+    /// Returns Saturation in the range of 0.0 ..= 100.0
+    pub fn s_scaled(&self) -> f32 {
+        self.s * 100.
+    }
+
+    /// Returns Intensity in the range of 0.0 ..= 100.0
+    pub fn i_scaled(&self) -> f32 {
+        self.i * 100.
+    }
+}
+
+//####################################################################################################
+
+impl From<Rgb> for Hsi {
+    fn from(rgb: Rgb) -> Self {
+        let (r, g, b) = (rgb.r(), rgb.g(), rgb.b());
+        let i = (r + g + b) / 3.;
+        let min = r.min(g.min(b));
+        let s = if i == 0. { 0. } else { 1. - min / i };
+        let h = if s == 0. {
+            0.
+        } else {
+            math::wrap_f32((3f32.sqrt() * (g - b)).atan2(2. * r - g - b) / (2. * PI))
+        };
+        Hsi::new(h, s, i)
+    }
+}
+
+impl From<Hsi> for Rgb {
+    /// Colors with components outside of 0.0 ..= 1.0 are clipped
+    fn from(color: Hsi) -> Self {
+        let (s, i) = (color.s(), color.i());
+        let h = math::wrap_f32(color.h()) * 2. * PI;
+        let sector = (h / (2. * PI / 3.)).floor().min(2.);
+        let h = h - sector * 2. * PI / 3.;
+
+        let low = i * (1. - s);
+        let high = i * (1. + s * h.cos() / (PI / 3. - h).cos());
+        let mid = 3. * i - (low + high);
+        match sector as u8 {
+            0 => Rgb::new(high, mid, low),
+            1 => Rgb::new(low, high, mid),
+            _ => Rgb::new(mid, low, high),
+        }
+    }
+}
+
+//####################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use super::{Hsi, Rgb};
+    #[test]
+    fn rgb_to_hsi() {
+        macro_rules! test_case {
+            (Rgb: $r:expr, $g:expr, $b:expr ;Hsi: $h:expr, $s:expr, $i:expr) => {
+                let got = Hsi::from(Rgb::new_scaled($r, $g, $b));
+                assert!((got.h_scaled() - $h).abs() < 0.1, "{got:?}");
+                assert!((got.s_scaled() - $s).abs() < 0.1, "{got:?}");
+                assert!((got.i_scaled() - $i).abs() < 0.1, "{got:?}");
+                let rgb = Rgb::from(got);
+                assert!((rgb.r_scaled() - $r as f32).abs() < 0.5, "{rgb:?}");
+                assert!((rgb.g_scaled() - $g as f32).abs() < 0.5, "{rgb:?}");
+                assert!((rgb.b_scaled() - $b as f32).abs() < 0.5, "{rgb:?}");
+            };
+        }
+
+        test_case!(Rgb: 0, 0, 0; Hsi: 0., 0., 0.);
+        test_case!(Rgb: 255, 255, 255; Hsi: 0., 0., 100.);
+        test_case!(Rgb: 255, 0, 0; Hsi: 0., 100., 33.33);
+        test_case!(Rgb: 0, 255, 0; Hsi: 120., 100., 33.33);
+        test_case!(Rgb: 0, 0, 255; Hsi: 240., 100., 33.33);
+        test_case!(Rgb: 255, 255, 0; Hsi: 60., 100., 66.67);
+        test_case!(Rgb: 191, 191, 0; Hsi: 60., 100., 49.93);
+        test_case!(Rgb: 160, 164, 36; Hsi: 61.57, 70., 47.06);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::color::{Rgb, hsv::Hsv};
+
+use serde::{Deserialize, Serialize};
+
+/// Hue, whiteness and blackness as defined by CSS Color Module Level 4.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Hwb {
+    h: f32,
+    w: f32,
+    b: f32,
+}
+
+impl Hwb {
+    /// Takes in values in the range 0.0 ..= 1.0 and returns an HWB color
+    pub fn new(hue: f32, whiteness: f32, blackness: f32) -> Self {
+        let hue = if hue.is_nan() { 0. } else { hue };
+        let whiteness = if whiteness.is_nan() { 0. } else { whiteness };
+        let blackness = if blackness.is_nan() { 0. } else { blackness };
+        Self {
+            h: hue,
+            w: whiteness,
+            b: blackness,
+        }
+    }
+
+    #[inline(always)]
+    /// Returns Hue in the range of 0.0 ..= 1.0
+    pub fn h(&self) -> f32 {
+        self.h
+    }
+
+    #[inline(always)]
+    /// Returns Whiteness in the range of 0.0 ..= 1.0
+    pub fn w(&self) -> f32 {
+        self.w
+    }
+
+    #[inline(always)]
+    /// Returns Blackness in the range of 0.0 ..= 1.0
+    pub fn b(&self) -> f32 {
+        self.b
+    }
+
+    /// Returns Hue in the range of 0.0 ..= 360.0
+    pub fn h_scaled(&self) -> f32 {
+        self.h * 360.
+    }
+
+    /// Returns Whiteness in the range of 0.0 ..= 100.0
+    pub fn w_scaled(&self) -> f32 {
+        self.w * 100.
+    }
+
+    /// Returns Blackness in the range of 0.0 ..= 100.0
+    pub fn b_scaled(&self) -> f32 {
+        self.b * 100.
+    }
+}
+
+//####################################################################################################
+
+impl From<Hsv> for Hwb {
+    fn from(color: Hsv) -> Self {
+        Hwb::new(color.h(), (1. - color.s()) * color.v(), 1. - color.v())
+    }
+}
+
+impl From<Hwb> for Hsv {
+    fn from(color: Hwb) -> Self {
+        // Whiteness and blackness adding up to more than 100% are normalized to a gray
+        let sum = color.w() + color.b();
+        let (w, b) = if sum > 1. {
+            (color.w() / sum, color.b() / sum)
+        } else {
+            (color.w(), color.b())
+        };
+        let v = 1. - b;
+        let s = if v == 0. { 0. } else { 1. - w / v };
+        Hsv::new(color.h(), s, v)
+    }
+}
+
+impl From<Rgb> for Hwb {
+    fn from(rgb: Rgb) -> Self {
+        Hsv::from(rgb).into()
+    }
+}
+
+impl From<Hwb> for Rgb {
+    fn from(color: Hwb) -> Self {
+        Hsv::from(color).into()
+    }
+}
+
+//####################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use super::{Hwb, Rgb};
+    #[test]
+    fn rgb_to_hwb() {
+        macro_rules! test_case {
+            (Rgb: $r:expr, $g:expr, $b:expr ;Hwb: $h:expr, $w:expr, $bb:expr) => {
+                let got = Hwb::from(Rgb::new_scaled($r, $g, $b));
+                assert!((got.h_scaled() - $h).abs() < 0.5, "{got:?}");
+                assert!((got.w_scaled() - $w).abs() < 0.5, "{got:?}");
+                assert!((got.b_scaled() - $bb).abs() < 0.5, "{got:?}");
+                let rgb = Rgb::from(got);
+                assert!((rgb.r_scaled() - $r as f32).abs() < 0.5, "{rgb:?}");
+                assert!((rgb.g_scaled() - $g as f32).abs() < 0.5, "{rgb:?}");
+                assert!((rgb.b_scaled() - $b as f32).abs() < 0.5, "{rgb:?}");
+            };
+        }
+
+        test_case!(Rgb: 0, 0, 0; Hwb: 0., 0., 100.);
+        test_case!(Rgb: 255, 255, 255; Hwb: 0., 100., 0.);
+        test_case!(Rgb: 255, 0, 0; Hwb: 0., 0., 0.);
+        test_case!(Rgb: 128, 128, 128; Hwb: 0., 50.2, 49.8);
+        test_case!(Rgb: 102, 153, 51; Hwb: 90., 20., 40.);
+        test_case!(Rgb: 51, 204, 255; Hwb: 195., 20., 0.);
+    }
+
+    #[test]
+    fn normalizes_gray() {
+        let rgb = Rgb::from(Hwb::new(0.3, 0.6, 0.6));
+        assert_eq!(rgb, Rgb::new(0.5, 0.5, 0.5));
+    }
+}
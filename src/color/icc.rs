@@ -0,0 +1,545 @@
+use crate::{
+    color::{
+        ChromaticAdaptationMethod, CustomWorkingSpace, GamutMapping, Illuminant, Rgb,
+        RgbWorkingSpace, TransferFunction, WhitePoint, Xyz, xyY,
+    },
+    math::{Matrix1x3, Matrix3},
+};
+
+use anyhow::{Context, Result, bail};
+use std::{fs, path::Path};
+
+const HEADER_SIZE: usize = 128;
+/// D50 white of the ICC profile connection space.
+const PCS_WHITE: [f32; 3] = [0.9642, 1., 0.8249];
+/// Largest difference of a media white point component still considered to be D50.
+const PCS_WHITE_TOLERANCE: f32 = 0.002;
+/// Range of encoded values whose gamma approximates a sampled tone curve.
+const GAMMA_FIT_RANGE: std::ops::RangeInclusive<f32> = 0.05..=0.95;
+
+/// Curve decoding encoded values of a channel to linear light.
+#[derive(Debug, Clone, PartialEq)]
+enum ToneCurve {
+    /// Parameters `g, a, b, c, d, e, f` of the ICC parametric curve, encoded values `v >= d`
+    /// decode to `(a * v + b) ^ g + e` and lower ones to `c * v + f`
+    Parametric([f32; 7]),
+    /// Linear light at evenly spaced encoded values
+    Sampled(Vec<f32>),
+}
+
+impl ToneCurve {
+    fn gamma(gamma: f32) -> Self {
+        ToneCurve::Parametric([gamma, 1., 0., 0., 0., 0., 0.])
+    }
+
+    fn decode(&self, value: f32) -> f32 {
+        let value = value.clamp(0., 1.);
+        match self {
+            ToneCurve::Parametric([g, a, b, c, d, e, f]) => {
+                if value >= *d {
+                    (a * value + b).max(0.).powf(*g) + e
+                } else {
+                    c * value + f
+                }
+            }
+            ToneCurve::Sampled(samples) => {
+                let position = value * (samples.len() - 1) as f32;
+                let i = (position.floor() as usize).min(samples.len() - 2);
+                let t = position - i as f32;
+                samples[i] + (samples[i + 1] - samples[i]) * t
+            }
+        }
+    }
+
+    /// Returns the closest transfer function of a working space. Offsets of parametric curves
+    /// are dropped and sampled curves are approximated by a gamma.
+    fn transfer_function(&self) -> TransferFunction {
+        match self {
+            ToneCurve::Parametric([g, a, b, c, d, ..]) => {
+                if *a == 1. && *b == 0. && *d == 0. {
+                    TransferFunction::Gamma(*g)
+                } else {
+                    TransferFunction::Parametric {
+                        gamma: *g,
+                        a: *a,
+                        b: *b,
+                        c: *c,
+                        d: *d,
+                    }
+                }
+            }
+            ToneCurve::Sampled(samples) => {
+                let last = (samples.len() - 1) as f32;
+                let gammas: Vec<_> = samples
+                    .iter()
+                    .enumerate()
+                    .map(|(i, linear)| (i as f32 / last, *linear))
+                    .filter(|(v, linear)| GAMMA_FIT_RANGE.contains(v) && *linear > 0.)
+                    .map(|(v, linear)| linear.ln() / v.ln())
+                    .collect();
+                if gammas.is_empty() {
+                    TransferFunction::Gamma(1.)
+                } else {
+                    TransferFunction::Gamma(gammas.iter().sum::<f32>() / gammas.len() as f32)
+                }
+            }
+        }
+    }
+}
+
+/// RGB matrix/TRC ICC profile, as used by most display profiles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IccProfile {
+    pub description: String,
+    /// Converts linear RGB to XYZ relative to the D50 white of the profile connection space
+    matrix: Matrix3,
+    /// Converts XYZ relative to D50 back to the actual white of the device
+    to_media_white: Option<Matrix3>,
+    curves: [ToneCurve; 3],
+}
+
+impl IccProfile {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let data = fs::read(path).context("failed to read ICC profile")?;
+        Self::parse(&data)
+    }
+
+    /// Parses a version 2 or 4 ICC profile of an RGB device with red, green and blue colorant
+    /// and tone curve tags. Profiles using lookup tables are not supported.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if data.len() < HEADER_SIZE + 4 || &data[36..40] != b"acsp" {
+            bail!("not an ICC profile");
+        }
+        if !(2..=4).contains(&data[8]) {
+            bail!("unsupported ICC profile version {}", data[8]);
+        }
+        if &data[16..20] != b"RGB " {
+            bail!("not an RGB profile");
+        }
+        if &data[20..24] != b"XYZ " {
+            bail!("only profiles with an XYZ connection space are supported");
+        }
+
+        let tag_count = read_u32(data, HEADER_SIZE)? as usize;
+        if tag_count > (data.len() - HEADER_SIZE - 4) / 12 {
+            bail!("truncated tag table");
+        }
+        let mut tags = Vec::with_capacity(tag_count);
+        for i in 0..tag_count {
+            let entry = HEADER_SIZE + 4 + i * 12;
+            let signature = data.get(entry..entry + 4).context("truncated tag table")?;
+            let offset = read_u32(data, entry + 4)? as usize;
+            let size = read_u32(data, entry + 8)? as usize;
+            let tag = data
+                .get(offset..offset.saturating_add(size))
+                .context("tag outside of the profile")?;
+            tags.push((signature, tag));
+        }
+        let tag = |signature: &[u8; 4]| {
+            tags.iter()
+                .find(|(s, _)| s == signature)
+                .map(|(_, tag)| *tag)
+        };
+        let required = |signature: &[u8; 4]| {
+            tag(signature).with_context(|| {
+                format!(
+                    "missing {} tag, only matrix/TRC profiles are supported",
+                    String::from_utf8_lossy(signature)
+                )
+            })
+        };
+
+        let [r, g, b] =
+            [b"rXYZ", b"gXYZ", b"bXYZ"].map(|signature| required(signature).and_then(parse_xyz));
+        let (r, g, b) = (r?, g?, b?);
+        let matrix = Matrix3::from([[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]]);
+        let [r, g, b] =
+            [b"rTRC", b"gTRC", b"bTRC"].map(|signature| required(signature).and_then(parse_curve));
+        let curves = [r?, g?, b?];
+
+        let to_media_white = match (tag(b"chad"), tag(b"wtpt")) {
+            (Some(chad), _) => Some(
+                parse_matrix(chad)?
+                    .inverse()
+                    .context("invalid chromatic adaptation matrix")?,
+            ),
+            (None, Some(white)) => {
+                let white = parse_xyz(white)?;
+                let is_d50 = white
+                    .iter()
+                    .zip(PCS_WHITE)
+                    .all(|(c, d50)| (c - d50).abs() < PCS_WHITE_TOLERANCE);
+                (!is_d50).then(|| bradford(PCS_WHITE, white))
+            }
+            (None, None) => None,
+        };
+
+        let description = tag(b"desc")
+            .and_then(parse_description)
+            .unwrap_or_else(|| "display profile".into());
+
+        Ok(Self {
+            description,
+            matrix,
+            to_media_white,
+            curves,
+        })
+    }
+
+    /// Returns XYZ relative to D50 of an RGB color encoded in this profile.
+    fn to_pcs(&self, rgb: Rgb) -> Xyz {
+        let linear = [rgb.r(), rgb.g(), rgb.b()];
+        let linear = [0, 1, 2].map(|i| self.curves[i].decode(linear[i]));
+        Xyz::from(self.matrix * Matrix1x3::from(linear))
+    }
+
+    /// Converts an RGB color encoded in this profile to `ws` using relative colorimetric
+    /// intent, bringing it into the gamut of the working space with `mapping`.
+    pub fn convert(&self, rgb: Rgb, ws: RgbWorkingSpace, mapping: GamutMapping) -> Rgb {
+        // Colorants don't always add up to the exact D50 white so the device white is used
+        let white = self.matrix * Matrix1x3::from([1., 1., 1.]);
+        let xyz = self.to_pcs(rgb).chromatic_adaptation_transform(
+            ChromaticAdaptationMethod::Bradford,
+            Illuminant::Custom(white_point([white[0], white[1], white[2]])),
+            ws.reference_illuminant(),
+        );
+        mapping.map(xyz, ws)
+    }
+
+    /// Returns a working space with the primaries and white point of the device. Channels share
+    /// the transfer function of the green tone curve, so the working space only approximates
+    /// profiles with different or sampled curves.
+    pub fn working_space(&self) -> CustomWorkingSpace {
+        let to_media = |xyz: Matrix1x3| match self.to_media_white {
+            Some(m) => m * xyz,
+            None => xyz,
+        };
+        let primary = |i: usize| {
+            let xyz = Xyz::from(to_media(Matrix1x3::from([
+                self.matrix[0][i],
+                self.matrix[1][i],
+                self.matrix[2][i],
+            ])));
+            let xyy = xyY::from(xyz);
+            (xyy.x(), xyy.y())
+        };
+        let white = Xyz::from(to_media(Matrix1x3::from(PCS_WHITE)));
+        CustomWorkingSpace {
+            red: primary(0),
+            green: primary(1),
+            blue: primary(2),
+            white: white_point([white.x(), white.y(), white.z()]),
+            transfer: self.curves[1].transfer_function(),
+        }
+    }
+}
+
+fn white_point(xyz: [f32; 3]) -> WhitePoint {
+    let xyy = xyY::from(Xyz::new(xyz[0], xyz[1], xyz[2]));
+    WhitePoint::new(xyy.x(), xyy.y())
+}
+
+/// Returns the Bradford matrix adapting colors from the `src` white to the `dst` white.
+fn bradford(src: [f32; 3], dst: [f32; 3]) -> Matrix3 {
+    let ma = ChromaticAdaptationMethod::Bradford.adaptation_matrix();
+    let src = ma * Matrix1x3::from(src);
+    let dst = ma * Matrix1x3::from(dst);
+    let gain = Matrix3::from([
+        [dst[0] / src[0], 0., 0.],
+        [0., dst[1] / src[1], 0.],
+        [0., 0., dst[2] / src[2]],
+    ]);
+    ma.inverse().expect("inverse adaptation matrix") * gain * ma
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+        .context("unexpected end of tag")
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+        .context("unexpected end of tag")
+}
+
+fn read_s15_fixed16(data: &[u8], offset: usize) -> Result<f32> {
+    read_u32(data, offset).map(|v| v as i32 as f32 / 65536.)
+}
+
+fn check_type(tag: &[u8], expected: &[u8; 4]) -> Result<()> {
+    if tag.get(..4) != Some(expected) {
+        bail!(
+            "expected {} tag type, found {}",
+            String::from_utf8_lossy(expected),
+            String::from_utf8_lossy(tag.get(..4).unwrap_or_default())
+        );
+    }
+    Ok(())
+}
+
+fn parse_xyz(tag: &[u8]) -> Result<[f32; 3]> {
+    check_type(tag, b"XYZ ")?;
+    Ok([
+        read_s15_fixed16(tag, 8)?,
+        read_s15_fixed16(tag, 12)?,
+        read_s15_fixed16(tag, 16)?,
+    ])
+}
+
+fn parse_matrix(tag: &[u8]) -> Result<Matrix3> {
+    check_type(tag, b"sf32")?;
+    let mut m = [[0.; 3]; 3];
+    for (i, value) in m.iter_mut().flatten().enumerate() {
+        *value = read_s15_fixed16(tag, 8 + i * 4)?;
+    }
+    Ok(Matrix3::from(m))
+}
+
+fn parse_curve(tag: &[u8]) -> Result<ToneCurve> {
+    match tag.get(..4) {
+        Some(b"curv") => {
+            let count = read_u32(tag, 8)? as usize;
+            match count {
+                0 => Ok(ToneCurve::gamma(1.)),
+                1 => Ok(ToneCurve::gamma(read_u16(tag, 12)? as f32 / 256.)),
+                _ => (0..count)
+                    .map(|i| read_u16(tag, 12 + i * 2).map(|v| v as f32 / u16::MAX as f32))
+                    .collect::<Result<_>>()
+                    .map(ToneCurve::Sampled),
+            }
+        }
+        Some(b"para") => {
+            let function = read_u16(tag, 8)?;
+            let count = match function {
+                0 => 1,
+                1 => 3,
+                2 => 4,
+                3 => 5,
+                4 => 7,
+                _ => bail!("unsupported parametric curve type {function}"),
+            };
+            let p = (0..count)
+                .map(|i| read_s15_fixed16(tag, 12 + i * 4))
+                .collect::<Result<Vec<_>>>()?;
+            // Types 1 and 2 start at -b/a
+            if matches!(function, 1 | 2) && p[1] == 0. {
+                bail!("invalid parametric curve");
+            }
+            // Express all function types with the parameters of type 4
+            Ok(ToneCurve::Parametric(match function {
+                0 => [p[0], 1., 0., 0., 0., 0., 0.],
+                1 => [p[0], p[1], p[2], 0., -p[2] / p[1], 0., 0.],
+                2 => [p[0], p[1], p[2], 0., -p[2] / p[1], p[3], p[3]],
+                3 => [p[0], p[1], p[2], p[3], p[4], 0., 0.],
+                _ => [p[0], p[1], p[2], p[3], p[4], p[5], p[6]],
+            }))
+        }
+        _ => bail!("unsupported tone curve tag type"),
+    }
+}
+
+/// Reads the text of a version 2 `desc` or version 4 `mluc` description tag.
+fn parse_description(tag: &[u8]) -> Option<String> {
+    let text = match tag.get(..4)? {
+        b"desc" => {
+            let count = read_u32(tag, 8).ok()? as usize;
+            String::from_utf8_lossy(tag.get(12..12 + count)?).to_string()
+        }
+        b"mluc" => {
+            let length = read_u32(tag, 20).ok()? as usize;
+            let offset = read_u32(tag, 24).ok()? as usize;
+            let units: Vec<_> = tag
+                .get(offset..offset + length)?
+                .chunks_exact(2)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        _ => return None,
+    };
+    let text = text.trim_end_matches('\0').trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn s15_fixed16(values: &[f32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|v| ((v * 65536.).round() as i32).to_be_bytes())
+            .collect()
+    }
+
+    fn typed(signature: &[u8; 4], data: Vec<u8>) -> Vec<u8> {
+        [signature.as_slice(), &[0; 4], &data].concat()
+    }
+
+    fn xyz_tag(xyz: [f32; 3]) -> Vec<u8> {
+        typed(b"XYZ ", s15_fixed16(&xyz))
+    }
+
+    fn profile(version: u8, tags: Vec<(&[u8; 4], Vec<u8>)>) -> Vec<u8> {
+        let mut header = vec![0; HEADER_SIZE];
+        header[8] = version;
+        header[16..20].copy_from_slice(b"RGB ");
+        header[20..24].copy_from_slice(b"XYZ ");
+        header[36..40].copy_from_slice(b"acsp");
+        let mut table = (tags.len() as u32).to_be_bytes().to_vec();
+        let mut data = vec![];
+        let mut offset = HEADER_SIZE + 4 + tags.len() * 12;
+        for (signature, tag) in &tags {
+            table.extend_from_slice(*signature);
+            table.extend((offset as u32).to_be_bytes());
+            table.extend((tag.len() as u32).to_be_bytes());
+            offset += tag.len();
+            data.extend(tag);
+        }
+        [header, table, data].concat()
+    }
+
+    /// Returns the Bradford matrix from the D65 white to D50.
+    fn d65_to_d50() -> Matrix3 {
+        let d65 = Illuminant::D65.xyz();
+        bradford([d65.x(), d65.y(), d65.z()], PCS_WHITE)
+    }
+
+    /// sRGB colorants adapted to D50 with the Bradford transform.
+    fn srgb_tags() -> Vec<(&'static [u8; 4], Vec<u8>)> {
+        let colorants = d65_to_d50() * RgbWorkingSpace::SRGB.rgb_matrix();
+        let colorant = |i: usize| xyz_tag([0, 1, 2].map(|row| colorants[row][i]));
+        vec![
+            (b"rXYZ", colorant(0)),
+            (b"gXYZ", colorant(1)),
+            (b"bXYZ", colorant(2)),
+            (b"wtpt", xyz_tag(PCS_WHITE)),
+        ]
+    }
+
+    #[test]
+    fn parses_v4_srgb_profile() {
+        let curve = typed(
+            b"para",
+            [
+                vec![0, 3, 0, 0],
+                s15_fixed16(&[2.4, 1. / 1.055, 0.055 / 1.055, 1. / 12.92, 0.04045]),
+            ]
+            .concat(),
+        );
+        let mut tags = srgb_tags();
+        tags.extend([
+            (b"rTRC", curve.clone()),
+            (b"gTRC", curve.clone()),
+            (b"bTRC", curve),
+            (
+                b"chad",
+                typed(b"sf32", s15_fixed16(d65_to_d50().0.as_flattened())),
+            ),
+            (
+                b"desc",
+                typed(
+                    b"mluc",
+                    [
+                        vec![0, 0, 0, 1, 0, 0, 0, 12, b'e', b'n', b'U', b'S', 0, 0, 0, 8],
+                        vec![0, 0, 0, 28, 0, b's', 0, b'R', 0, b'G', 0, b'B'],
+                    ]
+                    .concat(),
+                ),
+            ),
+        ]);
+        let icc = IccProfile::parse(&profile(4, tags)).unwrap();
+        assert_eq!(icc.description, "sRGB");
+
+        let ws = icc.working_space();
+        assert!((ws.red.0 - 0.64).abs() < 1e-3 && (ws.red.1 - 0.33).abs() < 1e-3);
+        assert!((ws.blue.0 - 0.15).abs() < 1e-3 && (ws.blue.1 - 0.06).abs() < 1e-3);
+        assert!((ws.white.x - 0.3127).abs() < 1e-3 && (ws.white.y - 0.329).abs() < 1e-3);
+
+        for rgb in [
+            Rgb::new(1., 1., 1.),
+            Rgb::new(1., 0., 0.),
+            Rgb::new(0.2, 0.5, 0.8),
+        ] {
+            let converted = icc.convert(rgb, RgbWorkingSpace::SRGB, GamutMapping::Clip);
+            assert!(
+                (converted.r() - rgb.r()).abs() < 5e-3,
+                "{converted:?} {rgb:?}"
+            );
+            assert!(
+                (converted.g() - rgb.g()).abs() < 5e-3,
+                "{converted:?} {rgb:?}"
+            );
+            assert!(
+                (converted.b() - rgb.b()).abs() < 5e-3,
+                "{converted:?} {rgb:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parses_v2_curves() {
+        let gamma = typed(b"curv", vec![0, 0, 0, 1, 2, 51]);
+        let sampled = typed(
+            b"curv",
+            [
+                vec![0, 0, 0, 5],
+                [0., 0.25, 0.5, 0.75, 1.]
+                    .iter()
+                    .flat_map(|v: &f32| ((v.powf(2.2) * 65535.).round() as u16).to_be_bytes())
+                    .collect(),
+            ]
+            .concat(),
+        );
+        let mut tags = srgb_tags();
+        tags.extend([
+            (b"rTRC", gamma.clone()),
+            (b"gTRC", sampled),
+            (b"bTRC", gamma),
+            (
+                b"desc",
+                typed(b"desc", [vec![0, 0, 0, 5], b"Test\0".to_vec()].concat()),
+            ),
+        ]);
+        let icc = IccProfile::parse(&profile(2, tags)).unwrap();
+        assert_eq!(icc.description, "Test");
+        assert!((icc.curves[0].decode(0.5) - 0.5f32.powf(2.2)).abs() < 1e-3);
+        assert!((icc.curves[1].decode(0.5) - 0.5f32.powf(2.2)).abs() < 1e-4);
+        let TransferFunction::Gamma(gamma) = icc.working_space().transfer else {
+            panic!("expected gamma transfer function");
+        };
+        assert!((gamma - 2.2).abs() < 0.01, "{gamma}");
+    }
+
+    #[test]
+    fn rejects_unsupported_profiles() {
+        assert!(IccProfile::parse(b"not a profile").is_err());
+        let mut lut_profile = profile(4, srgb_tags());
+        assert!(IccProfile::parse(&lut_profile).is_err());
+        lut_profile[16..20].copy_from_slice(b"CMYK");
+        assert!(IccProfile::parse(&lut_profile).is_err());
+
+        let mut corrupt = profile(4, srgb_tags());
+        corrupt[HEADER_SIZE..HEADER_SIZE + 4].copy_from_slice(&u32::MAX.to_be_bytes());
+        let e = IccProfile::parse(&corrupt).unwrap_err();
+        assert_eq!(e.to_string(), "truncated tag table");
+
+        let flat = typed(
+            b"para",
+            [vec![0, 1, 0, 0], s15_fixed16(&[2.2, 0., 0.1])].concat(),
+        );
+        let mut tags = srgb_tags();
+        tags.extend([
+            (b"rTRC", flat.clone()),
+            (b"gTRC", flat.clone()),
+            (b"bTRC", flat),
+        ]);
+        let e = IccProfile::parse(&profile(4, tags)).unwrap_err();
+        assert!(
+            format!("{e:#}").contains("invalid parametric curve"),
+            "{e:#}"
+        );
+    }
+}
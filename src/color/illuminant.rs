@@ -2,13 +2,74 @@ use crate::color::Xyz;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Deserialize, Serialize, Default)]
+/// Lowest and highest color temperature in kelvin that can be converted to a white point.
+pub const CCT_RANGE: std::ops::RangeInclusive<f32> = 1667.0..=25000.;
+
+/// Chromaticity coordinates of a white point.
+#[derive(Debug, PartialEq, Copy, Clone, Deserialize, Serialize)]
+pub struct WhitePoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Default for WhitePoint {
+    fn default() -> Self {
+        Self::from_cct(6504.)
+    }
+}
+
+impl WhitePoint {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the white point of a light source with correlated color temperature `cct` in
+    /// kelvin. Temperatures from 4000 K follow the CIE daylight locus, lower ones the Planckian
+    /// locus approximation by Kim et al.
+    pub fn from_cct(cct: f32) -> Self {
+        let t = cct.clamp(*CCT_RANGE.start(), *CCT_RANGE.end()) as f64;
+        let (t2, t3) = (t * t, t * t * t);
+        let (x, y) = if t >= 4000. {
+            let x = if t <= 7000. {
+                -4.6070e9 / t3 + 2.9678e6 / t2 + 0.09911e3 / t + 0.244063
+            } else {
+                -2.0064e9 / t3 + 1.9018e6 / t2 + 0.24748e3 / t + 0.237040
+            };
+            (x, -3. * x * x + 2.870 * x - 0.275)
+        } else {
+            let x = -0.2661239e9 / t3 - 0.2343589e6 / t2 + 0.8776956e3 / t + 0.179910;
+            let (x2, x3) = (x * x, x * x * x);
+            let y = if t <= 2222. {
+                -1.1063814 * x3 - 1.34811020 * x2 + 2.18555832 * x - 0.20219683
+            } else {
+                -0.9549476 * x3 - 1.37418593 * x2 + 2.09137015 * x - 0.16748867
+            };
+            (x, y)
+        };
+        Self::new(x as f32, y as f32)
+    }
+
+    /// Returns `true` if the coordinates can be converted to XYZ, that is `x >= 0` and
+    /// `0 < y <= 1`.
+    pub fn is_valid(&self) -> bool {
+        self.x.is_finite() && self.x >= 0. && self.y > 0. && self.y <= 1.
+    }
+
+    /// Returns the white point as XYZ normalized to `Y = 1`.
+    pub fn xyz(&self) -> Xyz {
+        Xyz::new(self.x / self.y, 1., (1. - self.x - self.y) / self.y)
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Deserialize, Serialize, Default)]
 pub enum Illuminant {
     A,
     B,
     C,
     D50,
     D55,
+    /// White point of ACES, close to CIE D60
+    D60,
     #[default]
     D65,
     D75,
@@ -16,9 +77,30 @@ pub enum Illuminant {
     F2,
     F7,
     F11,
+    /// White point of the DCI-P3 projection standard
+    Dci,
+    /// White point defined by the user
+    Custom(WhitePoint),
 }
 
 impl Illuminant {
+    /// Built-in illuminants
+    pub const ALL: [Illuminant; 13] = [
+        Illuminant::A,
+        Illuminant::B,
+        Illuminant::C,
+        Illuminant::D50,
+        Illuminant::D55,
+        Illuminant::D60,
+        Illuminant::D65,
+        Illuminant::D75,
+        Illuminant::E,
+        Illuminant::F2,
+        Illuminant::F7,
+        Illuminant::F11,
+        Illuminant::Dci,
+    ];
+
     #[rustfmt::skip]
     pub fn xyz(&self) -> Xyz {
         match self {
@@ -27,12 +109,15 @@ impl Illuminant {
             Illuminant::C   => Xyz::new(0.98074, 1., 1.18232),
             Illuminant::D50 => Xyz::new(0.96422, 1., 0.82521),
             Illuminant::D55 => Xyz::new(0.95682, 1., 0.92149),
+            Illuminant::D60 => Xyz::new(0.95265, 1., 1.00883),
             Illuminant::D65 => Xyz::new(0.95047, 1., 1.08883),
             Illuminant::D75 => Xyz::new(0.94972, 1., 1.22638),
             Illuminant::E   => Xyz::new(1.     , 1., 1.     ),
             Illuminant::F2  => Xyz::new(0.99186, 1., 0.67393),
             Illuminant::F7  => Xyz::new(0.95041, 1., 1.08747),
             Illuminant::F11 => Xyz::new(1.00962, 1., 0.64350),
+            Illuminant::Dci => Xyz::new(0.89459, 1., 0.95442),
+            Illuminant::Custom(white) => white.xyz(),
         }
     }
     pub fn reference_u(&self) -> f32 {
@@ -52,12 +137,35 @@ impl AsRef<str> for Illuminant {
             C => "C",
             D50 => "D50",
             D55 => "D55",
+            D60 => "D60",
             D65 => "D65",
             D75 => "D75",
             E => "E",
             F2 => "F2",
             F7 => "F7",
             F11 => "F11",
+            Dci => "DCI",
+            Custom(_) => "Custom",
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn white_point_from_cct() {
+        let d65 = WhitePoint::from_cct(6504.);
+        assert!((d65.x - 0.3127).abs() < 1e-3 && (d65.y - 0.3290).abs() < 1e-3);
+        let d50 = WhitePoint::from_cct(5003.);
+        assert!((d50.x - 0.3457).abs() < 1e-3 && (d50.y - 0.3585).abs() < 1e-3);
+        let a = WhitePoint::from_cct(2856.);
+        assert!((a.x - 0.4476).abs() < 2e-3 && (a.y - 0.4074).abs() < 2e-3);
+
+        let xyz = Illuminant::Custom(d65).xyz();
+        let reference = Illuminant::D65.xyz();
+        assert!((xyz.x() - reference.x()).abs() < 5e-3);
+        assert!((xyz.z() - reference.z()).abs() < 5e-3);
+    }
+}
@@ -1,39 +1,95 @@
+mod cam16;
+mod cct;
 mod chromatic_adaptation;
+mod chromaticity;
 mod cmyk;
+mod color_gradient;
+mod cvd;
+mod difference;
+mod duplicates;
 mod format;
+mod gamut;
+mod generator;
 mod gradient;
+mod harmony;
+mod hct;
+mod hsi;
 mod hsl;
 mod hsv;
+mod hwb;
+mod icc;
 mod illuminant;
 mod lab;
 mod lch_ab;
 mod lch_uv;
 mod luv;
+mod names;
+mod observer;
+mod oklab;
+mod oklch;
 mod palette;
 mod palettes;
+mod pick_history;
 mod rgb;
+mod sort;
+mod spectrum;
+mod terminal;
+mod tokens;
+mod tonal_scale;
+mod tones;
 mod working_space;
 mod xyy;
 mod xyz;
-
+mod ycbcr;
+mod yuv;
+
+pub use cam16::{Cam16, Cam16Ucs, Surround, ViewingConditions};
+pub use cct::{CctLocus, DUV_RANGE, KELVIN_RANGE};
+pub use chromaticity::{ChromaticityDiagram, brightest_rgb, spectral_locus};
+pub use color_gradient::{ColorGradient, InterpolationSpace};
+pub use cvd::ColorVisionDeficiency;
+pub use difference::delta_e2000;
+pub use duplicates::{DEFAULT_NEAR_DUPLICATE_TOLERANCE, NearDuplicate};
 pub use format::CustomPaletteFormat;
+pub use gamut::{Gamut, GamutMapping};
+pub use generator::{HueSpread, PaletteConstraints, min_pairwise_delta_e};
 pub use gradient::Gradient;
-pub use palette::{NamedPalette, Palette, PaletteFormat};
+pub use harmony::{
+    ColorHarmony, DEFAULT_ANALOGOUS_SPREAD, DEFAULT_CUSTOM_HARMONY_ANGLES, HarmonySpace,
+};
+pub use names::ColorName;
+pub use observer::Observer;
+pub use palette::{NamedPalette, Palette, PaletteEntry, PaletteFormat};
 pub use palettes::Palettes;
+pub use pick_history::{DEFAULT_PICK_HISTORY_LIMIT, PickHistory, format_elapsed};
+pub use sort::{DEFAULT_GROUP_THRESHOLD, PaletteSort};
+pub use spectrum::{SpectralConversion, Spectrum, SpectrumKind, parse_spectra};
+pub use terminal::{SlotMapping, TerminalFormat, TerminalSlot};
+pub use tokens::TokenNotation;
+pub use tonal_scale::TonalScale;
+pub use tones::{PerceptualSpace, ToneKind, ToneMode};
 
 pub use chromatic_adaptation::ChromaticAdaptationMethod;
 pub use cmyk::Cmyk;
+pub use hct::Hct;
+pub use hsi::Hsi;
 pub use hsl::Hsl;
 pub use hsv::Hsv;
-pub use illuminant::Illuminant;
+pub use hwb::Hwb;
+pub use icc::IccProfile;
+pub use illuminant::{CCT_RANGE, Illuminant, WhitePoint};
 pub use lab::Lab;
 pub use lch_ab::LchAB;
 pub use lch_uv::LchUV;
 pub use luv::Luv;
+pub use oklab::Oklab;
+pub use oklch::Oklch;
 pub use rgb::Rgb;
-pub use working_space::RgbWorkingSpace;
+pub use working_space::{CustomWorkingSpace, RgbWorkingSpace, TransferFunction};
 pub use xyy::xyY;
 pub use xyz::Xyz;
+pub use ycbcr::{YCBCR_BIT_DEPTHS, YCbCr, YCbCrRange, YCbCrStandard};
+pub use yuv::{Yiq, Yuv};
 
 use crate::color::format::CustomColorFormat;
 use egui::ecolor::{Color32, Hsva, HsvaGamma, Rgba};
@@ -78,36 +134,6 @@ pub fn parse_hex(color: &str) -> Option<(u8, u8, u8)> {
 
 //################################################################################
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, Default)]
-#[serde(rename_all = "lowercase")]
-pub enum ColorHarmony {
-    #[default]
-    Complementary,
-    Triadic,
-    Tetradic,
-    Analogous,
-    #[serde(rename = "split-complementary")]
-    SplitComplementary,
-    Square,
-    Monochromatic,
-}
-
-impl AsRef<str> for ColorHarmony {
-    fn as_ref(&self) -> &str {
-        match &self {
-            ColorHarmony::Complementary => "complementary",
-            ColorHarmony::Triadic => "triadic",
-            ColorHarmony::Tetradic => "tetradic",
-            ColorHarmony::Analogous => "analogous",
-            ColorHarmony::SplitComplementary => "split complementary",
-            ColorHarmony::Square => "square",
-            ColorHarmony::Monochromatic => "monochromatic",
-        }
-    }
-}
-
-//################################################################################
-
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub enum ColorFormat<'fmt> {
     #[serde(rename = "hex")]
@@ -120,6 +146,10 @@ pub enum ColorFormat<'fmt> {
     CssHsl {
         degree_symbol: bool,
     },
+    #[serde(rename = "css-display-p3")]
+    CssDisplayP3,
+    #[serde(rename = "css-hwb")]
+    CssHwb,
     Custom(&'fmt str),
 }
 
@@ -217,6 +247,29 @@ impl Color {
         )
     }
 
+    /// Returns the color in the CSS `color(display-p3 r g b)` notation.
+    pub fn as_css_display_p3(&self) -> String {
+        let p3 =
+            Xyz::from_rgb(self.rgb(), RgbWorkingSpace::SRGB).to_rgb(RgbWorkingSpace::DisplayP3);
+        format!(
+            "color(display-p3 {:.4} {:.4} {:.4})",
+            p3.r(),
+            p3.g(),
+            p3.b()
+        )
+    }
+
+    /// Returns the color in the CSS Color 4 `hwb(h w% b%)` notation.
+    pub fn as_css_hwb(&self) -> String {
+        let color = self.hwb();
+        format!(
+            "hwb({} {}% {}%)",
+            color.h_scaled().round() as u16 % 360,
+            color.w_scaled().round() as u16,
+            color.b_scaled().round() as u16
+        )
+    }
+
     pub fn display(
         &self,
         format: ColorFormat,
@@ -228,6 +281,8 @@ impl Color {
             ColorFormat::HexUpercase => self.as_hex().to_uppercase(),
             ColorFormat::CssRgb => self.as_css_rgb(),
             ColorFormat::CssHsl { degree_symbol } => self.as_css_hsl(degree_symbol),
+            ColorFormat::CssDisplayP3 => self.as_css_display_p3(),
+            ColorFormat::CssHwb => self.as_css_hwb(),
             ColorFormat::Custom(fmt) => {
                 if let Ok(fmt) = CustomColorFormat::parse(fmt) {
                     fmt.format_color(self, ws, illuminant).unwrap_or_default()
@@ -292,6 +347,26 @@ impl Color {
         self.into()
     }
 
+    pub fn hwb(&self) -> Hwb {
+        self.rgb().into()
+    }
+
+    pub fn hsi(&self) -> Hsi {
+        self.rgb().into()
+    }
+
+    pub fn ycbcr(&self, standard: YCbCrStandard) -> YCbCr {
+        YCbCr::from_rgb(self.rgb(), standard)
+    }
+
+    pub fn yuv(&self) -> Yuv {
+        self.rgb().into()
+    }
+
+    pub fn yiq(&self) -> Yiq {
+        self.rgb().into()
+    }
+
     pub fn lab(
         &self,
         ws: RgbWorkingSpace,
@@ -316,6 +391,19 @@ impl Color {
         self.lab(ws, ref_white, method).into()
     }
 
+    /// Returns the CAM16 appearance of this color adapted to the reference white of `ws`.
+    pub fn cam16(&self, ws: RgbWorkingSpace, conditions: &ViewingConditions) -> Cam16 {
+        Cam16::from_xyz(self.xyz(ws), ws.reference_illuminant(), conditions)
+    }
+
+    pub fn cam16_ucs(&self, ws: RgbWorkingSpace, conditions: &ViewingConditions) -> Cam16Ucs {
+        Cam16Ucs::from_xyz(self.xyz(ws), ws.reference_illuminant(), conditions)
+    }
+
+    pub fn hct(&self, ws: RgbWorkingSpace) -> Hct {
+        Hct::from_xyz(self.xyz(ws), ws.reference_illuminant())
+    }
+
     pub fn luv(&self, ws: RgbWorkingSpace) -> Luv {
         Xyz::from_rgb(self.rgb(), ws).into()
     }
@@ -328,6 +416,14 @@ impl Color {
         self.into()
     }
 
+    pub fn oklab(&self) -> Oklab {
+        self.rgb().into()
+    }
+
+    pub fn oklch(&self) -> Oklch {
+        self.rgb().into()
+    }
+
     pub fn xyz(&self, working_space: RgbWorkingSpace) -> Xyz {
         Xyz::from_rgb(self.rgb(), working_space)
     }
@@ -408,55 +504,6 @@ impl Color {
 
         colors
     }
-
-    pub fn complementary(&self) -> Color {
-        let white = Self::white();
-        let black = Self::black();
-
-        if self == &black {
-            return white;
-        } else if self == &white {
-            return black;
-        }
-
-        self.as_hue_offset(6. / 12.)
-    }
-
-    pub fn triadic(&self) -> (Color, Color) {
-        (self.as_hue_offset(4. / 12.), self.as_hue_offset(8. / 12.))
-    }
-
-    pub fn tetradic(&self) -> (Color, Color, Color) {
-        (
-            self.as_hue_offset(2. / 12.),
-            self.complementary(),
-            self.as_hue_offset(8. / 12.),
-        )
-    }
-
-    pub fn analogous(&self) -> (Color, Color) {
-        (self.as_hue_offset(-1. / 12.), self.as_hue_offset(1. / 12.))
-    }
-
-    pub fn split_complementary(&self) -> (Color, Color) {
-        (self.as_hue_offset(5. / 12.), self.as_hue_offset(7. / 12.))
-    }
-
-    pub fn square(&self) -> (Color, Color, Color) {
-        (
-            self.as_hue_offset(3. / 12.),
-            self.complementary(),
-            self.as_hue_offset(9. / 12.),
-        )
-    }
-
-    pub fn monochromatic(&self) -> (Color, Color, Color) {
-        (
-            self.as_saturation_offset(-3. / 12.),
-            self.as_saturation_offset(-6. / 12.),
-            self.as_saturation_offset(-9. / 12.),
-        )
-    }
 }
 
 //##################################################################################################
@@ -581,7 +628,28 @@ impl From<Rgb> for Color {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_hex;
+    use super::{Color, Rgb, parse_hex};
+
+    #[test]
+    fn formats_css_display_p3() {
+        let red: Color = Rgb::new(1., 0., 0.).into();
+        assert_eq!(
+            red.as_css_display_p3(),
+            "color(display-p3 0.9175 0.2003 0.1386)"
+        );
+        assert_eq!(
+            Color::white().as_css_display_p3(),
+            "color(display-p3 1.0000 1.0000 1.0000)"
+        );
+    }
+
+    #[test]
+    fn formats_css_hwb() {
+        let green: Color = Rgb::new_scaled(102, 153, 51).into();
+        assert_eq!(green.as_css_hwb(), "hwb(90 20% 40%)");
+        assert_eq!(Color::black().as_css_hwb(), "hwb(0 0% 100%)");
+    }
+
     #[test]
     fn parses_hex() {
         macro_rules! test_case {
@@ -0,0 +1,159 @@
+use crate::color::Xyz;
+
+use serde::{Deserialize, Serialize};
+
+/// First wavelength in nanometers of the color matching function tables.
+pub const CMF_START: f32 = 380.;
+/// Last wavelength in nanometers of the color matching function tables.
+pub const CMF_END: f32 = 780.;
+/// Distance in nanometers between the rows of the color matching function tables.
+pub const CMF_STEP: f32 = 10.;
+
+/// CIE 1931 2° standard observer color matching functions x̄, ȳ, z̄ from 380 to 780 nm.
+#[rustfmt::skip]
+const CIE1931: [[f32; 3]; 41] = [
+    [0.001368, 0.000039, 0.00645], // 380
+    [0.004243, 0.00012 , 0.02005], // 390
+    [0.01431 , 0.000396, 0.06785], // 400
+    [0.04351 , 0.00121 , 0.2074], // 410
+    [0.13438 , 0.004   , 0.6456], // 420
+    [0.2839  , 0.0116  , 1.3856], // 430
+    [0.34828 , 0.023   , 1.74706], // 440
+    [0.3362  , 0.038   , 1.77211], // 450
+    [0.2908  , 0.06    , 1.6692], // 460
+    [0.19536 , 0.09098 , 1.28764], // 470
+    [0.09564 , 0.13902 , 0.81295], // 480
+    [0.03201 , 0.20802 , 0.46518], // 490
+    [0.0049  , 0.323   , 0.272], // 500
+    [0.0093  , 0.503   , 0.1582], // 510
+    [0.06327 , 0.71    , 0.07825], // 520
+    [0.1655  , 0.862   , 0.04216], // 530
+    [0.2904  , 0.954   , 0.0203], // 540
+    [0.43345 , 0.99495 , 0.00875], // 550
+    [0.5945  , 0.995   , 0.0039], // 560
+    [0.7621  , 0.952   , 0.0021], // 570
+    [0.9163  , 0.87    , 0.00165], // 580
+    [1.0263  , 0.757   , 0.0011], // 590
+    [1.0622  , 0.631   , 0.0008], // 600
+    [1.0026  , 0.503   , 0.00034], // 610
+    [0.85445 , 0.381   , 0.00019], // 620
+    [0.6424  , 0.265   , 0.00005], // 630
+    [0.4479  , 0.175   , 0.00002], // 640
+    [0.2835  , 0.107   , 0.0], // 650
+    [0.1649  , 0.061   , 0.0], // 660
+    [0.0874  , 0.032   , 0.0], // 670
+    [0.04677 , 0.017   , 0.0], // 680
+    [0.0227  , 0.00821 , 0.0], // 690
+    [0.011359, 0.004102, 0.0], // 700
+    [0.00579 , 0.002091, 0.0], // 710
+    [0.002899, 0.001047, 0.0], // 720
+    [0.00144 , 0.00052 , 0.0], // 730
+    [0.00069 , 0.000249, 0.0], // 740
+    [0.000332, 0.00012 , 0.0], // 750
+    [0.000166, 0.00006 , 0.0], // 760
+    [0.000083, 0.00003 , 0.0], // 770
+    [0.000042, 0.000015, 0.0], // 780
+];
+
+/// CIE 1964 10° standard observer color matching functions x̄₁₀, ȳ₁₀, z̄₁₀ from 380 to 780 nm.
+#[rustfmt::skip]
+const CIE1964: [[f32; 3]; 41] = [
+    [0.00016 , 0.000017, 0.000705], // 380
+    [0.002362, 0.000253, 0.010482], // 390
+    [0.01911 , 0.002004, 0.086011], // 400
+    [0.084736, 0.008756, 0.389366], // 410
+    [0.204492, 0.021391, 0.972542], // 420
+    [0.314679, 0.038676, 1.55348], // 430
+    [0.383734, 0.062077, 1.96728], // 440
+    [0.370702, 0.089456, 1.9948], // 450
+    [0.302273, 0.128201, 1.74537], // 460
+    [0.195618, 0.18519 , 1.31756], // 470
+    [0.080507, 0.253589, 0.772125], // 480
+    [0.016172, 0.339133, 0.415254], // 490
+    [0.003816, 0.460777, 0.218502], // 500
+    [0.037465, 0.606741, 0.112044], // 510
+    [0.117749, 0.761757, 0.060709], // 520
+    [0.236491, 0.875211, 0.030451], // 530
+    [0.376772, 0.961988, 0.013676], // 540
+    [0.529826, 0.991761, 0.003988], // 550
+    [0.705224, 0.99734 , 0.0], // 560
+    [0.878655, 0.955552, 0.0], // 570
+    [1.01416 , 0.868934, 0.0], // 580
+    [1.11852 , 0.777405, 0.0], // 590
+    [1.12399 , 0.658341, 0.0], // 600
+    [1.03048 , 0.527963, 0.0], // 610
+    [0.856297, 0.398057, 0.0], // 620
+    [0.647467, 0.283493, 0.0], // 630
+    [0.431567, 0.179828, 0.0], // 640
+    [0.268329, 0.107633, 0.0], // 650
+    [0.152568, 0.060281, 0.0], // 660
+    [0.081261, 0.0318  , 0.0], // 670
+    [0.040851, 0.015905, 0.0], // 680
+    [0.019941, 0.007749, 0.0], // 690
+    [0.009577, 0.003718, 0.0], // 700
+    [0.004553, 0.001768, 0.0], // 710
+    [0.002175, 0.000846, 0.0], // 720
+    [0.001045, 0.000407, 0.0], // 730
+    [0.000508, 0.000199, 0.0], // 740
+    [0.000251, 0.000098, 0.0], // 750
+    [0.000126, 0.00005 , 0.0], // 760
+    [0.000065, 0.000025, 0.0], // 770
+    [0.000033, 0.000013, 0.0], // 780
+];
+
+/// Standard observer whose color matching functions are used to integrate spectra.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum Observer {
+    /// CIE 1931 2° standard observer, used by all working spaces and white points
+    #[default]
+    Cie1931,
+    /// CIE 1964 10° standard observer, better suited for samples seen at a larger angle
+    Cie1964,
+}
+
+impl Observer {
+    pub const ALL: [Observer; 2] = [Observer::Cie1931, Observer::Cie1964];
+
+    /// Returns the color matching functions at `wavelength` in nanometers, linearly interpolated
+    /// between table rows and zero outside of the table.
+    pub fn cmf(&self, wavelength: f32) -> Xyz {
+        match self {
+            Observer::Cie1931 => interpolate(&CIE1931, wavelength),
+            Observer::Cie1964 => interpolate(&CIE1964, wavelength),
+        }
+    }
+}
+
+impl AsRef<str> for Observer {
+    fn as_ref(&self) -> &str {
+        match self {
+            Observer::Cie1931 => "CIE 1931 2°",
+            Observer::Cie1964 => "CIE 1964 10°",
+        }
+    }
+}
+
+fn interpolate(table: &[[f32; 3]], wavelength: f32) -> Xyz {
+    if !(CMF_START..=CMF_END).contains(&wavelength) {
+        return Xyz::new(0., 0., 0.);
+    }
+    let position = (wavelength - CMF_START) / CMF_STEP;
+    let i = (position.floor() as usize).min(table.len() - 2);
+    let t = position - i as f32;
+    let [x, y, z] = [0, 1, 2].map(|c| table[i][c] + (table[i + 1][c] - table[i][c]) * t);
+    Xyz::new(x, y, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolates_table() {
+        let at_555 = Observer::Cie1931.cmf(555.);
+        assert!((at_555.y() - 0.994975).abs() < 1e-5);
+        assert_eq!(Observer::Cie1931.cmf(800.), Xyz::new(0., 0., 0.));
+        assert!((Observer::Cie1931.cmf(780.).x() - 0.000042).abs() < 1e-7);
+        assert!((Observer::Cie1964.cmf(555.).y() - 0.99455).abs() < 1e-5);
+    }
+}
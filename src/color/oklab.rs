@@ -0,0 +1,138 @@
+use crate::color::{Rgb, oklch::Oklch};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Oklab {
+    l: f32,
+    a: f32,
+    b: f32,
+}
+
+impl Oklab {
+    pub fn new(l: f32, a: f32, b: f32) -> Self {
+        let l = if l.is_nan() { 0. } else { l };
+        let a = if a.is_nan() { 0. } else { a };
+        let b = if b.is_nan() { 0. } else { b };
+
+        Self { l, a, b }
+    }
+
+    #[inline(always)]
+    /// Returns Light in the range 0.0 ..= 1.0
+    pub fn l(&self) -> f32 {
+        self.l
+    }
+
+    #[inline(always)]
+    /// Returns A coordinate
+    pub fn a(&self) -> f32 {
+        self.a
+    }
+
+    #[inline(always)]
+    /// Returns B coordinate
+    pub fn b(&self) -> f32 {
+        self.b
+    }
+
+    /// Converts linear sRGB components to Oklab. The components are not clamped so colors
+    /// outside of sRGB gamut can be converted as well.
+    pub fn from_linear_srgb(r: f32, g: f32, b: f32) -> Self {
+        let l = 0.41222147 * r + 0.53633254 * g + 0.05144599 * b;
+        let m = 0.2119035 * r + 0.6806995 * g + 0.10739696 * b;
+        let s = 0.08830246 * r + 0.28171885 * g + 0.6299787 * b;
+
+        let l = l.cbrt();
+        let m = m.cbrt();
+        let s = s.cbrt();
+
+        Self::new(
+            0.21045426 * l + 0.7936178 * m - 0.004072047 * s,
+            1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+            0.025904037 * l + 0.78277177 * m - 0.80867577 * s,
+        )
+    }
+
+    /// Returns unclamped linear sRGB components of this color.
+    pub fn to_linear_srgb(self) -> [f32; 3] {
+        let l = self.l + 0.39633778 * self.a + 0.21580376 * self.b;
+        let m = self.l - 0.105561346 * self.a - 0.06385417 * self.b;
+        let s = self.l - 0.08948418 * self.a - 1.2914855 * self.b;
+
+        let l = l.powi(3);
+        let m = m.powi(3);
+        let s = s.powi(3);
+
+        [
+            4.0767417 * l - 3.3077116 * m + 0.23096994 * s,
+            -1.268438 * l + 2.6097574 * m - 0.34131938 * s,
+            -0.0041960864 * l - 0.7034186 * m + 1.7076147 * s,
+        ]
+    }
+
+    pub fn from_rgb(rgb: Rgb) -> Self {
+        let rgb = rgb.inverse_srgb_compand();
+        Self::from_linear_srgb(rgb.r(), rgb.g(), rgb.b())
+    }
+
+    pub fn to_rgb(self) -> Rgb {
+        let [r, g, b] = self.to_linear_srgb();
+        Rgb::new(r, g, b).srgb_compand()
+    }
+}
+
+//####################################################################################################
+
+impl From<Oklch> for Oklab {
+    fn from(color: Oklch) -> Self {
+        let h = color.h().to_radians();
+
+        let l = color.l();
+        let a = color.c() * h.cos();
+        let b = color.c() * h.sin();
+
+        Self { l, a, b }
+    }
+}
+
+impl From<Rgb> for Oklab {
+    fn from(rgb: Rgb) -> Self {
+        Self::from_rgb(rgb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn converts_srgb() {
+        macro_rules! test_case {
+            ($r:expr, $g:expr, $b:expr, $l:expr, $a:expr, $bb:expr) => {
+                let got = Oklab::from_rgb(Rgb::new_scaled($r, $g, $b));
+                assert!((got.l() - $l).abs() < 1e-3, "{got:?}");
+                assert!((got.a() - $a).abs() < 1e-3, "{got:?}");
+                assert!((got.b() - $bb).abs() < 1e-3, "{got:?}");
+                let rgb = got.to_rgb();
+                assert!((rgb.r_scaled() - $r as f32).abs() < 0.5, "{rgb:?}");
+                assert!((rgb.g_scaled() - $g as f32).abs() < 0.5, "{rgb:?}");
+                assert!((rgb.b_scaled() - $b as f32).abs() < 0.5, "{rgb:?}");
+            };
+        }
+
+        test_case!(255, 255, 255, 1., 0., 0.);
+        test_case!(0, 0, 0, 0., 0., 0.);
+        test_case!(255, 0, 0, 0.62796, 0.22486, 0.12585);
+        test_case!(0, 255, 0, 0.86644, -0.23389, 0.1795);
+        test_case!(0, 0, 255, 0.45201, -0.03246, -0.31153);
+    }
+
+    #[test]
+    fn oklch_roundtrip() {
+        let inp = Oklab::new(0.5, 0.1, -0.1);
+        let got = Oklab::from(Oklch::from(inp));
+
+        assert!((got.a() - inp.a()).abs() < 1e-6);
+        assert!((got.b() - inp.b()).abs() < 1e-6);
+    }
+}
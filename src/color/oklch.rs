@@ -0,0 +1,69 @@
+use crate::color::{Rgb, oklab::Oklab};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Oklch {
+    l: f32,
+    c: f32,
+    h: f32,
+}
+
+impl Oklch {
+    pub fn new(l: f32, c: f32, h: f32) -> Self {
+        let l = if l.is_nan() { 0. } else { l };
+        let c = if c.is_nan() { 0. } else { c };
+        let h = if h.is_nan() { 0. } else { h };
+
+        Self { l, c, h }
+    }
+
+    #[inline(always)]
+    /// Returns Light in the range 0.0 ..= 1.0
+    pub fn l(&self) -> f32 {
+        self.l
+    }
+
+    #[inline(always)]
+    /// Returns Chroma
+    pub fn c(&self) -> f32 {
+        self.c
+    }
+
+    #[inline(always)]
+    /// Returns Hue in the range of 0.0 ..= 360.0
+    pub fn h(&self) -> f32 {
+        self.h
+    }
+
+    pub fn from_rgb(rgb: Rgb) -> Self {
+        Oklab::from_rgb(rgb).into()
+    }
+
+    pub fn to_rgb(self) -> Rgb {
+        Oklab::from(self).to_rgb()
+    }
+}
+
+//####################################################################################################
+
+impl From<Oklab> for Oklch {
+    fn from(color: Oklab) -> Self {
+        let arctan_ba = f32::atan2(color.b(), color.a()).to_degrees();
+        let l = color.l();
+        let c = (color.a().powi(2) + color.b().powi(2)).sqrt();
+        let h = if arctan_ba >= 0. {
+            arctan_ba
+        } else {
+            arctan_ba + 360.
+        };
+
+        Self::new(l, c, h)
+    }
+}
+
+impl From<Rgb> for Oklch {
+    fn from(rgb: Rgb) -> Self {
+        Self::from_rgb(rgb)
+    }
+}
@@ -1,4 +1,7 @@
-use crate::color::{Color, CustomPaletteFormat, Illuminant, RgbWorkingSpace};
+use crate::color::{
+    Color, CustomPaletteFormat, Illuminant, RgbWorkingSpace, SlotMapping, TerminalFormat,
+    TokenNotation,
+};
 
 use serde::{Deserialize, Serialize};
 use std::fmt::Write as _;
@@ -28,6 +31,14 @@ impl NamedPalette {
         match format {
             PaletteFormat::Gimp => self.palette.as_gimp_palette(&self.name),
             PaletteFormat::HexList => self.palette.as_hex_list(),
+            PaletteFormat::DesignTokens(notation) => self.as_design_tokens(*notation),
+            PaletteFormat::CssVariables(notation) => self.as_css_variables(*notation),
+            PaletteFormat::ScssVariables(notation) => self.as_scss_variables(*notation),
+            PaletteFormat::LessVariables(notation) => self.as_less_variables(*notation),
+            PaletteFormat::Tailwind(notation) => self.as_tailwind_config(*notation),
+            PaletteFormat::Terminal(format) => {
+                self.as_terminal_theme(*format, &SlotMapping::auto(self))
+            }
             PaletteFormat::Custom(_, fmt) => fmt
                 .format_palette(&self.palette, ws, illuminant)
                 .unwrap_or_default(),
@@ -35,36 +46,91 @@ impl NamedPalette {
     }
 }
 
+/// A single color of a palette with an optional name and note attached to it.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct PaletteEntry {
+    pub color: Color,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl PaletteEntry {
+    pub fn new(color: Color) -> Self {
+        Self {
+            color,
+            name: None,
+            note: None,
+        }
+    }
+
+    pub fn named(color: Color, name: impl Into<String>) -> Self {
+        Self {
+            color,
+            name: Some(name.into()),
+            note: None,
+        }
+    }
+
+    /// Returns the name of this entry or `None` if it's not set or empty.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref().filter(|name| !name.is_empty())
+    }
+
+    /// Returns the note of this entry or `None` if it's not set or empty.
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref().filter(|note| !note.is_empty())
+    }
+}
+
+impl From<Color> for PaletteEntry {
+    fn from(color: Color) -> Self {
+        Self::new(color)
+    }
+}
+
 #[derive(Clone, Default, Debug, Deserialize, Serialize, PartialEq)]
-pub struct Palette(pub Vec<Color>);
+pub struct Palette(pub Vec<PaletteEntry>);
 
 impl Palette {
     pub fn iter(&self) -> impl Iterator<Item = &Color> {
+        self.0.iter().map(|entry| &entry.color)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &PaletteEntry> {
         self.0.iter()
     }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut PaletteEntry> {
+        self.0.get_mut(i)
+    }
+
+    fn contains(&self, color: &Color) -> bool {
+        self.find_similar(color, 0.).is_some()
+    }
+
     pub fn add(&mut self, color: Color) -> bool {
-        if !self
-            .0
-            .iter()
-            .any(|clr| clr.as_rgb_triplet_scaled() == color.as_rgb_triplet_scaled())
-        {
-            self.0.push(color);
+        self.add_entry(PaletteEntry::new(color))
+    }
+
+    pub fn add_entry(&mut self, entry: PaletteEntry) -> bool {
+        if !self.contains(&entry.color) {
+            self.0.push(entry);
             return true;
         }
         false
     }
 
-    pub fn insert(&mut self, i: usize, color: Color) {
-        if !self
-            .0
-            .iter()
-            .any(|clr| clr.as_rgb_triplet_scaled() == color.as_rgb_triplet_scaled())
-        {
-            self.0.insert(i, color);
+    pub fn insert(&mut self, i: usize, entry: PaletteEntry) {
+        if !self.contains(&entry.color) {
+            self.0.insert(i, entry);
         }
     }
 
-    pub fn remove_pos(&mut self, i: usize) -> Option<Color> {
+    pub fn remove_pos(&mut self, i: usize) -> Option<PaletteEntry> {
         if i < self.0.len() {
             Some(self.0.remove(i))
         } else {
@@ -74,22 +140,23 @@ impl Palette {
 
     pub fn as_gimp_palette(&self, name: &str) -> String {
         let mut gpl = format!("GIMP Palette\nName: {name}.gpl\nColumns: 1\n#\n");
-        for (i, color) in self.0.iter().enumerate() {
-            let color = color.color32();
-            let _ = writeln!(
-                gpl,
-                "{}\t{}\t{}\tcolor {}",
-                color.r(),
-                color.g(),
-                color.b(),
-                i
-            );
+        for (i, entry) in self.0.iter().enumerate() {
+            let color = entry.color.color32();
+            let _ = write!(gpl, "{}\t{}\t{}\t", color.r(), color.g(), color.b());
+            match entry.name() {
+                Some(name) => {
+                    let _ = writeln!(gpl, "{name}");
+                }
+                None => {
+                    let _ = writeln!(gpl, "color {i}");
+                }
+            }
         }
         gpl
     }
 
     pub fn as_hex_list(&self) -> String {
-        self.0.iter().fold(String::new(), |mut s, color| {
+        self.iter().fold(String::new(), |mut s, color| {
             s.push_str(&color.as_hex());
             s.push('\n');
             s
@@ -99,6 +166,12 @@ impl Palette {
 
 impl std::iter::FromIterator<Color> for Palette {
     fn from_iter<T: IntoIterator<Item = Color>>(iter: T) -> Self {
+        Self(iter.into_iter().map(PaletteEntry::from).collect())
+    }
+}
+
+impl std::iter::FromIterator<PaletteEntry> for Palette {
+    fn from_iter<T: IntoIterator<Item = PaletteEntry>>(iter: T) -> Self {
         Self(iter.into_iter().collect())
     }
 }
@@ -107,9 +180,16 @@ impl std::iter::FromIterator<Color> for Palette {
 pub enum PaletteFormat {
     Gimp,
     HexList,
+    DesignTokens(TokenNotation),
+    CssVariables(TokenNotation),
+    ScssVariables(TokenNotation),
+    LessVariables(TokenNotation),
+    Tailwind(TokenNotation),
+    Terminal(TerminalFormat),
     Custom(String, CustomPaletteFormat),
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for PaletteFormat {
     fn default() -> Self {
         Self::HexList
@@ -121,18 +201,68 @@ impl AsRef<str> for PaletteFormat {
         match self {
             PaletteFormat::Gimp => "GIMP (gpl)",
             PaletteFormat::HexList => "Hex list",
+            PaletteFormat::DesignTokens(_) => "Design tokens (json)",
+            PaletteFormat::CssVariables(_) => "CSS variables",
+            PaletteFormat::ScssVariables(_) => "SCSS variables",
+            PaletteFormat::LessVariables(_) => "LESS variables",
+            PaletteFormat::Tailwind(_) => "Tailwind colors",
+            PaletteFormat::Terminal(format) => format.as_ref(),
             PaletteFormat::Custom(name, _) => name,
         }
     }
 }
 
 impl PaletteFormat {
+    /// Returns all built-in formats, the ones supporting it using the provided notation.
+    pub fn builtin(notation: TokenNotation) -> impl Iterator<Item = PaletteFormat> {
+        [
+            PaletteFormat::Gimp,
+            PaletteFormat::HexList,
+            PaletteFormat::DesignTokens(notation),
+            PaletteFormat::CssVariables(notation),
+            PaletteFormat::ScssVariables(notation),
+            PaletteFormat::LessVariables(notation),
+            PaletteFormat::Tailwind(notation),
+        ]
+        .into_iter()
+        .chain(TerminalFormat::ALL.map(PaletteFormat::Terminal))
+    }
+
     pub fn extension(&self) -> &str {
         match self {
             PaletteFormat::Gimp => "gpl",
+            PaletteFormat::DesignTokens(_) => "json",
+            PaletteFormat::CssVariables(_) => "css",
+            PaletteFormat::ScssVariables(_) => "scss",
+            PaletteFormat::LessVariables(_) => "less",
+            PaletteFormat::Tailwind(_) => "js",
+            PaletteFormat::Terminal(format) => format.extension(),
             _ => "txt",
         }
     }
+
+    /// Returns the color notation used by this format if it's configurable.
+    pub fn notation(&self) -> Option<TokenNotation> {
+        match self {
+            PaletteFormat::DesignTokens(notation)
+            | PaletteFormat::CssVariables(notation)
+            | PaletteFormat::ScssVariables(notation)
+            | PaletteFormat::LessVariables(notation)
+            | PaletteFormat::Tailwind(notation) => Some(*notation),
+            _ => None,
+        }
+    }
+
+    pub fn notation_mut(&mut self) -> Option<&mut TokenNotation> {
+        match self {
+            PaletteFormat::DesignTokens(notation)
+            | PaletteFormat::CssVariables(notation)
+            | PaletteFormat::ScssVariables(notation)
+            | PaletteFormat::LessVariables(notation)
+            | PaletteFormat::Tailwind(notation) => Some(notation),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -167,4 +297,26 @@ Columns: 1
 
         assert_eq!(colors.as_gimp_palette("colors"), want);
     }
+
+    #[test]
+    fn export_named_color_palette() {
+        let mut colors = Palette::default();
+        colors.add_entry(PaletteEntry::named(
+            Rgb::new_scaled(255, 0, 0).into(),
+            "danger",
+        ));
+        colors.add(Rgb::new_scaled(0, 255, 0).into());
+        colors.add_entry(PaletteEntry::named(Rgb::new_scaled(0, 0, 255).into(), ""));
+
+        let want = r#"GIMP Palette
+Name: tokens.gpl
+Columns: 1
+#
+255	0	0	danger
+0	255	0	color 1
+0	0	255	color 2
+"#;
+
+        assert_eq!(colors.as_gimp_palette("tokens"), want);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::color::NamedPalette;
+use crate::color::{Color, NamedPalette, Palette};
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
@@ -7,7 +7,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct Palettes {
     pub palettes: Vec<NamedPalette>,
     current_idx: usize,
@@ -103,9 +103,17 @@ impl Palettes {
 
     /// Loads the saved colors from the specified file located at `path`. The file is expected to
     /// be a valid ron file.
+    ///
+    /// Files saved before palette entries could be named are also accepted.
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
         let data = fs::read_to_string(path).context("failed to read palette file")?;
-        ron::from_str(&data).context("failed to deserialize palette file")
+        match ron::from_str(&data) {
+            Ok(palettes) => Ok(palettes),
+            Err(e) => ron::from_str::<LegacyPalettes>(&data)
+                .map(Self::from)
+                .map_err(|_| e)
+                .context("failed to deserialize palette file"),
+        }
     }
 
     /// Saves this colors as ron file in the provided `path`.
@@ -131,6 +139,42 @@ impl Palettes {
     }
 }
 
+/// Layout of the palettes file from before palette entries had names and notes.
+#[derive(Deserialize)]
+struct LegacyPalettes {
+    palettes: Vec<LegacyNamedPalette>,
+    current_idx: usize,
+}
+
+#[derive(Deserialize)]
+struct LegacyNamedPalette {
+    name: String,
+    palette: LegacyPalette,
+}
+
+#[derive(Deserialize)]
+struct LegacyPalette(Vec<Color>);
+
+impl From<LegacyPalettes> for Palettes {
+    fn from(legacy: LegacyPalettes) -> Self {
+        let palettes: Vec<_> = legacy
+            .palettes
+            .into_iter()
+            .map(|p| NamedPalette {
+                name: p.name,
+                palette: Palette::from_iter(p.palette.0),
+            })
+            .collect();
+        if palettes.is_empty() {
+            return Self::default();
+        }
+        Self {
+            current_idx: legacy.current_idx.min(palettes.len() - 1),
+            palettes,
+        }
+    }
+}
+
 impl std::ops::Index<usize> for Palettes {
     type Output = NamedPalette;
 
@@ -142,7 +186,7 @@ impl std::ops::Index<usize> for Palettes {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::color::{Color, Palette, Rgb};
+    use crate::color::{PaletteEntry, Rgb};
     const C1: crate::color::Color = Color::Rgb(Rgb::new_unchecked(0., 0., 0.));
     const C2: crate::color::Color = Color::Rgb(Rgb::new_unchecked(0., 1., 0.));
     const C3: crate::color::Color = Color::Rgb(Rgb::new_unchecked(1., 0., 1.));
@@ -177,6 +221,30 @@ mod tests {
         palettes.add(p4);
     }
 
+    #[test]
+    fn loads_legacy_palettes() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join(Palettes::FILE_NAME);
+        let legacy = "(palettes:[(name:\"old\",palette:([Rgb((r:0.0,g:1.0,b:0.0)),\
+                      Color32(((255,0,0,255)))]))],current_idx:0)";
+        fs::write(&path, legacy).unwrap();
+
+        let palettes = Palettes::load(&path).unwrap();
+        assert_eq!(palettes.current().name, "old");
+        let entries: Vec<_> = palettes.current().palette.entries().cloned().collect();
+        assert_eq!(entries[0], PaletteEntry::new(C2));
+        assert_eq!(entries[1].name, None);
+
+        let mut palettes = palettes;
+        palettes.current_mut().palette.get_mut(0).unwrap().name = Some("primary".into());
+        palettes.save(&path).unwrap();
+        let reloaded = Palettes::load(&path).unwrap();
+        assert_eq!(
+            reloaded.current().palette.entries().next().unwrap().name(),
+            Some("primary")
+        );
+    }
+
     #[test]
     fn addition() {
         let (p1, p2, p3, p4) = test_palettes();
@@ -0,0 +1,113 @@
+use crate::color::Color;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, fs, path::Path};
+
+pub const DEFAULT_PICK_HISTORY_LIMIT: usize = 50;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+pub struct PickedColor {
+    pub color: Color,
+    /// Seconds since the unix epoch when the color was picked
+    pub timestamp: u64,
+}
+
+/// Colors picked from the screen or entered by hand, the most recent first.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct PickHistory {
+    colors: VecDeque<PickedColor>,
+}
+
+impl PickHistory {
+    pub const FILE_NAME: &'static str = "pick_history.ron";
+
+    /// Adds a color to the front of the history dropping the oldest colors above `limit`.
+    /// Picking the same color again only refreshes its timestamp.
+    pub fn push(&mut self, color: Color, timestamp: u64, limit: usize) {
+        if let Some(last) = self.colors.front_mut()
+            && last.color.color32() == color.color32()
+        {
+            last.timestamp = timestamp;
+            return;
+        }
+        self.colors.push_front(PickedColor { color, timestamp });
+        self.colors.truncate(limit);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PickedColor> {
+        self.colors.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    pub fn remove(&mut self, i: usize) -> Option<PickedColor> {
+        self.colors.remove(i)
+    }
+
+    pub fn clear(&mut self) {
+        self.colors.clear();
+    }
+
+    /// Loads the history from the ron file located at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let data = fs::read_to_string(path).context("failed to read pick history file")?;
+        ron::from_str(&data).context("failed to deserialize pick history file")
+    }
+
+    /// Saves the history as ron file in the provided `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut data = String::with_capacity(128);
+        ron::ser::to_writer(&mut data, &self).context("failed to serialize pick history")?;
+        fs::write(path, &data).context("failed to write pick history to file")
+    }
+}
+
+/// Formats a duration in seconds as a short human readable string like `5 min ago`.
+pub fn format_elapsed(secs: u64) -> String {
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{} min ago", secs / 60),
+        3600..86400 => format!("{} h ago", secs / 3600),
+        _ => format!("{} days ago", secs / 86400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Rgb;
+
+    fn color(v: u8) -> Color {
+        Rgb::new_scaled(0, v, 0).into()
+    }
+
+    #[test]
+    fn bounded_history() {
+        let mut history = PickHistory::default();
+        history.push(color(1), 1, 3);
+        history.push(color(1), 2, 3);
+        assert_eq!(history.iter().count(), 1);
+        assert_eq!(history.iter().next().unwrap().timestamp, 2);
+
+        for i in 2..6 {
+            history.push(color(i), i as u64 + 1, 3);
+        }
+        let colors: Vec<_> = history.iter().map(|c| c.color).collect();
+        assert_eq!(colors, [color(5), color(4), color(3)]);
+    }
+
+    #[test]
+    fn saves_and_loads() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join(PickHistory::FILE_NAME);
+        let mut history = PickHistory::default();
+        history.push(color(10), 100, 10);
+        history.push(color(20), 200, 10);
+        history.save(&path).unwrap();
+
+        assert_eq!(PickHistory::load(&path).unwrap(), history);
+    }
+}
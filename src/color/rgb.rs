@@ -8,6 +8,10 @@ use egui::{
 };
 use serde::{Deserialize, Serialize};
 
+/// Constants of the BT.709 transfer function in the precision of BT.2020.
+const REC709_ALPHA: f32 = 1.0992968;
+const REC709_BETA: f32 = 0.01805397;
+
 #[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
 pub struct Rgb {
     r: f32,
@@ -170,6 +174,36 @@ impl Rgb {
         self.b = inverse_compand(self.b);
         self
     }
+
+    /// Applies the ITU-R BT.709 and BT.2020 transfer function.
+    pub fn rec709_compand(mut self) -> Rgb {
+        fn compand(num: f32) -> f32 {
+            if num < REC709_BETA {
+                num * 4.5
+            } else {
+                REC709_ALPHA * num.powf(0.45) - (REC709_ALPHA - 1.)
+            }
+        }
+
+        self.r = compand(self.r);
+        self.g = compand(self.g);
+        self.b = compand(self.b);
+        self
+    }
+
+    pub fn inverse_rec709_compand(mut self) -> Rgb {
+        fn inverse_compand(num: f32) -> f32 {
+            if num < REC709_BETA * 4.5 {
+                num / 4.5
+            } else {
+                ((num + (REC709_ALPHA - 1.)) / REC709_ALPHA).powf(1. / 0.45)
+            }
+        }
+        self.r = inverse_compand(self.r);
+        self.g = inverse_compand(self.g);
+        self.b = inverse_compand(self.b);
+        self
+    }
 }
 
 //####################################################################################################
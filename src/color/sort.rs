@@ -0,0 +1,203 @@
+use crate::color::{
+    Lab, LchAB, Palette, PaletteEntry, RgbWorkingSpace,
+    difference::{delta_e76, delta_e2000, reference_lab},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Colors with a CIE LCh chroma below this value are treated as grays when sorting by hue.
+const ACHROMATIC_CHROMA: f32 = 5.;
+
+pub const DEFAULT_GROUP_THRESHOLD: f32 = 10.;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PaletteSort {
+    Hue,
+    LabLightness,
+    OklabLightness,
+    Chroma,
+    Luminance,
+    Visual,
+}
+
+impl PaletteSort {
+    pub const ALL: [PaletteSort; 6] = [
+        PaletteSort::Hue,
+        PaletteSort::LabLightness,
+        PaletteSort::OklabLightness,
+        PaletteSort::Chroma,
+        PaletteSort::Luminance,
+        PaletteSort::Visual,
+    ];
+}
+
+impl AsRef<str> for PaletteSort {
+    fn as_ref(&self) -> &str {
+        match self {
+            PaletteSort::Hue => "Hue",
+            PaletteSort::LabLightness => "Lightness (L*)",
+            PaletteSort::OklabLightness => "Lightness (OKLab L)",
+            PaletteSort::Chroma => "Chroma",
+            PaletteSort::Luminance => "Luminance",
+            PaletteSort::Visual => "Visual (nearest neighbour)",
+        }
+    }
+}
+
+impl Palette {
+    /// Reorders the colors of this palette according to `sort`. Names and notes move along with
+    /// their colors.
+    pub fn sort_by(&mut self, sort: PaletteSort) {
+        match sort {
+            PaletteSort::Hue => self.sort_by_key(|entry| {
+                let lch = LchAB::from(reference_lab(&entry.color));
+                if lch.c() < ACHROMATIC_CHROMA {
+                    (1, lch.l())
+                } else {
+                    (0, lch.h())
+                }
+            }),
+            PaletteSort::LabLightness => {
+                self.sort_by_key(|entry| (0, reference_lab(&entry.color).l()))
+            }
+            PaletteSort::OklabLightness => self.sort_by_key(|entry| (0, entry.color.oklab().l())),
+            PaletteSort::Chroma => {
+                self.sort_by_key(|entry| (0, LchAB::from(reference_lab(&entry.color)).c()))
+            }
+            PaletteSort::Luminance => {
+                self.sort_by_key(|entry| (0, entry.color.xyz(RgbWorkingSpace::SRGB).y()))
+            }
+            PaletteSort::Visual => self.sort_nearest_neighbour(),
+        }
+    }
+
+    fn sort_by_key(&mut self, key: impl Fn(&PaletteEntry) -> (u8, f32)) {
+        let mut keyed: Vec<_> = self.0.drain(..).map(|entry| (key(&entry), entry)).collect();
+        keyed.sort_by(|((g1, k1), _), ((g2, k2), _)| g1.cmp(g2).then(k1.total_cmp(k2)));
+        self.0 = keyed.into_iter().map(|(_, entry)| entry).collect();
+    }
+
+    /// Starts at the darkest color and repeatedly appends the closest remaining one in Lab space
+    /// so that neighbouring colors look alike.
+    fn sort_nearest_neighbour(&mut self) {
+        let mut remaining: Vec<(Lab, PaletteEntry)> = self
+            .0
+            .drain(..)
+            .map(|entry| (reference_lab(&entry.color), entry))
+            .collect();
+        let Some(start) = remaining
+            .iter()
+            .enumerate()
+            .min_by(|(_, (a, _)), (_, (b, _))| a.l().total_cmp(&b.l()))
+            .map(|(i, _)| i)
+        else {
+            return;
+        };
+        let (mut last, entry) = remaining.remove(start);
+        self.0.push(entry);
+        while !remaining.is_empty() {
+            let next = remaining
+                .iter()
+                .enumerate()
+                .min_by(|(_, (a, _)), (_, (b, _))| {
+                    delta_e76(&last, a).total_cmp(&delta_e76(&last, b))
+                })
+                .map(|(i, _)| i)
+                .unwrap_or_default();
+            let (lab, entry) = remaining.remove(next);
+            last = lab;
+            self.0.push(entry);
+        }
+    }
+
+    /// Moves colors that differ by less than `threshold` (CIEDE2000) from the first color of a
+    /// group next to it. Groups keep the order in which they first appear in the palette.
+    ///
+    /// Returns the number of groups.
+    pub fn group_similar(&mut self, threshold: f32) -> usize {
+        let mut groups: Vec<(Lab, Vec<PaletteEntry>)> = Vec::new();
+        for entry in self.0.drain(..) {
+            let lab = reference_lab(&entry.color);
+            match groups
+                .iter_mut()
+                .find(|(seed, _)| delta_e2000(seed, &lab) < threshold)
+            {
+                Some((_, group)) => group.push(entry),
+                None => groups.push((lab, vec![entry])),
+            }
+        }
+        let count = groups.len();
+        self.0 = groups.into_iter().flat_map(|(_, group)| group).collect();
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{Color, Rgb};
+
+    fn palette(colors: &[(u8, u8, u8)]) -> Palette {
+        colors
+            .iter()
+            .map(|&(r, g, b)| Color::from(Rgb::new_scaled(r, g, b)))
+            .collect()
+    }
+
+    fn hex(palette: &Palette) -> Vec<String> {
+        palette.iter().map(|c| c.as_hex()).collect()
+    }
+
+    #[test]
+    fn sorts_by_lightness() {
+        let mut p = palette(&[(255, 255, 255), (0, 0, 0), (128, 128, 128)]);
+        p.sort_by(PaletteSort::LabLightness);
+        assert_eq!(hex(&p), ["#000000", "#808080", "#ffffff"]);
+        p.sort_by(PaletteSort::Luminance);
+        assert_eq!(hex(&p), ["#000000", "#808080", "#ffffff"]);
+    }
+
+    #[test]
+    fn sorts_by_hue_with_grays_last() {
+        let mut p = palette(&[(128, 128, 128), (0, 0, 255), (255, 0, 0), (0, 255, 0)]);
+        p.sort_by(PaletteSort::Hue);
+        assert_eq!(hex(&p), ["#ff0000", "#00ff00", "#0000ff", "#808080"]);
+    }
+
+    #[test]
+    fn keeps_entry_names() {
+        let mut p = Palette::default();
+        p.add_entry(PaletteEntry::named(
+            Rgb::new_scaled(255, 255, 255).into(),
+            "light",
+        ));
+        p.add_entry(PaletteEntry::named(Rgb::new_scaled(0, 0, 0).into(), "dark"));
+        p.sort_by(PaletteSort::OklabLightness);
+        let names: Vec<_> = p.entries().filter_map(|e| e.name()).collect();
+        assert_eq!(names, ["dark", "light"]);
+    }
+
+    #[test]
+    fn visual_ordering() {
+        let mut p = palette(&[
+            (250, 0, 0),
+            (0, 0, 250),
+            (10, 10, 10),
+            (255, 10, 10),
+            (0, 0, 240),
+        ]);
+        p.sort_by(PaletteSort::Visual);
+        assert_eq!(
+            hex(&p),
+            ["#0a0a0a", "#fa0000", "#ff0a0a", "#0000f0", "#0000fa"]
+        );
+    }
+
+    #[test]
+    fn groups_similar_colors() {
+        let mut p = palette(&[(250, 0, 0), (0, 0, 250), (255, 5, 5), (0, 0, 245)]);
+        assert_eq!(p.group_similar(DEFAULT_GROUP_THRESHOLD), 2);
+        assert_eq!(hex(&p), ["#fa0000", "#ff0505", "#0000fa", "#0000f5"]);
+        assert_eq!(p.group_similar(0.), 4);
+    }
+}
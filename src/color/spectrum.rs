@@ -0,0 +1,588 @@
+use crate::color::{
+    ChromaticAdaptationMethod, Color, GamutMapping, Illuminant, RgbWorkingSpace, WhitePoint, Xyz,
+    brightest_rgb,
+    cct::planck_radiance,
+    observer::{CMF_END, CMF_START, CMF_STEP, Observer},
+    xyY,
+};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+/// Wavelengths in nanometers accepted in spectral data.
+const WAVELENGTH_RANGE: RangeInclusive<f32> = 300.0..=830.;
+/// Files with spectra with values above this are assumed to be given in percent.
+const PERCENT_THRESHOLD: f32 = 1.5;
+/// Distance in nanometers between the wavelengths spectra are integrated at, fine enough to not
+/// miss narrow peaks between the rows of the color matching function tables.
+const INTEGRATION_STEP: f32 = 1.;
+/// Color temperature in kelvin of CIE illuminant A.
+const ILLUMINANT_A_CCT: f64 = 2856.;
+
+/// Components S₀, S₁, S₂ of the CIE daylight spectral power distributions from 380 to 780 nm.
+#[rustfmt::skip]
+const DAYLIGHT_COMPONENTS: [[f32; 3]; 41] = [
+    [ 63.4,  38.5,  3.0], // 380
+    [ 65.8,  35.0,  1.2], // 390
+    [ 94.8,  43.4, -1.1], // 400
+    [104.8,  46.3, -0.5], // 410
+    [105.9,  43.9, -0.7], // 420
+    [ 96.8,  37.1, -1.2], // 430
+    [113.9,  36.7, -2.6], // 440
+    [125.6,  35.9, -2.9], // 450
+    [125.5,  32.6, -2.8], // 460
+    [121.3,  27.9, -2.6], // 470
+    [121.3,  24.3, -2.6], // 480
+    [113.5,  20.1, -1.8], // 490
+    [113.1,  16.2, -1.5], // 500
+    [110.8,  13.2, -1.3], // 510
+    [106.5,   8.6, -1.2], // 520
+    [108.8,   6.1, -1.0], // 530
+    [105.3,   4.2, -0.5], // 540
+    [104.4,   1.9, -0.3], // 550
+    [100.0,   0.0,  0.0], // 560
+    [ 96.0,  -1.6,  0.2], // 570
+    [ 95.1,  -3.5,  0.5], // 580
+    [ 89.1,  -3.5,  2.1], // 590
+    [ 90.5,  -5.8,  3.2], // 600
+    [ 90.3,  -7.2,  4.1], // 610
+    [ 88.4,  -8.6,  4.7], // 620
+    [ 84.0,  -9.5,  5.1], // 630
+    [ 85.1, -10.9,  6.7], // 640
+    [ 81.9, -10.7,  7.3], // 650
+    [ 82.6, -12.0,  8.6], // 660
+    [ 84.9, -14.0,  9.8], // 670
+    [ 81.3, -13.6, 10.2], // 680
+    [ 71.9, -12.0,  8.3], // 690
+    [ 74.3, -13.3,  9.6], // 700
+    [ 76.4, -12.9,  8.5], // 710
+    [ 63.3, -10.6,  7.0], // 720
+    [ 71.7, -11.6,  7.6], // 730
+    [ 77.0, -12.2,  8.0], // 740
+    [ 65.2, -10.2,  6.7], // 750
+    [ 47.7,  -7.8,  5.2], // 760
+    [ 68.6, -11.2,  7.4], // 770
+    [ 65.0, -10.4,  6.8], // 780
+];
+
+/// What the values of a spectrum describe.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum SpectrumKind {
+    /// Reflectance or transmittance of a surface lit by an illuminant
+    #[default]
+    Reflectance,
+    /// Spectral power of a light source
+    Emission,
+}
+
+impl SpectrumKind {
+    pub const ALL: [SpectrumKind; 2] = [SpectrumKind::Reflectance, SpectrumKind::Emission];
+}
+
+impl AsRef<str> for SpectrumKind {
+    fn as_ref(&self) -> &str {
+        match self {
+            SpectrumKind::Reflectance => "reflectance",
+            SpectrumKind::Emission => "emission",
+        }
+    }
+}
+
+/// How spectra are turned into colors.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct SpectralConversion {
+    pub kind: SpectrumKind,
+    pub observer: Observer,
+    /// Light source of reflectance spectra
+    pub illuminant: Illuminant,
+}
+
+impl SpectralConversion {
+    /// Returns the tristimulus values of `spectrum` with the white they are relative to.
+    ///
+    /// Reflectance is integrated under the spectral power distribution of the illuminant
+    /// normalized so that a perfect reflector has `Y = 1`. Illuminants without a built-in
+    /// distribution are approximated by integrating under illuminant E and adapting the result
+    /// to their white point. Emission spectra are normalized to `Y = 1` relative to illuminant E.
+    pub fn xyz(&self, spectrum: &Spectrum) -> (Xyz, Illuminant) {
+        let spd = match self.kind {
+            SpectrumKind::Reflectance => spectral_power(self.illuminant),
+            SpectrumKind::Emission => None,
+        };
+        let (mut x, mut y, mut z) = (0., 0., 0.);
+        let (mut white_x, mut white_y, mut white_z) = (0., 0., 0.);
+        for (i, wavelength) in integration_wavelengths().enumerate() {
+            let cmf = self.observer.cmf(wavelength);
+            let power = spd.as_ref().map_or(1., |spd| spd[i]);
+            let value = spectrum.value_at(wavelength);
+            let sample = match self.kind {
+                SpectrumKind::Reflectance => power * value,
+                SpectrumKind::Emission => value,
+            };
+            x += sample * cmf.x();
+            y += sample * cmf.y();
+            z += sample * cmf.z();
+            white_x += power * cmf.x();
+            white_y += power * cmf.y();
+            white_z += power * cmf.z();
+        }
+        let scale = match self.kind {
+            SpectrumKind::Reflectance => white_y,
+            SpectrumKind::Emission => y,
+        };
+        if scale <= 0. {
+            return (Xyz::new(0., 0., 0.), Illuminant::E);
+        }
+        let xyz = Xyz::new(x / scale, y / scale, z / scale);
+        let white = Illuminant::Custom(white_point(Xyz::new(white_x, white_y, white_z)));
+        match (self.kind, spd) {
+            (SpectrumKind::Reflectance, None) => (
+                xyz.chromatic_adaptation_transform(
+                    ChromaticAdaptationMethod::Bradford,
+                    white,
+                    self.illuminant,
+                ),
+                self.illuminant,
+            ),
+            _ => (xyz, white),
+        }
+    }
+
+    /// Returns the color of `spectrum` in `ws`.
+    ///
+    /// Reflectance is adapted from the white of the illuminant to the white of the working space
+    /// with `method` and brought into its gamut with `mapping`. Light sources are shown with
+    /// their chromaticity at the highest brightness of the working space.
+    pub fn color(
+        &self,
+        spectrum: &Spectrum,
+        ws: RgbWorkingSpace,
+        method: ChromaticAdaptationMethod,
+        mapping: GamutMapping,
+    ) -> Color {
+        let (xyz, white) = self.xyz(spectrum);
+        match self.kind {
+            SpectrumKind::Reflectance => {
+                let adapted =
+                    xyz.chromatic_adaptation_transform(method, white, ws.reference_illuminant());
+                Color::Rgb(mapping.map(adapted, ws))
+            }
+            SpectrumKind::Emission => {
+                let xyy = xyY::from(xyz);
+                Color::Rgb(brightest_rgb(xyy.x(), xyy.y(), ws))
+            }
+        }
+    }
+}
+
+/// A named spectral measurement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spectrum {
+    pub name: String,
+    /// Wavelengths in nanometers and values sorted by wavelength
+    samples: Vec<(f32, f32)>,
+}
+
+impl Spectrum {
+    /// Creates a spectrum from wavelengths in nanometers and values.
+    pub fn new(name: impl Into<String>, mut samples: Vec<(f32, f32)>) -> Self {
+        samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self {
+            name: name.into(),
+            samples,
+        }
+    }
+
+    fn max_value(&self) -> f32 {
+        self.samples
+            .iter()
+            .map(|(_, value)| *value)
+            .fold(f32::MIN, f32::max)
+    }
+
+    /// Returns the value at `wavelength` linearly interpolated between samples. Outside of the
+    /// measured range the nearest sample is used as recommended by CIE 15.
+    fn value_at(&self, wavelength: f32) -> f32 {
+        let (Some(first), Some(last)) = (self.samples.first(), self.samples.last()) else {
+            return 0.;
+        };
+        if wavelength <= first.0 {
+            return first.1;
+        }
+        if wavelength >= last.0 {
+            return last.1;
+        }
+        let i = self.samples.partition_point(|(w, _)| *w <= wavelength);
+        let (w0, v0) = self.samples[i - 1];
+        let (w1, v1) = self.samples[i];
+        if w1 == w0 {
+            return v0;
+        }
+        v0 + (v1 - v0) * (wavelength - w0) / (w1 - w0)
+    }
+}
+
+fn integration_wavelengths() -> impl Iterator<Item = f32> {
+    let steps = ((CMF_END - CMF_START) / INTEGRATION_STEP).round() as usize;
+    (0..=steps).map(|i| CMF_START + i as f32 * INTEGRATION_STEP)
+}
+
+/// Returns the daylight components at `wavelength` linearly interpolated between table rows as
+/// recommended by CIE 15.
+fn daylight_components(wavelength: f32) -> [f32; 3] {
+    let position = (wavelength - CMF_START) / CMF_STEP;
+    let i = (position.floor() as usize).min(DAYLIGHT_COMPONENTS.len() - 2);
+    let t = position - i as f32;
+    let (row, next) = (DAYLIGHT_COMPONENTS[i], DAYLIGHT_COMPONENTS[i + 1]);
+    [0, 1, 2].map(|c| row[c] + (next[c] - row[c]) * t)
+}
+
+fn white_point(xyz: Xyz) -> WhitePoint {
+    let xyy = xyY::from(xyz);
+    WhitePoint::new(xyy.x(), xyy.y())
+}
+
+/// Returns the relative spectral power distribution of `illuminant` at the integration
+/// wavelengths, `None` for illuminants without a built-in distribution.
+fn spectral_power(illuminant: Illuminant) -> Option<Vec<f32>> {
+    let daylight_cct = match illuminant {
+        Illuminant::A => {
+            let at_560 = planck_radiance(560., ILLUMINANT_A_CCT);
+            return Some(
+                integration_wavelengths()
+                    .map(|wavelength| {
+                        (planck_radiance(wavelength as f64, ILLUMINANT_A_CCT) / at_560 * 100.)
+                            as f32
+                    })
+                    .collect(),
+            );
+        }
+        Illuminant::E => return Some(integration_wavelengths().map(|_| 100.).collect()),
+        Illuminant::D50 => 5003.,
+        Illuminant::D55 => 5503.,
+        Illuminant::D65 => 6504.,
+        Illuminant::D75 => 7504.,
+        _ => return None,
+    };
+    let WhitePoint { x, y } = WhitePoint::from_cct(daylight_cct);
+    let m = 0.0241 + 0.2562 * x - 0.7341 * y;
+    let m1 = (-1.3515 - 1.7703 * x + 5.9114 * y) / m;
+    let m2 = (0.03 - 31.4424 * x + 30.0717 * y) / m;
+    Some(
+        integration_wavelengths()
+            .map(|wavelength| {
+                let [s0, s1, s2] = daylight_components(wavelength);
+                s0 + m1 * s1 + m2 * s2
+            })
+            .collect(),
+    )
+}
+
+/// Parses spectral data in CGATS text as written by measurement software, or CSV with either
+/// one spectrum per row under a header of wavelengths, or one spectrum per column next to a
+/// column of wavelengths.
+///
+/// Values of files in percent are scaled to the 0-1 range. The scale is decided for the whole
+/// file so that dark samples of a file in percent aren't mistaken for fractions.
+pub fn parse_spectra(text: &str) -> Result<Vec<Spectrum>> {
+    let mut spectra = if text.contains("BEGIN_DATA_FORMAT") {
+        parse_cgats(text)?
+    } else {
+        parse_csv(text)?
+    };
+    if spectra.is_empty() {
+        bail!("no spectra found");
+    }
+    if spectra
+        .iter()
+        .any(|spectrum| spectrum.max_value() > PERCENT_THRESHOLD)
+    {
+        for spectrum in &mut spectra {
+            spectrum
+                .samples
+                .iter_mut()
+                .for_each(|(_, value)| *value /= 100.);
+        }
+    }
+    Ok(spectra)
+}
+
+/// Returns the wavelength of a spectral field name like `SPECTRAL_NM380`, `SPECTRAL_380` or
+/// `nm380`, or of a plain number.
+fn field_wavelength(field: &str) -> Option<f32> {
+    let upper = field.to_ascii_uppercase();
+    let digits = upper.trim_start_matches(|c: char| !c.is_ascii_digit());
+    let prefix = &upper[..upper.len() - digits.len()];
+    if !(prefix.is_empty() || prefix.starts_with("SPECTRAL") || prefix.starts_with("NM")) {
+        return None;
+    }
+    digits
+        .parse::<f32>()
+        .ok()
+        .filter(|wavelength| WAVELENGTH_RANGE.contains(wavelength))
+}
+
+fn parse_value(value: &str, line: usize) -> Result<f32> {
+    value
+        .parse()
+        .with_context(|| format!("invalid value `{value}` on line {line}"))
+}
+
+/// Splits a CGATS line into whitespace separated values keeping quoted strings together.
+fn cgats_values(line: &str) -> Vec<&str> {
+    let mut values = vec![];
+    let mut rest = line.trim();
+    while !rest.is_empty() {
+        let (value, remaining) = match rest.strip_prefix('"') {
+            Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+            None => rest.split_once(char::is_whitespace).unwrap_or((rest, "")),
+        };
+        values.push(value);
+        rest = remaining.trim_start();
+    }
+    values
+}
+
+fn parse_cgats(text: &str) -> Result<Vec<Spectrum>> {
+    let mut fields = vec![];
+    let mut spectra = vec![];
+    let mut section = None;
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        match line {
+            "BEGIN_DATA_FORMAT" | "BEGIN_DATA" => section = Some(line),
+            "END_DATA_FORMAT" | "END_DATA" => section = None,
+            _ if line.is_empty() || line.starts_with('#') => {}
+            _ => match section {
+                Some("BEGIN_DATA_FORMAT") => {
+                    fields.extend(cgats_values(line).into_iter().map(str::to_string))
+                }
+                Some("BEGIN_DATA") => {
+                    let values = cgats_values(line);
+                    if values.len() != fields.len() {
+                        bail!(
+                            "expected {} values on line {}, found {}",
+                            fields.len(),
+                            i + 1,
+                            values.len()
+                        );
+                    }
+                    let mut name = None;
+                    let mut samples = vec![];
+                    for (field, value) in fields.iter().zip(values) {
+                        match field.as_str() {
+                            "SAMPLE_NAME" => name = Some(value.to_string()),
+                            "SAMPLE_ID" if name.is_none() => name = Some(value.to_string()),
+                            _ => {
+                                if let Some(wavelength) = field_wavelength(field) {
+                                    samples.push((wavelength, parse_value(value, i + 1)?));
+                                }
+                            }
+                        }
+                    }
+                    if samples.is_empty() {
+                        bail!("no spectral fields in the data format");
+                    }
+                    let name = name.unwrap_or_else(|| format!("sample {}", spectra.len() + 1));
+                    spectra.push(Spectrum::new(name, samples));
+                }
+                _ => {}
+            },
+        }
+    }
+    Ok(spectra)
+}
+
+fn csv_values(line: &str) -> Vec<&str> {
+    let separator = [',', ';', '\t']
+        .into_iter()
+        .find(|separator| line.contains(*separator))
+        .unwrap_or(',');
+    line.split(separator)
+        .map(|value| value.trim().trim_matches('"'))
+        .collect()
+}
+
+fn parse_csv(text: &str) -> Result<Vec<Spectrum>> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'));
+    let Some((_, header)) = lines.next() else {
+        return Ok(vec![]);
+    };
+    let header = csv_values(header);
+    if header.len() < 2 {
+        bail!("expected at least two columns");
+    }
+
+    let wavelengths: Option<Vec<f32>> = header[1..].iter().map(|f| field_wavelength(f)).collect();
+    if let Some(wavelengths) = wavelengths {
+        // One spectrum per row
+        lines
+            .map(|(i, line)| {
+                let values = csv_values(line);
+                if values.len() != header.len() {
+                    bail!(
+                        "expected {} values on line {}, found {}",
+                        header.len(),
+                        i + 1,
+                        values.len()
+                    );
+                }
+                let samples = wavelengths
+                    .iter()
+                    .zip(&values[1..])
+                    .map(|(wavelength, value)| Ok((*wavelength, parse_value(value, i + 1)?)))
+                    .collect::<Result<_>>()?;
+                Ok(Spectrum::new(values[0], samples))
+            })
+            .collect()
+    } else {
+        // One spectrum per column
+        let mut samples = vec![vec![]; header.len() - 1];
+        for (i, line) in lines {
+            let values = csv_values(line);
+            let wavelength = field_wavelength(values[0])
+                .with_context(|| format!("invalid wavelength `{}` on line {}", values[0], i + 1))?;
+            for (column, value) in samples.iter_mut().zip(&values[1..]) {
+                if !value.is_empty() {
+                    column.push((wavelength, parse_value(value, i + 1)?));
+                }
+            }
+        }
+        Ok(header[1..]
+            .iter()
+            .zip(samples)
+            .map(|(name, samples)| Spectrum::new(*name, samples))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat(value: f32) -> Spectrum {
+        Spectrum::new(
+            "flat",
+            (380..=780).step_by(5).map(|w| (w as f32, value)).collect(),
+        )
+    }
+
+    #[test]
+    fn integrates_reflectance() {
+        let conversion = SpectralConversion {
+            illuminant: Illuminant::D65,
+            ..Default::default()
+        };
+        let (white, reference) = conversion.xyz(&flat(1.));
+        assert!((white.x() - 0.9502).abs() < 1e-3, "{white:?}");
+        assert!((white.y() - 1.).abs() < 1e-4, "{white:?}");
+        assert!((white.z() - 1.0870).abs() < 1e-3, "{white:?}");
+        let reference = xyY::from(reference.xyz());
+        assert!((reference.x() - 0.3127).abs() < 3e-4, "{reference:?}");
+        assert!((conversion.xyz(&flat(0.5)).0.y() - 0.5).abs() < 1e-4);
+
+        let ten_degree = SpectralConversion {
+            observer: Observer::Cie1964,
+            ..conversion
+        };
+        let (white, _) = ten_degree.xyz(&flat(1.));
+        assert!((white.x() - 0.9481).abs() < 1e-3, "{white:?}");
+        assert!((white.z() - 1.073).abs() < 1e-3, "{white:?}");
+
+        let a = SpectralConversion {
+            illuminant: Illuminant::A,
+            ..Default::default()
+        };
+        let (white, _) = a.xyz(&flat(1.));
+        assert!((white.x() - 1.0985).abs() < 1e-3, "{white:?}");
+        assert!((white.z() - 0.3558).abs() < 1e-3, "{white:?}");
+
+        let rgb = conversion
+            .color(
+                &flat(1.),
+                RgbWorkingSpace::SRGB,
+                ChromaticAdaptationMethod::Bradford,
+                GamutMapping::Clip,
+            )
+            .rgb();
+        assert!(
+            rgb.r() > 0.999 && rgb.g() > 0.999 && rgb.b() > 0.999,
+            "{rgb:?}"
+        );
+    }
+
+    #[test]
+    fn integrates_emission() {
+        let green = Spectrum::new("520 nm", vec![(519., 0.), (520., 1.), (521., 0.)]);
+        let conversion = SpectralConversion {
+            kind: SpectrumKind::Emission,
+            ..Default::default()
+        };
+        let xyy = xyY::from(conversion.xyz(&green).0);
+        assert!((xyy.x() - 0.0743).abs() < 1e-3, "{xyy:?}");
+        assert!((xyy.y() - 0.8338).abs() < 1e-3, "{xyy:?}");
+
+        // A line between the rows of the color matching function tables
+        let line = Spectrum::new("525 nm", vec![(524., 0.), (525., 1.), (526., 0.)]);
+        let xyz = conversion.xyz(&line).0;
+        assert!((xyz.y() - 1.).abs() < 1e-4, "{xyz:?}");
+        let xyy = xyY::from(xyz);
+        let locus = xyY::from(Observer::Cie1931.cmf(525.));
+        assert!((xyy.x() - locus.x()).abs() < 1e-3, "{xyy:?}");
+        assert!((xyy.y() - locus.y()).abs() < 1e-3, "{xyy:?}");
+    }
+
+    #[test]
+    fn parses_cgats() {
+        let text = r#"CGATS.17
+ORIGINATOR "test"
+NUMBER_OF_FIELDS 5
+BEGIN_DATA_FORMAT
+SAMPLE_ID SAMPLE_NAME SPECTRAL_NM380 SPECTRAL_NM390
+SPECTRAL_NM400
+END_DATA_FORMAT
+NUMBER_OF_SETS 2
+BEGIN_DATA
+1 "Light gray" 80 80 80
+2 Red 5 10 20
+3 Black 1 1 1.5
+END_DATA
+"#;
+        let spectra = parse_spectra(text).unwrap();
+        assert_eq!(spectra.len(), 3);
+        assert_eq!(spectra[0].name, "Light gray");
+        assert_eq!(
+            spectra[0].samples,
+            vec![(380., 0.8), (390., 0.8), (400., 0.8)]
+        );
+        assert_eq!(spectra[1].samples[2], (400., 0.2));
+        assert_eq!(spectra[2].samples[0], (380., 0.01));
+        assert!(
+            parse_spectra("BEGIN_DATA_FORMAT\nSAMPLE_ID\nEND_DATA_FORMAT\nBEGIN_DATA\n1\n")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parses_csv() {
+        let rows = "name,380,385,390\nwhite,1,1,1\n\"dark\",0.1,0.2,0.3\n";
+        let spectra = parse_spectra(rows).unwrap();
+        assert_eq!(spectra.len(), 2);
+        assert_eq!(spectra[1].name, "dark");
+        assert_eq!(spectra[1].samples[1], (385., 0.2));
+
+        let columns = "nm;white;dark\n380;1;0.1\n390;1;0.2\n";
+        let spectra = parse_spectra(columns).unwrap();
+        assert_eq!(spectra.len(), 2);
+        assert_eq!(spectra[1].samples, vec![(380., 0.1), (390., 0.2)]);
+        assert_eq!(spectra[1].value_at(385.), 0.15);
+        assert_eq!(spectra[1].value_at(700.), 0.2);
+
+        assert!(parse_spectra("name,380\nwhite,x\n").is_err());
+        assert!(parse_spectra("").is_err());
+    }
+}
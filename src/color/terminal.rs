@@ -0,0 +1,545 @@
+//! Exports of named palettes as terminal and editor color schemes.
+
+use crate::color::{
+    Color, NamedPalette,
+    tokens::{json_string, slug},
+};
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+/// A color slot of a terminal color scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalSlot {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    Background,
+    Foreground,
+    Cursor,
+}
+
+impl AsRef<str> for TerminalSlot {
+    fn as_ref(&self) -> &str {
+        match self {
+            TerminalSlot::Black => "black",
+            TerminalSlot::Red => "red",
+            TerminalSlot::Green => "green",
+            TerminalSlot::Yellow => "yellow",
+            TerminalSlot::Blue => "blue",
+            TerminalSlot::Magenta => "magenta",
+            TerminalSlot::Cyan => "cyan",
+            TerminalSlot::White => "white",
+            TerminalSlot::BrightBlack => "bright black",
+            TerminalSlot::BrightRed => "bright red",
+            TerminalSlot::BrightGreen => "bright green",
+            TerminalSlot::BrightYellow => "bright yellow",
+            TerminalSlot::BrightBlue => "bright blue",
+            TerminalSlot::BrightMagenta => "bright magenta",
+            TerminalSlot::BrightCyan => "bright cyan",
+            TerminalSlot::BrightWhite => "bright white",
+            TerminalSlot::Background => "background",
+            TerminalSlot::Foreground => "foreground",
+            TerminalSlot::Cursor => "cursor",
+        }
+    }
+}
+
+impl TerminalSlot {
+    pub const COUNT: usize = 19;
+    pub const ALL: [TerminalSlot; Self::COUNT] = [
+        TerminalSlot::Black,
+        TerminalSlot::Red,
+        TerminalSlot::Green,
+        TerminalSlot::Yellow,
+        TerminalSlot::Blue,
+        TerminalSlot::Magenta,
+        TerminalSlot::Cyan,
+        TerminalSlot::White,
+        TerminalSlot::BrightBlack,
+        TerminalSlot::BrightRed,
+        TerminalSlot::BrightGreen,
+        TerminalSlot::BrightYellow,
+        TerminalSlot::BrightBlue,
+        TerminalSlot::BrightMagenta,
+        TerminalSlot::BrightCyan,
+        TerminalSlot::BrightWhite,
+        TerminalSlot::Background,
+        TerminalSlot::Foreground,
+        TerminalSlot::Cursor,
+    ];
+
+    /// Returns the index of this slot in [`TerminalSlot::ALL`]. For ANSI colors this is the
+    /// color number.
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Returns entry names that are automatically mapped to this slot.
+    fn aliases(&self) -> Vec<String> {
+        let name = slug(self.as_ref());
+        let mut aliases = vec![name.clone(), name.replace('-', "")];
+        match self {
+            TerminalSlot::Background => aliases.push("bg".into()),
+            TerminalSlot::Foreground => aliases.push("fg".into()),
+            _ => aliases.push(format!("color{}", self.index())),
+        }
+        aliases
+    }
+}
+
+/// Mapping of terminal slots to indices of palette entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SlotMapping([Option<usize>; TerminalSlot::COUNT]);
+
+impl SlotMapping {
+    /// Maps entries to slots by their names like `red`, `bright-red`, `color9`, `bg` or
+    /// `cursor`. Remaining slots are filled by position, first 16 entries being the ANSI colors
+    /// followed by background, foreground and cursor. Entries matched by name are not used
+    /// again for other slots.
+    pub fn auto(palette: &NamedPalette) -> Self {
+        let names: Vec<_> = palette
+            .palette
+            .entries()
+            .map(|entry| entry.name().map(slug))
+            .collect();
+        let mut mapping = Self::default();
+        for slot in TerminalSlot::ALL {
+            let aliases = slot.aliases();
+            mapping.0[slot.index()] = names
+                .iter()
+                .position(|name| name.as_ref().is_some_and(|name| aliases.contains(name)));
+        }
+        let matched: Vec<_> = mapping.0.iter().flatten().copied().collect();
+        for slot in TerminalSlot::ALL {
+            let idx = slot.index();
+            if mapping.0[idx].is_none() && idx < names.len() && !matched.contains(&idx) {
+                mapping.0[idx] = Some(idx);
+            }
+        }
+        mapping
+    }
+
+    pub fn get(&self, slot: TerminalSlot) -> Option<usize> {
+        self.0[slot.index()]
+    }
+
+    pub fn get_mut(&mut self, slot: TerminalSlot) -> &mut Option<usize> {
+        &mut self.0[slot.index()]
+    }
+
+    fn resolve(&self, palette: &NamedPalette) -> Slots {
+        Slots(TerminalSlot::ALL.map(|slot| {
+            self.get(slot)
+                .and_then(|idx| palette.palette.0.get(idx).map(|e| e.color))
+        }))
+    }
+}
+
+struct Slots([Option<Color>; TerminalSlot::COUNT]);
+
+impl Slots {
+    fn hex(&self, slot: TerminalSlot) -> Option<String> {
+        self.0[slot.index()].map(|color| color.as_hex())
+    }
+
+    /// Returns hex values of either normal or bright ANSI colors if all 8 of them are set.
+    fn ansi_row(&self, bright: bool) -> Option<Vec<String>> {
+        let offset = if bright { 8 } else { 0 };
+        TerminalSlot::ALL[offset..offset + 8]
+            .iter()
+            .map(|slot| self.hex(*slot))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TerminalFormat {
+    Xresources,
+    Alacritty,
+    Kitty,
+    WezTerm,
+    Foot,
+    VsCode,
+}
+
+impl AsRef<str> for TerminalFormat {
+    fn as_ref(&self) -> &str {
+        match self {
+            TerminalFormat::Xresources => "Xresources",
+            TerminalFormat::Alacritty => "Alacritty (toml)",
+            TerminalFormat::Kitty => "Kitty (conf)",
+            TerminalFormat::WezTerm => "WezTerm (toml)",
+            TerminalFormat::Foot => "foot (ini)",
+            TerminalFormat::VsCode => "VS Code theme (json)",
+        }
+    }
+}
+
+impl TerminalFormat {
+    pub const ALL: [TerminalFormat; 6] = [
+        TerminalFormat::Xresources,
+        TerminalFormat::Alacritty,
+        TerminalFormat::Kitty,
+        TerminalFormat::WezTerm,
+        TerminalFormat::Foot,
+        TerminalFormat::VsCode,
+    ];
+
+    pub fn extension(&self) -> &str {
+        match self {
+            TerminalFormat::Xresources => "Xresources",
+            TerminalFormat::Alacritty | TerminalFormat::WezTerm => "toml",
+            TerminalFormat::Kitty => "conf",
+            TerminalFormat::Foot => "ini",
+            TerminalFormat::VsCode => "json",
+        }
+    }
+}
+
+const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+const VSCODE_ANSI_NAMES: [&str; 8] = [
+    "Black", "Red", "Green", "Yellow", "Blue", "Magenta", "Cyan", "White",
+];
+
+impl NamedPalette {
+    /// Exports this palette as a terminal or editor color scheme. Slots without a color are
+    /// left out.
+    pub fn as_terminal_theme(&self, format: TerminalFormat, mapping: &SlotMapping) -> String {
+        let slots = mapping.resolve(self);
+        match format {
+            TerminalFormat::Xresources => self.as_xresources(&slots),
+            TerminalFormat::Alacritty => as_alacritty(&slots),
+            TerminalFormat::Kitty => self.as_kitty(&slots),
+            TerminalFormat::WezTerm => self.as_wezterm(&slots),
+            TerminalFormat::Foot => as_foot(&slots),
+            TerminalFormat::VsCode => self.as_vscode_theme(&slots),
+        }
+    }
+
+    fn as_xresources(&self, slots: &Slots) -> String {
+        let mut s = format!("! {}\n", self.name);
+        for (slot, key) in [
+            (TerminalSlot::Foreground, "foreground"),
+            (TerminalSlot::Background, "background"),
+            (TerminalSlot::Cursor, "cursorColor"),
+        ] {
+            if let Some(hex) = slots.hex(slot) {
+                let _ = writeln!(s, "*.{key}: {hex}");
+            }
+        }
+        for slot in &TerminalSlot::ALL[..16] {
+            if let Some(hex) = slots.hex(*slot) {
+                let _ = writeln!(s, "*.color{}: {hex}", slot.index());
+            }
+        }
+        s
+    }
+
+    fn as_kitty(&self, slots: &Slots) -> String {
+        let mut s = format!("# {}\n", self.name);
+        for (slot, key) in [
+            (TerminalSlot::Foreground, "foreground"),
+            (TerminalSlot::Background, "background"),
+            (TerminalSlot::Cursor, "cursor"),
+        ] {
+            if let Some(hex) = slots.hex(slot) {
+                let _ = writeln!(s, "{key} {hex}");
+            }
+        }
+        for slot in &TerminalSlot::ALL[..16] {
+            if let Some(hex) = slots.hex(*slot) {
+                let _ = writeln!(s, "color{} {hex}", slot.index());
+            }
+        }
+        s
+    }
+
+    fn as_wezterm(&self, slots: &Slots) -> String {
+        let mut s = String::from("[colors]\n");
+        for (slot, key) in [
+            (TerminalSlot::Foreground, "foreground"),
+            (TerminalSlot::Background, "background"),
+            (TerminalSlot::Cursor, "cursor_bg"),
+            (TerminalSlot::Cursor, "cursor_border"),
+        ] {
+            if let Some(hex) = slots.hex(slot) {
+                let _ = writeln!(s, "{key} = \"{hex}\"");
+            }
+        }
+        for (bright, key) in [(false, "ansi"), (true, "brights")] {
+            if let Some(row) = slots.ansi_row(bright) {
+                let row: Vec<_> = row.iter().map(|hex| format!("\"{hex}\"")).collect();
+                let _ = writeln!(s, "{key} = [{}]", row.join(", "));
+            }
+        }
+        let _ = write!(s, "\n[metadata]\nname = {}\n", json_string(&self.name));
+        s
+    }
+
+    fn as_vscode_theme(&self, slots: &Slots) -> String {
+        let is_light =
+            slots.0[TerminalSlot::Background.index()].is_some_and(|color| color.oklab().l() > 0.6);
+        let mut colors = vec![];
+        for (slot, keys) in [
+            (
+                TerminalSlot::Background,
+                &["editor.background", "terminal.background"],
+            ),
+            (
+                TerminalSlot::Foreground,
+                &["editor.foreground", "terminal.foreground"],
+            ),
+            (
+                TerminalSlot::Cursor,
+                &["editorCursor.foreground", "terminalCursor.foreground"],
+            ),
+        ] {
+            if let Some(hex) = slots.hex(slot) {
+                for key in keys {
+                    colors.push((key.to_string(), hex.clone()));
+                }
+            }
+        }
+        for slot in &TerminalSlot::ALL[..16] {
+            if let Some(hex) = slots.hex(*slot) {
+                let bright = if slot.index() >= 8 { "Bright" } else { "" };
+                let name = VSCODE_ANSI_NAMES[slot.index() % 8];
+                colors.push((format!("terminal.ansi{bright}{name}"), hex));
+            }
+        }
+
+        let mut s = format!(
+            "{{\n  \"name\": {},\n  \"type\": \"{}\",\n  \"colors\": {{\n",
+            json_string(&self.name),
+            if is_light { "light" } else { "dark" }
+        );
+        let len = colors.len();
+        for (i, (key, hex)) in colors.into_iter().enumerate() {
+            let sep = if i + 1 < len { "," } else { "" };
+            let _ = writeln!(s, "    \"{key}\": \"{hex}\"{sep}");
+        }
+        s.push_str("  },\n  \"tokenColors\": []\n}\n");
+        s
+    }
+}
+
+fn as_alacritty(slots: &Slots) -> String {
+    let mut sections = vec![];
+    let mut primary = String::new();
+    for slot in [TerminalSlot::Background, TerminalSlot::Foreground] {
+        if let Some(hex) = slots.hex(slot) {
+            let _ = writeln!(primary, "{} = \"{hex}\"", slot.as_ref());
+        }
+    }
+    if !primary.is_empty() {
+        sections.push(format!("[colors.primary]\n{primary}"));
+    }
+    if let Some(hex) = slots.hex(TerminalSlot::Cursor) {
+        let mut cursor = format!("[colors.cursor]\ncursor = \"{hex}\"\n");
+        if let Some(bg) = slots.hex(TerminalSlot::Background) {
+            let _ = writeln!(cursor, "text = \"{bg}\"");
+        }
+        sections.push(cursor);
+    }
+    for (offset, section) in [(0, "normal"), (8, "bright")] {
+        let mut colors = String::new();
+        for (i, name) in ANSI_NAMES.iter().enumerate() {
+            if let Some(hex) = slots.hex(TerminalSlot::ALL[offset + i]) {
+                let _ = writeln!(colors, "{name} = \"{hex}\"");
+            }
+        }
+        if !colors.is_empty() {
+            sections.push(format!("[colors.{section}]\n{colors}"));
+        }
+    }
+    sections.join("\n")
+}
+
+fn as_foot(slots: &Slots) -> String {
+    let hex = |slot| {
+        slots
+            .hex(slot)
+            .map(|hex| hex.trim_start_matches('#').to_string())
+    };
+    let mut s = String::new();
+    if let (Some(bg), Some(cursor)) = (hex(TerminalSlot::Background), hex(TerminalSlot::Cursor)) {
+        let _ = writeln!(s, "[cursor]\ncolor={bg} {cursor}\n");
+    }
+    s.push_str("[colors]\n");
+    for slot in [TerminalSlot::Foreground, TerminalSlot::Background] {
+        if let Some(hex) = hex(slot) {
+            let _ = writeln!(s, "{}={hex}", slot.as_ref());
+        }
+    }
+    for slot in &TerminalSlot::ALL[..16] {
+        if let Some(hex) = hex(*slot) {
+            let (key, i) = if slot.index() >= 8 {
+                ("bright", slot.index() - 8)
+            } else {
+                ("regular", slot.index())
+            };
+            let _ = writeln!(s, "{key}{i}={hex}");
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{Palette, PaletteEntry, Rgb};
+    use pretty_assertions::assert_eq;
+
+    fn gray(v: u8) -> Color {
+        Rgb::new_scaled(v, v, v).into()
+    }
+
+    fn test_palette() -> NamedPalette {
+        let mut entries: Vec<_> = (0..16).map(|i| PaletteEntry::new(gray(i * 16))).collect();
+        entries.push(PaletteEntry::named(gray(0xf0), "fg"));
+        entries.push(PaletteEntry::named(gray(0xf8), "Background"));
+        entries.push(PaletteEntry::named(gray(0xff), "cursor"));
+        NamedPalette {
+            name: "scheme".into(),
+            palette: Palette::from_iter(entries),
+        }
+    }
+
+    #[test]
+    fn maps_slots() {
+        let palette = test_palette();
+        let mapping = SlotMapping::auto(&palette);
+        assert_eq!(mapping.get(TerminalSlot::Black), Some(0));
+        assert_eq!(mapping.get(TerminalSlot::BrightWhite), Some(15));
+        assert_eq!(mapping.get(TerminalSlot::Foreground), Some(16));
+        assert_eq!(mapping.get(TerminalSlot::Background), Some(17));
+        assert_eq!(mapping.get(TerminalSlot::Cursor), Some(18));
+
+        let palette = NamedPalette {
+            name: "few".into(),
+            palette: Palette::from_iter([
+                PaletteEntry::named(gray(1), "Bright Red"),
+                PaletteEntry::named(gray(2), "color1"),
+            ]),
+        };
+        let mapping = SlotMapping::auto(&palette);
+        assert_eq!(mapping.get(TerminalSlot::Black), None);
+        assert_eq!(mapping.get(TerminalSlot::Red), Some(1));
+        assert_eq!(mapping.get(TerminalSlot::BrightRed), Some(0));
+        assert_eq!(mapping.get(TerminalSlot::Green), None);
+    }
+
+    #[test]
+    fn exports_terminal_themes() {
+        let palette = test_palette();
+        let mut mapping = SlotMapping::auto(&palette);
+        for slot in &TerminalSlot::ALL[2..16] {
+            *mapping.get_mut(*slot) = None;
+        }
+
+        let want = "! scheme
+*.foreground: #f0f0f0
+*.background: #f8f8f8
+*.cursorColor: #ffffff
+*.color0: #000000
+*.color1: #101010
+";
+        assert_eq!(
+            palette.as_terminal_theme(TerminalFormat::Xresources, &mapping),
+            want
+        );
+
+        let want = "[colors.primary]
+background = \"#f8f8f8\"
+foreground = \"#f0f0f0\"
+
+[colors.cursor]
+cursor = \"#ffffff\"
+text = \"#f8f8f8\"
+
+[colors.normal]
+black = \"#000000\"
+red = \"#101010\"
+";
+        assert_eq!(
+            palette.as_terminal_theme(TerminalFormat::Alacritty, &mapping),
+            want
+        );
+
+        let want = "[cursor]
+color=f8f8f8 ffffff
+
+[colors]
+foreground=f0f0f0
+background=f8f8f8
+regular0=000000
+regular1=101010
+";
+        assert_eq!(
+            palette.as_terminal_theme(TerminalFormat::Foot, &mapping),
+            want
+        );
+
+        let want = r##"{
+  "name": "scheme",
+  "type": "light",
+  "colors": {
+    "editor.background": "#f8f8f8",
+    "terminal.background": "#f8f8f8",
+    "editor.foreground": "#f0f0f0",
+    "terminal.foreground": "#f0f0f0",
+    "editorCursor.foreground": "#ffffff",
+    "terminalCursor.foreground": "#ffffff",
+    "terminal.ansiBlack": "#000000",
+    "terminal.ansiRed": "#101010"
+  },
+  "tokenColors": []
+}
+"##;
+        assert_eq!(
+            palette.as_terminal_theme(TerminalFormat::VsCode, &mapping),
+            want
+        );
+    }
+
+    #[test]
+    fn exports_full_wezterm_scheme() {
+        let palette = test_palette();
+        let mapping = SlotMapping::auto(&palette);
+        let want = r##"[colors]
+foreground = "#f0f0f0"
+background = "#f8f8f8"
+cursor_bg = "#ffffff"
+cursor_border = "#ffffff"
+ansi = ["#000000", "#101010", "#202020", "#303030", "#404040", "#505050", "#606060", "#707070"]
+brights = ["#808080", "#909090", "#a0a0a0", "#b0b0b0", "#c0c0c0", "#d0d0d0", "#e0e0e0", "#f0f0f0"]
+
+[metadata]
+name = "scheme"
+"##;
+        assert_eq!(
+            palette.as_terminal_theme(TerminalFormat::WezTerm, &mapping),
+            want
+        );
+        let kitty = palette.as_terminal_theme(TerminalFormat::Kitty, &mapping);
+        assert!(kitty.starts_with("# scheme\nforeground #f0f0f0\nbackground #f8f8f8\n"));
+        assert!(kitty.ends_with("color15 #f0f0f0\n"));
+    }
+}
@@ -0,0 +1,345 @@
+//! Exports of named palettes as design tokens understood by various tools.
+
+use crate::color::{Color, NamedPalette, PaletteEntry};
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+/// Notation of color values in exported design tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum TokenNotation {
+    #[default]
+    Hex,
+    Rgb,
+    Oklch,
+}
+
+impl AsRef<str> for TokenNotation {
+    fn as_ref(&self) -> &str {
+        match self {
+            TokenNotation::Hex => "hex",
+            TokenNotation::Rgb => "rgb",
+            TokenNotation::Oklch => "oklch",
+        }
+    }
+}
+
+impl TokenNotation {
+    pub const ALL: [TokenNotation; 3] =
+        [TokenNotation::Hex, TokenNotation::Rgb, TokenNotation::Oklch];
+
+    pub fn format(&self, color: &Color) -> String {
+        match self {
+            TokenNotation::Hex => color.as_hex(),
+            TokenNotation::Rgb => color.as_css_rgb(),
+            TokenNotation::Oklch => {
+                let color = color.oklch();
+                format!(
+                    "oklch({:.2}% {:.4} {:.2})",
+                    color.l() * 100.,
+                    color.c(),
+                    color.h()
+                )
+            }
+        }
+    }
+
+    /// Returns the `$value` of a color token in the W3C Design Tokens format. Hex notation is
+    /// written as a string, other notations as a color object with a hex fallback.
+    fn design_token_value(&self, color: &Color) -> String {
+        let (space, components) = match self {
+            TokenNotation::Hex => return json_string(&color.as_hex()),
+            TokenNotation::Rgb => {
+                let rgb = color.rgb();
+                ("srgb", [rgb.r(), rgb.g(), rgb.b()])
+            }
+            TokenNotation::Oklch => {
+                let oklch = color.oklch();
+                ("oklch", [oklch.l(), oklch.c(), oklch.h()])
+            }
+        };
+        let components = components.map(json_number).join(", ");
+        format!(
+            "{{ \"colorSpace\": \"{space}\", \"components\": [{components}], \"hex\": {} }}",
+            json_string(&color.as_hex())
+        )
+    }
+}
+
+/// Returns the names of all entries usable as identifiers in exported tokens. Entries without
+/// a name are called `color-{i}` and repeated names get a numeric suffix.
+fn token_names(palette: &NamedPalette) -> Vec<String> {
+    let mut names: Vec<String> = Vec::with_capacity(palette.palette.0.len());
+    for (i, entry) in palette.palette.entries().enumerate() {
+        let name = entry
+            .name()
+            .map(slug)
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| format!("color-{i}"));
+        let mut unique = name.clone();
+        let mut suffix = 2;
+        while names.contains(&unique) {
+            unique = format!("{name}-{suffix}");
+            suffix += 1;
+        }
+        names.push(unique);
+    }
+    names
+}
+
+/// Formats a number for JSON with at most 4 decimal places.
+fn json_number(value: f32) -> String {
+    let s = format!("{value:.4}");
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" { "0".into() } else { s.into() }
+}
+
+/// Lowercases the text and replaces all runs of characters other than alphanumerics, `-` and
+/// `_` with a single `-`.
+pub(super) fn slug(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for ch in text.trim().chars().flat_map(char::to_lowercase) {
+        if ch.is_alphanumeric() || ch == '_' {
+            slug.push(ch);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
+pub(super) fn json_string(text: &str) -> String {
+    let mut s = String::with_capacity(text.len() + 2);
+    s.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            ch if ch.is_control() => {
+                let _ = write!(s, "\\u{:04x}", ch as u32);
+            }
+            ch => s.push(ch),
+        }
+    }
+    s.push('"');
+    s
+}
+
+/// Returns the note of the entry on a single line so that it can be used in a line comment.
+fn comment(entry: &PaletteEntry) -> Option<String> {
+    entry.note().map(|note| {
+        note.lines()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace("*/", "* /")
+    })
+}
+
+impl NamedPalette {
+    /// Exports this palette as a group of color tokens in the W3C Design Tokens format.
+    pub fn as_design_tokens(&self, notation: TokenNotation) -> String {
+        let mut json = format!("{{\n  {}: {{\n", json_string(&self.name));
+        let len = self.palette.0.len();
+        let names = token_names(self);
+        for (i, (entry, name)) in self.palette.entries().zip(&names).enumerate() {
+            let _ = write!(
+                json,
+                "    {}: {{\n      \"$type\": \"color\",\n      \"$value\": {}",
+                json_string(name),
+                notation.design_token_value(&entry.color)
+            );
+            if let Some(note) = entry.note() {
+                let _ = write!(json, ",\n      \"$description\": {}", json_string(note));
+            }
+            json.push_str("\n    }");
+            if i + 1 < len {
+                json.push(',');
+            }
+            json.push('\n');
+        }
+        json.push_str("  }\n}\n");
+        json
+    }
+
+    /// Exports this palette as CSS custom properties defined on `:root`.
+    pub fn as_css_variables(&self, notation: TokenNotation) -> String {
+        let mut css = String::from(":root {\n");
+        for (entry, name) in self.palette.entries().zip(token_names(self)) {
+            let _ = write!(css, "  --{name}: {};", notation.format(&entry.color));
+            if let Some(note) = comment(entry) {
+                let _ = write!(css, " /* {note} */");
+            }
+            css.push('\n');
+        }
+        css.push_str("}\n");
+        css
+    }
+
+    /// Exports this palette as SCSS variables.
+    pub fn as_scss_variables(&self, notation: TokenNotation) -> String {
+        self.as_variables('$', notation)
+    }
+
+    /// Exports this palette as LESS variables.
+    pub fn as_less_variables(&self, notation: TokenNotation) -> String {
+        self.as_variables('@', notation)
+    }
+
+    fn as_variables(&self, sigil: char, notation: TokenNotation) -> String {
+        let mut vars = String::new();
+        for (entry, name) in self.palette.entries().zip(token_names(self)) {
+            let _ = write!(vars, "{sigil}{name}: {};", notation.format(&entry.color));
+            if let Some(note) = comment(entry) {
+                let _ = write!(vars, " // {note}");
+            }
+            vars.push('\n');
+        }
+        vars
+    }
+
+    /// Exports this palette as a Tailwind CSS config extending the theme colors. Colors are
+    /// grouped under the name of the palette.
+    pub fn as_tailwind_config(&self, notation: TokenNotation) -> String {
+        let mut name = slug(&self.name);
+        if name.is_empty() {
+            name.push_str("palette");
+        }
+        let mut js = format!(
+            "module.exports = {{\n  theme: {{\n    extend: {{\n      colors: {{\n        '{name}': {{\n"
+        );
+        for (entry, name) in self.palette.entries().zip(token_names(self)) {
+            let _ = writeln!(
+                js,
+                "          '{name}': '{}',",
+                notation.format(&entry.color)
+            );
+        }
+        js.push_str("        },\n      },\n    },\n  },\n};\n");
+        js
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{Palette, Rgb};
+    use pretty_assertions::assert_eq;
+
+    fn test_palette() -> NamedPalette {
+        let mut danger = PaletteEntry::named(Rgb::new_scaled(255, 0, 0).into(), "Danger Zone");
+        danger.note = Some("used for \"errors\"".into());
+        NamedPalette {
+            name: "Brand colors".into(),
+            palette: Palette::from_iter([
+                danger,
+                PaletteEntry::named(Rgb::new_scaled(0, 0, 255).into(), "surface-2"),
+                PaletteEntry::new(Rgb::new_scaled(0, 255, 0).into()),
+            ]),
+        }
+    }
+
+    #[test]
+    fn formats_notation() {
+        let color = Color::from(Rgb::new_scaled(255, 0, 0));
+        assert_eq!(TokenNotation::Hex.format(&color), "#ff0000");
+        assert_eq!(TokenNotation::Rgb.format(&color), "rgb(255,0,0)");
+        assert_eq!(
+            TokenNotation::Oklch.format(&color),
+            "oklch(62.80% 0.2577 29.23)"
+        );
+    }
+
+    #[test]
+    fn exports_design_tokens() {
+        let want = r##"{
+  "Brand colors": {
+    "danger-zone": {
+      "$type": "color",
+      "$value": "#ff0000",
+      "$description": "used for \"errors\""
+    },
+    "surface-2": {
+      "$type": "color",
+      "$value": "#0000ff"
+    },
+    "color-2": {
+      "$type": "color",
+      "$value": "#00ff00"
+    }
+  }
+}
+"##;
+        assert_eq!(test_palette().as_design_tokens(TokenNotation::Hex), want);
+
+        let palette = NamedPalette {
+            name: "p".into(),
+            palette: Palette::from_iter([
+                PaletteEntry::named(Rgb::new_scaled(255, 0, 0).into(), "Red"),
+                PaletteEntry::named(Rgb::new_scaled(255, 255, 255).into(), "red"),
+            ]),
+        };
+        let want = r##"{
+  "p": {
+    "red": {
+      "$type": "color",
+      "$value": { "colorSpace": "srgb", "components": [1, 0, 0], "hex": "#ff0000" }
+    },
+    "red-2": {
+      "$type": "color",
+      "$value": { "colorSpace": "srgb", "components": [1, 1, 1], "hex": "#ffffff" }
+    }
+  }
+}
+"##;
+        assert_eq!(palette.as_design_tokens(TokenNotation::Rgb), want);
+    }
+
+    #[test]
+    fn exports_stylesheet_variables() {
+        let palette = test_palette();
+        let want = r#":root {
+  --danger-zone: rgb(255,0,0); /* used for "errors" */
+  --surface-2: rgb(0,0,255);
+  --color-2: rgb(0,255,0);
+}
+"#;
+        assert_eq!(palette.as_css_variables(TokenNotation::Rgb), want);
+
+        let want = r#"$danger-zone: #ff0000; // used for "errors"
+$surface-2: #0000ff;
+$color-2: #00ff00;
+"#;
+        assert_eq!(palette.as_scss_variables(TokenNotation::Hex), want);
+
+        let want = r#"@danger-zone: #ff0000; // used for "errors"
+@surface-2: #0000ff;
+@color-2: #00ff00;
+"#;
+        assert_eq!(palette.as_less_variables(TokenNotation::Hex), want);
+    }
+
+    #[test]
+    fn exports_tailwind_config() {
+        let want = r#"module.exports = {
+  theme: {
+    extend: {
+      colors: {
+        'brand-colors': {
+          'danger-zone': '#ff0000',
+          'surface-2': '#0000ff',
+          'color-2': '#00ff00',
+        },
+      },
+    },
+  },
+};
+"#;
+        assert_eq!(test_palette().as_tailwind_config(TokenNotation::Hex), want);
+    }
+}
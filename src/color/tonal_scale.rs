@@ -0,0 +1,173 @@
+use crate::color::{
+    Color, LchAB, NamedPalette, Palette, PaletteEntry,
+    difference::{from_reference_lab, reference_lab},
+    tokens::slug,
+    tones::fit_lch_chroma,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Target CIE L* of each step of a 50–900 design system ramp.
+const STEPS_50_900: [(u16, f32); 10] = [
+    (50, 97.),
+    (100, 93.),
+    (200, 85.),
+    (300, 76.),
+    (400, 65.),
+    (500, 54.),
+    (600, 44.),
+    (700, 35.),
+    (800, 26.),
+    (900, 18.),
+];
+
+/// Material 3 tones, a tone is the CIE L* of the color.
+const MATERIAL3_TONES: [(u16, f32); 13] = [
+    (0, 0.),
+    (10, 10.),
+    (20, 20.),
+    (30, 30.),
+    (40, 40.),
+    (50, 50.),
+    (60, 60.),
+    (70, 70.),
+    (80, 80.),
+    (90, 90.),
+    (95, 95.),
+    (99, 99.),
+    (100, 100.),
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum TonalScale {
+    #[default]
+    Steps50To900,
+    Material3,
+}
+
+impl TonalScale {
+    pub const ALL: [TonalScale; 2] = [TonalScale::Steps50To900, TonalScale::Material3];
+
+    /// Returns pairs of step names and their target CIE L* lightness.
+    pub fn steps(&self) -> &'static [(u16, f32)] {
+        match self {
+            TonalScale::Steps50To900 => &STEPS_50_900,
+            TonalScale::Material3 => &MATERIAL3_TONES,
+        }
+    }
+}
+
+impl AsRef<str> for TonalScale {
+    fn as_ref(&self) -> &str {
+        match self {
+            TonalScale::Steps50To900 => "50 - 900",
+            TonalScale::Material3 => "Material 3 tones",
+        }
+    }
+}
+
+impl Color {
+    /// Returns a ramp of colors with the hue of this color at the lightness of each step of
+    /// `scale`. Chroma is kept where the sRGB gamut allows it.
+    ///
+    /// With `snap_seed` this color replaces the step closest to it in lightness.
+    pub fn tonal_scale(&self, scale: TonalScale, snap_seed: bool) -> Vec<(u16, Color)> {
+        let lch = LchAB::from(reference_lab(self));
+        let steps = scale.steps();
+        let snapped = snap_seed
+            .then(|| {
+                steps
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, (_, a)), (_, (_, b))| {
+                        (a - lch.l()).abs().total_cmp(&(b - lch.l()).abs())
+                    })
+                    .map(|(i, _)| i)
+            })
+            .flatten();
+
+        steps
+            .iter()
+            .enumerate()
+            .map(|(i, &(step, l))| {
+                if snapped == Some(i) {
+                    return (step, *self);
+                }
+                if l <= 0. {
+                    return (step, Color::black());
+                }
+                if l >= 100. {
+                    return (step, Color::white());
+                }
+                let c = fit_lch_chroma(l, lch.c(), lch.h());
+                (step, from_reference_lab(LchAB::new(l, c, lch.h()).into()))
+            })
+            .collect()
+    }
+}
+
+impl NamedPalette {
+    /// Creates a palette called `name` from the tonal scale of `seed` with entries named like
+    /// `blue-500`. Steps repeating a color of a previous step are left out.
+    pub fn tonal_scale(name: &str, seed: &Color, scale: TonalScale, snap_seed: bool) -> Self {
+        let prefix = slug(name);
+        let mut palette = Palette::default();
+        for (step, color) in seed.tonal_scale(scale, snap_seed) {
+            palette.add_entry(PaletteEntry::named(color, format!("{prefix}-{step}")));
+        }
+        Self {
+            name: name.to_string(),
+            palette,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Rgb;
+
+    #[test]
+    fn names_entries() {
+        let seed: Color = Rgb::new_scaled(37, 99, 235).into();
+        let palette = NamedPalette::tonal_scale("Blue", &seed, TonalScale::Steps50To900, false);
+        assert_eq!(palette.name, "Blue");
+        let names: Vec<_> = palette.palette.entries().filter_map(|e| e.name()).collect();
+        assert_eq!(
+            names,
+            [
+                "blue-50", "blue-100", "blue-200", "blue-300", "blue-400", "blue-500", "blue-600",
+                "blue-700", "blue-800", "blue-900"
+            ]
+        );
+    }
+
+    #[test]
+    fn targets_lightness() {
+        let seed: Color = Rgb::new_scaled(37, 99, 235).into();
+        let scale = seed.tonal_scale(TonalScale::Material3, false);
+        for ((_, color), (_, l)) in scale.iter().zip(MATERIAL3_TONES) {
+            assert!((reference_lab(color).l() - l).abs() < 1., "{l}");
+        }
+        assert_eq!(scale[0].1.as_hex(), "#000000");
+        assert_eq!(scale[12].1.as_hex(), "#ffffff");
+
+        let hue = LchAB::from(reference_lab(&seed)).h();
+        let mid = LchAB::from(reference_lab(&scale[5].1));
+        assert!((mid.h() - hue).abs() < 2.);
+    }
+
+    #[test]
+    fn snaps_seed() {
+        let seed: Color = Rgb::new_scaled(37, 99, 235).into();
+        let scale = seed.tonal_scale(TonalScale::Steps50To900, true);
+        let (step, _) = scale.iter().find(|(_, c)| *c == seed).unwrap();
+        assert_eq!(*step, 600);
+        assert!(
+            !seed
+                .tonal_scale(TonalScale::Steps50To900, false)
+                .iter()
+                .any(|(_, c)| *c == seed)
+        );
+    }
+}
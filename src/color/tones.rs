@@ -0,0 +1,251 @@
+use crate::color::{
+    Color, InterpolationSpace, LchAB, Oklab, Oklch,
+    difference::{delta_e76, from_reference_lab, reference_lab},
+};
+
+use serde::{Deserialize, Serialize};
+
+const CHROMA_SEARCH_STEPS: usize = 16;
+const GAMUT_EPSILON: f32 = 1e-4;
+/// Largest Lab difference after converting to sRGB and back that still counts as in gamut.
+const LAB_ROUNDTRIP_TOLERANCE: f32 = 0.1;
+
+/// Color space in which perceptual tints, shades and tones are computed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum PerceptualSpace {
+    #[default]
+    Oklch,
+    LchAB,
+}
+
+impl PerceptualSpace {
+    pub const ALL: [PerceptualSpace; 2] = [PerceptualSpace::Oklch, PerceptualSpace::LchAB];
+}
+
+impl AsRef<str> for PerceptualSpace {
+    fn as_ref(&self) -> &str {
+        match self {
+            PerceptualSpace::Oklch => "OKLCH",
+            PerceptualSpace::LchAB => "CIE LCh",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum ToneMode {
+    /// Only lightness changes for tints and shades, only chroma for tones. Chroma is reduced only
+    /// as much as needed to stay inside of the sRGB gamut.
+    #[default]
+    KeepHueChroma,
+    /// Mix toward white, black or gray
+    Mix,
+}
+
+impl ToneMode {
+    pub const ALL: [ToneMode; 2] = [ToneMode::KeepHueChroma, ToneMode::Mix];
+}
+
+impl AsRef<str> for ToneMode {
+    fn as_ref(&self) -> &str {
+        match self {
+            ToneMode::KeepHueChroma => "Keep hue and chroma",
+            ToneMode::Mix => "Mix",
+        }
+    }
+}
+
+/// Direction in which variations of a color are generated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ToneKind {
+    /// Toward white
+    Tint,
+    /// Toward black
+    Shade,
+    /// Toward gray of the same lightness
+    Tone,
+}
+
+impl Color {
+    /// Returns `total` variations of this color starting with the color itself and ending at
+    /// white, black or gray depending on `kind`, evenly spaced in the perceptual `space`.
+    pub fn perceptual_variants(
+        &self,
+        kind: ToneKind,
+        total: u8,
+        space: PerceptualSpace,
+        mode: ToneMode,
+    ) -> Vec<Color> {
+        if total <= 1 {
+            return vec![*self];
+        }
+        let steps = (total - 1) as f32;
+        (0..total)
+            .map(|i| self.perceptual_variant(kind, i as f32 / steps, space, mode))
+            .collect()
+    }
+
+    fn perceptual_variant(
+        &self,
+        kind: ToneKind,
+        t: f32,
+        space: PerceptualSpace,
+        mode: ToneMode,
+    ) -> Color {
+        if t <= 0. {
+            return *self;
+        }
+        match (kind, t >= 1.) {
+            (ToneKind::Tint, true) => return Color::white(),
+            (ToneKind::Shade, true) => return Color::black(),
+            _ => {}
+        }
+        match mode {
+            ToneMode::Mix => {
+                let target = match kind {
+                    ToneKind::Tint => Color::white(),
+                    ToneKind::Shade => Color::black(),
+                    ToneKind::Tone => self.gray(space),
+                };
+                let space = match space {
+                    PerceptualSpace::Oklch => InterpolationSpace::Oklch,
+                    PerceptualSpace::LchAB => InterpolationSpace::LchShorter,
+                };
+                space.interpolate(self, &target, t)
+            }
+            ToneMode::KeepHueChroma => {
+                let lerp = |a: f32, b: f32| a + (b - a) * t;
+                match space {
+                    PerceptualSpace::Oklch => {
+                        let lch = self.oklch();
+                        let (l, c) = match kind {
+                            ToneKind::Tint => (lerp(lch.l(), 1.), lch.c()),
+                            ToneKind::Shade => (lerp(lch.l(), 0.), lch.c()),
+                            ToneKind::Tone => (lch.l(), lerp(lch.c(), 0.)),
+                        };
+                        Oklch::new(l, fit_oklch_chroma(l, c, lch.h()), lch.h())
+                            .to_rgb()
+                            .into()
+                    }
+                    PerceptualSpace::LchAB => {
+                        let lch = LchAB::from(reference_lab(self));
+                        let (l, c) = match kind {
+                            ToneKind::Tint => (lerp(lch.l(), 100.), lch.c()),
+                            ToneKind::Shade => (lerp(lch.l(), 0.), lch.c()),
+                            ToneKind::Tone => (lch.l(), lerp(lch.c(), 0.)),
+                        };
+                        let c = fit_lch_chroma(l, c, lch.h());
+                        from_reference_lab(LchAB::new(l, c, lch.h()).into())
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the achromatic color with the same perceptual lightness as this color.
+    fn gray(&self, space: PerceptualSpace) -> Color {
+        match space {
+            PerceptualSpace::Oklch => Oklch::new(self.oklch().l(), 0., 0.).to_rgb().into(),
+            PerceptualSpace::LchAB => {
+                from_reference_lab(LchAB::new(reference_lab(self).l(), 0., 0.).into())
+            }
+        }
+    }
+}
+
+/// Returns the highest OKLCH chroma up to `c` that fits into the sRGB gamut.
+pub(super) fn fit_oklch_chroma(l: f32, c: f32, h: f32) -> f32 {
+    max_chroma(c, |c| {
+        let rgb = Oklab::from(Oklch::new(l, c, h)).to_linear_srgb();
+        rgb.iter()
+            .all(|v| (-GAMUT_EPSILON..=1. + GAMUT_EPSILON).contains(v))
+    })
+}
+
+/// Returns the highest CIE LCh chroma up to `c` that fits into the sRGB gamut.
+pub(super) fn fit_lch_chroma(l: f32, c: f32, h: f32) -> f32 {
+    if l <= 0. || l >= 100. {
+        return 0.;
+    }
+    max_chroma(c, |c| {
+        let lab = LchAB::new(l, c, h).into();
+        delta_e76(&reference_lab(&from_reference_lab(lab)), &lab) < LAB_ROUNDTRIP_TOLERANCE
+    })
+}
+
+/// Returns the highest chroma up to `chroma` for which `in_gamut` holds so that colors keep their
+/// hue instead of being clipped channel by channel.
+fn max_chroma(chroma: f32, in_gamut: impl Fn(f32) -> bool) -> f32 {
+    if in_gamut(chroma) {
+        return chroma;
+    }
+    let (mut low, mut high) = (0., chroma);
+    for _ in 0..CHROMA_SEARCH_STEPS {
+        let mid = (low + high) / 2.;
+        if in_gamut(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Rgb;
+
+    fn hex(colors: Vec<Color>) -> Vec<String> {
+        colors.into_iter().map(|c| c.as_hex()).collect()
+    }
+
+    #[test]
+    fn variants_end_at_target() {
+        let color: Color = Rgb::new_scaled(40, 120, 200).into();
+        for space in PerceptualSpace::ALL {
+            for mode in ToneMode::ALL {
+                let tints = hex(color.perceptual_variants(ToneKind::Tint, 5, space, mode));
+                assert_eq!(tints.len(), 5);
+                assert_eq!(tints[0], color.as_hex());
+                let shades = color.perceptual_variants(ToneKind::Shade, 5, space, mode);
+                assert_eq!(shades[4].as_hex(), "#000000");
+            }
+        }
+        let tints =
+            color.perceptual_variants(ToneKind::Tint, 3, PerceptualSpace::Oklch, ToneMode::Mix);
+        assert_eq!(tints[2].as_hex(), "#ffffff");
+    }
+
+    #[test]
+    fn tones_keep_lightness() {
+        let color: Color = Rgb::new_scaled(200, 40, 60).into();
+        let tones = color.perceptual_variants(
+            ToneKind::Tone,
+            4,
+            PerceptualSpace::Oklch,
+            ToneMode::KeepHueChroma,
+        );
+        let gray = tones[3].rgb();
+        assert!((gray.r() - gray.g()).abs() < 0.01 && (gray.g() - gray.b()).abs() < 0.01);
+        for tone in &tones {
+            assert!((tone.oklch().l() - color.oklch().l()).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn shades_keep_hue() {
+        let color: Color = Rgb::new_scaled(40, 120, 200).into();
+        let shades = color.perceptual_variants(
+            ToneKind::Shade,
+            6,
+            PerceptualSpace::Oklch,
+            ToneMode::KeepHueChroma,
+        );
+        let hue = color.oklch().h();
+        for shade in &shades[..4] {
+            assert!((shade.oklch().h() - hue).abs() < 3.);
+        }
+        let lightness: Vec<_> = shades.iter().map(|c| c.oklch().l()).collect();
+        assert!(lightness.windows(2).all(|w| w[0] > w[1]));
+    }
+}
@@ -1,25 +1,139 @@
 #![allow(dead_code)]
 use crate::{
-    color::{Rgb, illuminant::Illuminant, xyY},
+    color::{
+        Rgb,
+        illuminant::{Illuminant, WhitePoint},
+        xyY,
+    },
     math::{Matrix1x3, Matrix3},
 };
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+/// Function converting between encoded RGB values and linear light.
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+pub enum TransferFunction {
+    Gamma(f32),
+    /// ICC parametric curve, encoded values `v >= d` decode to `(a * v + b) ^ gamma` and lower ones
+    /// to `c * v`
+    Parametric {
+        gamma: f32,
+        a: f32,
+        b: f32,
+        c: f32,
+        d: f32,
+    },
+}
+
+impl TransferFunction {
+    pub const SRGB: TransferFunction = TransferFunction::Parametric {
+        gamma: 2.4,
+        a: 1. / 1.055,
+        b: 0.055 / 1.055,
+        c: 1. / 12.92,
+        d: 0.04045,
+    };
+
+    /// Converts an encoded value to linear light.
+    pub fn decode(&self, value: f32) -> f32 {
+        match *self {
+            TransferFunction::Gamma(gamma) => value.max(0.).powf(gamma),
+            TransferFunction::Parametric { gamma, a, b, c, d } => {
+                if value >= d {
+                    (a * value + b).max(0.).powf(gamma)
+                } else {
+                    c * value
+                }
+            }
+        }
+    }
+
+    /// Converts linear light to an encoded value.
+    pub fn encode(&self, value: f32) -> f32 {
+        match *self {
+            TransferFunction::Gamma(gamma) => value.max(0.).powf(1. / gamma),
+            TransferFunction::Parametric { gamma, a, b, c, d } => {
+                if value >= c * d && a != 0. {
+                    (value.max(0.).powf(1. / gamma) - b) / a
+                } else if c != 0. {
+                    value / c
+                } else {
+                    0.
+                }
+            }
+        }
+    }
+
+    /// Returns the exponent of the curve.
+    pub fn gamma(&self) -> f32 {
+        match *self {
+            TransferFunction::Gamma(gamma) => gamma,
+            TransferFunction::Parametric { gamma, .. } => gamma,
+        }
+    }
+}
+
+/// RGB working space defined by the user.
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CustomWorkingSpace {
+    /// Chromaticity coordinates of the red primary
+    pub red: (f32, f32),
+    pub green: (f32, f32),
+    pub blue: (f32, f32),
+    pub white: WhitePoint,
+    pub transfer: TransferFunction,
+}
+
+impl Default for CustomWorkingSpace {
+    fn default() -> Self {
+        Self {
+            red: (0.64, 0.33),
+            green: (0.30, 0.60),
+            blue: (0.15, 0.06),
+            white: WhitePoint::new(0.3127, 0.3290),
+            transfer: TransferFunction::SRGB,
+        }
+    }
+}
+
+impl CustomWorkingSpace {
+    /// Smallest area of the primaries triangle, smaller ones give a singular RGB to XYZ matrix.
+    const MIN_GAMUT_AREA: f32 = 1e-6;
+
+    /// Returns `true` if all chromaticities are valid and the primaries span a triangle.
+    pub fn is_valid(&self) -> bool {
+        let [r, g, b] = [self.red, self.green, self.blue];
+        let area = ((g.0 - r.0) * (b.1 - r.1) - (b.0 - r.0) * (g.1 - r.1)).abs() / 2.;
+        [r, g, b]
+            .into_iter()
+            .all(|(x, y)| WhitePoint::new(x, y).is_valid())
+            && self.white.is_valid()
+            && area > Self::MIN_GAMUT_AREA
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
 #[allow(clippy::upper_case_acronyms)]
 pub enum RgbWorkingSpace {
+    ACES2065,
+    ACEScg,
     Adobe,
     Apple,
     CIE,
+    DCIP3,
+    DisplayP3,
     ECI,
     NTSC,
     PAL,
     ProPhoto,
+    Rec709,
+    Rec2020,
     SRGB,
     WideGamut,
+    Custom(CustomWorkingSpace),
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for RgbWorkingSpace {
     fn default() -> Self {
         Self::SRGB
@@ -27,34 +141,62 @@ impl Default for RgbWorkingSpace {
 }
 
 impl RgbWorkingSpace {
+    /// Built-in working spaces
+    pub const ALL: [RgbWorkingSpace; 15] = [
+        RgbWorkingSpace::ACES2065,
+        RgbWorkingSpace::ACEScg,
+        RgbWorkingSpace::Adobe,
+        RgbWorkingSpace::Apple,
+        RgbWorkingSpace::CIE,
+        RgbWorkingSpace::DCIP3,
+        RgbWorkingSpace::DisplayP3,
+        RgbWorkingSpace::ECI,
+        RgbWorkingSpace::NTSC,
+        RgbWorkingSpace::PAL,
+        RgbWorkingSpace::ProPhoto,
+        RgbWorkingSpace::Rec709,
+        RgbWorkingSpace::Rec2020,
+        RgbWorkingSpace::SRGB,
+        RgbWorkingSpace::WideGamut,
+    ];
+
     pub fn reference_illuminant(&self) -> Illuminant {
         use RgbWorkingSpace::*;
         match &self {
+            ACES2065 | ACEScg => Illuminant::D60,
             Adobe => Illuminant::D65,
             Apple => Illuminant::D65,
             CIE => Illuminant::E,
+            DCIP3 => Illuminant::Dci,
+            DisplayP3 => Illuminant::D65,
             ECI => Illuminant::D50,
             NTSC => Illuminant::C,
             PAL => Illuminant::D65,
             ProPhoto => Illuminant::D50,
+            Rec709 | Rec2020 => Illuminant::D65,
             SRGB => Illuminant::D65,
             WideGamut => Illuminant::D50,
+            Custom(ws) => Illuminant::Custom(ws.white),
         }
     }
 
+    /// Returns the chromaticity coordinates of the red, green and blue primaries.
+    fn primaries(&self) -> [(f32, f32); 3] {
+        if let RgbWorkingSpace::Custom(ws) = self {
+            return [ws.red, ws.green, ws.blue];
+        }
+        [
+            self.reference_red_xyy(),
+            self.reference_green_xyy(),
+            self.reference_blue_xyy(),
+        ]
+        .map(|c| (c.x(), c.y()))
+    }
+
     pub fn rgb_matrix(&self) -> Matrix3 {
-        let ref_red = self.reference_red_xyy();
-        let ref_green = self.reference_green_xyy();
-        let ref_blue = self.reference_blue_xyy();
+        let [(xr, yr), (xg, yg), (xb, yb)] = self.primaries();
         let ref_white = self.reference_illuminant().xyz();
 
-        let xr = ref_red.x();
-        let yr = ref_red.y();
-        let xg = ref_green.x();
-        let yg = ref_green.y();
-        let xb = ref_blue.x();
-        let yb = ref_blue.y();
-
         let xxr = xr / yr;
         let yyr = 1.;
         let zzr = (1. - xr - yr) / yr;
@@ -86,37 +228,56 @@ impl RgbWorkingSpace {
     pub fn gamma(&self) -> f32 {
         use RgbWorkingSpace::*;
         match &self {
+            ACES2065 | ACEScg => 1.,
             Adobe => 2.2,
             Apple => 1.8,
             CIE => 2.2,
+            DCIP3 => 2.6,
+            DisplayP3 => 2.2,
             ECI => 3.,
             NTSC => 2.2,
             PAL => 2.2,
             ProPhoto => 1.8,
+            Rec709 | Rec2020 => 2.2,
             SRGB => 2.2,
             WideGamut => 2.2,
+            Custom(ws) => ws.transfer.gamma(),
         }
     }
 
     pub fn compand_channels(&self, color: Rgb) -> Rgb {
         use RgbWorkingSpace::*;
         match &self {
-            Adobe | Apple | CIE | NTSC | PAL | ProPhoto | WideGamut => {
+            Adobe | Apple | CIE | DCIP3 | NTSC | PAL | ProPhoto | WideGamut => {
                 color.gamma_compand(self.gamma())
             }
+            ACES2065 | ACEScg => color,
             ECI => color.l_compand(),
-            SRGB => color.srgb_compand(),
+            Rec709 | Rec2020 => color.rec709_compand(),
+            DisplayP3 | SRGB => color.srgb_compand(),
+            Custom(ws) => Rgb::new(
+                ws.transfer.encode(color.r()),
+                ws.transfer.encode(color.g()),
+                ws.transfer.encode(color.b()),
+            ),
         }
     }
 
     pub fn inverse_compand_channels(&self, color: Rgb) -> Rgb {
         use RgbWorkingSpace::*;
         match &self {
-            Adobe | Apple | CIE | NTSC | PAL | ProPhoto | WideGamut => {
+            Adobe | Apple | CIE | DCIP3 | NTSC | PAL | ProPhoto | WideGamut => {
                 color.inverse_gamma_compand(self.gamma())
             }
+            ACES2065 | ACEScg => color,
             ECI => color.inverse_l_compand(),
-            SRGB => color.inverse_srgb_compand(),
+            Rec709 | Rec2020 => color.inverse_rec709_compand(),
+            DisplayP3 | SRGB => color.inverse_srgb_compand(),
+            Custom(ws) => Rgb::new(
+                ws.transfer.decode(color.r()),
+                ws.transfer.decode(color.g()),
+                ws.transfer.decode(color.b()),
+            ),
         }
     }
 
@@ -124,15 +285,22 @@ impl RgbWorkingSpace {
     pub fn reference_red_xyy(&self) -> xyY {
         use RgbWorkingSpace::*;
         match &self {
+            ACES2065  => xyY::new(0.7347, 0.2653, 0.343966),
+            ACEScg    => xyY::new(0.7130, 0.2930, 0.272229),
             Adobe     => xyY::new(0.6400, 0.3300, 0.297361),
             Apple     => xyY::new(0.6250, 0.3400, 0.244634),
             CIE       => xyY::new(0.7350, 0.2650, 0.176204),
+            DCIP3     => xyY::new(0.6800, 0.3200, 0.209492),
+            DisplayP3 => xyY::new(0.6800, 0.3200, 0.228975),
             ECI       => xyY::new(0.6700, 0.3300, 0.320250),
             NTSC      => xyY::new(0.6700, 0.3300, 0.298839),
             PAL       => xyY::new(0.6400, 0.3300, 0.222021),
             ProPhoto  => xyY::new(0.7347, 0.2653, 0.288040),
+            Rec709    => xyY::new(0.6400, 0.3300, 0.212639),
+            Rec2020   => xyY::new(0.7080, 0.2920, 0.262700),
             SRGB      => xyY::new(0.6400, 0.3300, 0.212656),
             WideGamut => xyY::new(0.7350, 0.2650, 0.258187),
+            Custom(ws) => xyY::new(ws.red.0, ws.red.1, self.rgb_matrix()[1][0]),
        }
     }
 
@@ -140,15 +308,22 @@ impl RgbWorkingSpace {
     pub fn reference_green_xyy(&self) -> xyY {
         use RgbWorkingSpace::*;
         match &self {
+            ACES2065  => xyY::new(0.0000, 1.0000, 0.728166),
+            ACEScg    => xyY::new(0.1650, 0.8300, 0.674082),
             Adobe     => xyY::new(0.2100, 0.7100, 0.627355),
             Apple     => xyY::new(0.2800, 0.5950, 0.672034),
             CIE       => xyY::new(0.2740, 0.7170, 0.812985),
+            DCIP3     => xyY::new(0.2650, 0.6900, 0.721595),
+            DisplayP3 => xyY::new(0.2650, 0.6900, 0.691739),
             ECI       => xyY::new(0.2100, 0.7100, 0.602071),
             NTSC      => xyY::new(0.2100, 0.7100, 0.586811),
             PAL       => xyY::new(0.2900, 0.6000, 0.706645),
             ProPhoto  => xyY::new(0.1596, 0.8404, 0.711874),
+            Rec709    => xyY::new(0.3000, 0.6000, 0.715169),
+            Rec2020   => xyY::new(0.1700, 0.7970, 0.677998),
             SRGB      => xyY::new(0.3000, 0.6000, 0.715158),
             WideGamut => xyY::new(0.1150, 0.8260, 0.724938),
+            Custom(ws) => xyY::new(ws.green.0, ws.green.1, self.rgb_matrix()[1][1]),
         }
     }
 
@@ -156,15 +331,22 @@ impl RgbWorkingSpace {
     pub fn reference_blue_xyy(&self) -> xyY {
         use RgbWorkingSpace::*;
         match &self {
+            ACES2065  => xyY::new(0.0001, -0.0770, -0.072133),
+            ACEScg    => xyY::new(0.1280, 0.0440, 0.053690),
             Adobe     => xyY::new(0.1500, 0.0600, 0.075285),
             Apple     => xyY::new(0.1550, 0.0700, 0.083332),
             CIE       => xyY::new(0.1670, 0.0090, 0.010811),
+            DCIP3     => xyY::new(0.1500, 0.0600, 0.068913),
+            DisplayP3 => xyY::new(0.1500, 0.0600, 0.079287),
             ECI       => xyY::new(0.1400, 0.0800, 0.077679),
             NTSC      => xyY::new(0.1400, 0.0800, 0.114350),
             PAL       => xyY::new(0.1500, 0.0600, 0.071334),
             ProPhoto  => xyY::new(0.0366, 0.0001, 0.000086),
+            Rec709    => xyY::new(0.1500, 0.0600, 0.072192),
+            Rec2020   => xyY::new(0.1310, 0.0460, 0.059302),
             SRGB      => xyY::new(0.1500, 0.0600, 0.072186),
             WideGamut => xyY::new(0.1570, 0.0180, 0.016875),
+            Custom(ws) => xyY::new(ws.blue.0, ws.blue.1, self.rgb_matrix()[1][2]),
         }
     }
 }
@@ -172,17 +354,51 @@ impl RgbWorkingSpace {
 impl AsRef<str> for RgbWorkingSpace {
     fn as_ref(&self) -> &str {
         match &self {
+            RgbWorkingSpace::ACES2065 => "ACES2065-1",
+            RgbWorkingSpace::ACEScg => "ACEScg",
             RgbWorkingSpace::Adobe => "Adobe RGB",
             RgbWorkingSpace::Apple => "Apple RGB",
             RgbWorkingSpace::CIE => "CIE RGB",
+            RgbWorkingSpace::DCIP3 => "DCI-P3",
+            RgbWorkingSpace::DisplayP3 => "Display P3",
             RgbWorkingSpace::ECI => "ECI RGB",
             RgbWorkingSpace::NTSC => "NTSC RGB",
             RgbWorkingSpace::PAL => "PAL RGB",
             RgbWorkingSpace::ProPhoto => "Pro Photo RGB",
+            RgbWorkingSpace::Rec709 => "Rec. 709",
+            RgbWorkingSpace::Rec2020 => "Rec. 2020",
             RgbWorkingSpace::SRGB => "SRGB",
             RgbWorkingSpace::WideGamut => "Adobe Wide Gamut RGB",
+            RgbWorkingSpace::Custom(_) => "Custom",
         }
     }
 }
 
 //####################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_custom_working_space() {
+        assert!(CustomWorkingSpace::default().is_valid());
+        let collinear = CustomWorkingSpace {
+            red: (0.6, 0.3),
+            green: (0.4, 0.3),
+            blue: (0.2, 0.3),
+            ..Default::default()
+        };
+        assert!(!collinear.is_valid());
+        let zero_y = CustomWorkingSpace {
+            blue: (0.15, 0.),
+            ..Default::default()
+        };
+        assert!(!zero_y.is_valid());
+        let white = CustomWorkingSpace {
+            white: WhitePoint::new(0.3, -0.1),
+            ..Default::default()
+        };
+        assert!(!white.is_valid());
+    }
+}
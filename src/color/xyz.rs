@@ -75,11 +75,10 @@ impl Xyz {
         let src_lms = ma * src_ref_xyz;
         let dst_lms = ma * dst_ref_xyz;
 
-        let lms = Matrix3::from([
-            [src_lms[0] / dst_lms[0], 0., 0.],
-            [0., src_lms[1] / dst_lms[1], 0.],
-            [0., 0., src_lms[2] / dst_lms[2]],
-        ]);
+        // Incomplete adaptation blends each cone gain with no adaptation at all
+        let d = method.degree();
+        let gain = |i: usize| d * dst_lms[i] / src_lms[i] + 1. - d;
+        let lms = Matrix3::from([[gain(0), 0., 0.], [0., gain(1), 0.], [0., 0., gain(2)]]);
 
         let m = ma.inverse().expect("inverse adaptation matrix") * lms * ma;
 
@@ -174,6 +173,7 @@ impl From<Xyz> for Matrix1x3 {
 #[cfg(test)]
 mod tests {
     use super::{CIEColor, Rgb, RgbWorkingSpace, Xyz};
+    use crate::color::{ChromaticAdaptationMethod, CustomWorkingSpace, Illuminant};
 
     #[test]
     fn rgb_to_xyz() {
@@ -196,4 +196,81 @@ mod tests {
             Xyz: 0.9504699, 1., 1.0888301
         );
     }
+
+    #[test]
+    fn video_and_cinema_spaces_roundtrip() {
+        for ws in [
+            RgbWorkingSpace::ACES2065,
+            RgbWorkingSpace::ACEScg,
+            RgbWorkingSpace::DCIP3,
+            RgbWorkingSpace::DisplayP3,
+            RgbWorkingSpace::Rec709,
+            RgbWorkingSpace::Rec2020,
+        ] {
+            let white = Xyz::from_rgb(Rgb::new(1., 1., 1.), ws);
+            let reference = ws.reference_illuminant().xyz();
+            assert!((white.x() - reference.x()).abs() < 1e-3, "{ws:?}");
+            assert!((white.z() - reference.z()).abs() < 1e-3, "{ws:?}");
+
+            let rgb = Rgb::new(0.2, 0.5, 0.8);
+            let back = Xyz::from_rgb(rgb, ws).to_rgb(ws);
+            assert!((back.r() - rgb.r()).abs() < 1e-3, "{ws:?}");
+            assert!((back.g() - rgb.g()).abs() < 1e-3, "{ws:?}");
+            assert!((back.b() - rgb.b()).abs() < 1e-3, "{ws:?}");
+        }
+    }
+
+    #[test]
+    fn custom_working_space_matches_builtin() {
+        let custom = RgbWorkingSpace::Custom(CustomWorkingSpace::default());
+        let rgb = Rgb::new(0.2, 0.5, 0.8);
+        let expected = Xyz::from_rgb(rgb, RgbWorkingSpace::SRGB);
+        let got = Xyz::from_rgb(rgb, custom);
+        assert!((got.x() - expected.x()).abs() < 1e-3);
+        assert!((got.y() - expected.y()).abs() < 1e-3);
+        assert!((got.z() - expected.z()).abs() < 1e-3);
+
+        let back = got.to_rgb(custom);
+        assert!((back.r() - rgb.r()).abs() < 1e-3);
+        assert!((back.b() - rgb.b()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn adapts_white_points() {
+        let d65 = Illuminant::D65.xyz();
+        let d50 = Illuminant::D50.xyz();
+        for method in ChromaticAdaptationMethod::ALL {
+            let got = d65.chromatic_adaptation_transform(method, Illuminant::D65, Illuminant::D50);
+            assert!((got.x() - d50.x()).abs() < 1e-4, "{method:?}");
+            assert!((got.y() - d50.y()).abs() < 1e-4, "{method:?}");
+            assert!((got.z() - d50.z()).abs() < 1e-4, "{method:?}");
+        }
+
+        let unadapted = d65.chromatic_adaptation_transform(
+            ChromaticAdaptationMethod::Cat16 { degree: Some(0.) },
+            Illuminant::D65,
+            Illuminant::D50,
+        );
+        assert!((unadapted.z() - d65.z()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn cat_transforms() {
+        let xyz = Xyz::new(0.2, 0.3, 0.4);
+        for (method, expected) in [
+            (
+                ChromaticAdaptationMethod::Cat02 { degree: None },
+                [0.1966, 0.2966, 0.3036],
+            ),
+            (
+                ChromaticAdaptationMethod::Cat16 { degree: None },
+                [0.2007, 0.2996, 0.3039],
+            ),
+        ] {
+            let got = xyz.chromatic_adaptation_transform(method, Illuminant::D65, Illuminant::D50);
+            assert!((got.x() - expected[0]).abs() < 1e-3, "{method:?} {got:?}");
+            assert!((got.y() - expected[1]).abs() < 1e-3, "{method:?} {got:?}");
+            assert!((got.z() - expected[2]).abs() < 1e-3, "{method:?} {got:?}");
+        }
+    }
 }
@@ -0,0 +1,201 @@
+use crate::color::Rgb;
+
+use serde::{Deserialize, Serialize};
+
+/// Bit depths offered for YCbCr code values.
+pub const YCBCR_BIT_DEPTHS: [u8; 3] = [8, 10, 12];
+
+/// Standard defining the luma coefficients of a YCbCr encoding.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum YCbCrStandard {
+    /// ITU-R BT.601, standard definition video
+    Bt601,
+    /// ITU-R BT.709, high definition video
+    #[default]
+    Bt709,
+    /// ITU-R BT.2020 non-constant luminance, ultra high definition video
+    Bt2020,
+}
+
+impl YCbCrStandard {
+    pub const ALL: [YCbCrStandard; 3] = [
+        YCbCrStandard::Bt601,
+        YCbCrStandard::Bt709,
+        YCbCrStandard::Bt2020,
+    ];
+
+    /// Returns the red and blue luma coefficients.
+    fn kr_kb(&self) -> (f32, f32) {
+        match self {
+            YCbCrStandard::Bt601 => (0.299, 0.114),
+            YCbCrStandard::Bt709 => (0.2126, 0.0722),
+            YCbCrStandard::Bt2020 => (0.2627, 0.0593),
+        }
+    }
+}
+
+impl AsRef<str> for YCbCrStandard {
+    fn as_ref(&self) -> &str {
+        match self {
+            YCbCrStandard::Bt601 => "BT.601",
+            YCbCrStandard::Bt709 => "BT.709",
+            YCbCrStandard::Bt2020 => "BT.2020",
+        }
+    }
+}
+
+/// Range of code values used by a YCbCr encoding.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum YCbCrRange {
+    /// Every code value is used, as in JPEG
+    Full,
+    /// Studio swing with footroom and headroom, Y from 16 to 235 and CbCr from 16 to 240 at
+    /// 8 bits
+    #[default]
+    Limited,
+}
+
+impl YCbCrRange {
+    pub const ALL: [YCbCrRange; 2] = [YCbCrRange::Full, YCbCrRange::Limited];
+}
+
+impl AsRef<str> for YCbCrRange {
+    fn as_ref(&self) -> &str {
+        match self {
+            YCbCrRange::Full => "full",
+            YCbCrRange::Limited => "limited",
+        }
+    }
+}
+
+/// Luma and blue and red difference chroma of gamma encoded RGB.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct YCbCr {
+    y: f32,
+    cb: f32,
+    cr: f32,
+}
+
+impl YCbCr {
+    pub fn new(y: f32, cb: f32, cr: f32) -> Self {
+        let y = if y.is_nan() { 0. } else { y };
+        let cb = if cb.is_nan() { 0. } else { cb };
+        let cr = if cr.is_nan() { 0. } else { cr };
+        Self { y, cb, cr }
+    }
+
+    #[inline(always)]
+    /// Returns Luma in the range of 0.0 ..= 1.0
+    pub fn y(&self) -> f32 {
+        self.y
+    }
+
+    #[inline(always)]
+    /// Returns blue difference Chroma in the range of -0.5 ..= 0.5
+    pub fn cb(&self) -> f32 {
+        self.cb
+    }
+
+    #[inline(always)]
+    /// Returns red difference Chroma in the range of -0.5 ..= 0.5
+    pub fn cr(&self) -> f32 {
+        self.cr
+    }
+
+    pub fn from_rgb(rgb: Rgb, standard: YCbCrStandard) -> Self {
+        let (kr, kb) = standard.kr_kb();
+        let y = kr * rgb.r() + (1. - kr - kb) * rgb.g() + kb * rgb.b();
+        Self::new(
+            y,
+            (rgb.b() - y) / (2. * (1. - kb)),
+            (rgb.r() - y) / (2. * (1. - kr)),
+        )
+    }
+
+    /// Colors outside of the RGB cube are clipped
+    pub fn to_rgb(self, standard: YCbCrStandard) -> Rgb {
+        let (kr, kb) = standard.kr_kb();
+        let r = self.y + 2. * (1. - kr) * self.cr;
+        let b = self.y + 2. * (1. - kb) * self.cb;
+        let g = (self.y - kr * r - kb * b) / (1. - kr - kb);
+        Rgb::new(r, g, b)
+    }
+
+    /// Returns unrounded Y, Cb and Cr code values with `bits` bits per component.
+    pub fn code_values(&self, bits: u8, range: YCbCrRange) -> [f32; 3] {
+        match range {
+            YCbCrRange::Full => {
+                let max = ((1u32 << bits) - 1) as f32;
+                let offset = (1u32 << (bits - 1)) as f32;
+                [self.y * max, self.cb * max + offset, self.cr * max + offset]
+            }
+            YCbCrRange::Limited => {
+                let scale = (1u32 << (bits - 8)) as f32;
+                [
+                    (219. * self.y + 16.) * scale,
+                    (224. * self.cb + 128.) * scale,
+                    (224. * self.cr + 128.) * scale,
+                ]
+            }
+        }
+    }
+
+    /// Returns Y, Cb and Cr code values rounded and clamped to valid values of `bits` bits.
+    pub fn quantize(&self, bits: u8, range: YCbCrRange) -> [u32; 3] {
+        let max = ((1u32 << bits) - 1) as f32;
+        self.code_values(bits, range)
+            .map(|value| value.round().clamp(0., max) as u32)
+    }
+
+    pub fn from_code_values(values: [f32; 3], bits: u8, range: YCbCrRange) -> Self {
+        let [y, cb, cr] = values;
+        match range {
+            YCbCrRange::Full => {
+                let max = ((1u32 << bits) - 1) as f32;
+                let offset = (1u32 << (bits - 1)) as f32;
+                Self::new(y / max, (cb - offset) / max, (cr - offset) / max)
+            }
+            YCbCrRange::Limited => {
+                let scale = (1u32 << (bits - 8)) as f32;
+                Self::new(
+                    (y / scale - 16.) / 219.,
+                    (cb / scale - 128.) / 224.,
+                    (cr / scale - 128.) / 224.,
+                )
+            }
+        }
+    }
+}
+
+//####################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn code_values() {
+        macro_rules! test_case {
+            ($standard:expr, $bits:expr, $range:expr, Rgb: $r:expr, $g:expr, $b:expr; YCbCr: $y:expr, $cb:expr, $cr:expr) => {
+                let rgb = Rgb::new_scaled($r, $g, $b);
+                let ycbcr = YCbCr::from_rgb(rgb, $standard);
+                assert_eq!(ycbcr.quantize($bits, $range), [$y, $cb, $cr], "{ycbcr:?}");
+                let got = YCbCr::from_code_values(ycbcr.code_values($bits, $range), $bits, $range)
+                    .to_rgb($standard);
+                assert!((got.r_scaled() - $r as f32).abs() < 0.5, "{got:?}");
+                assert!((got.g_scaled() - $g as f32).abs() < 0.5, "{got:?}");
+                assert!((got.b_scaled() - $b as f32).abs() < 0.5, "{got:?}");
+            };
+        }
+        use YCbCrRange::*;
+        use YCbCrStandard::*;
+
+        test_case!(Bt709, 8, Limited, Rgb: 0, 0, 0; YCbCr: 16, 128, 128);
+        test_case!(Bt709, 8, Limited, Rgb: 255, 255, 255; YCbCr: 235, 128, 128);
+        test_case!(Bt709, 8, Limited, Rgb: 255, 0, 0; YCbCr: 63, 102, 240);
+        test_case!(Bt709, 10, Limited, Rgb: 0, 0, 255; YCbCr: 127, 960, 471);
+        test_case!(Bt601, 8, Limited, Rgb: 255, 0, 0; YCbCr: 81, 90, 240);
+        test_case!(Bt601, 8, Full, Rgb: 255, 0, 0; YCbCr: 76, 85, 255);
+        test_case!(Bt601, 8, Full, Rgb: 128, 128, 128; YCbCr: 128, 128, 128);
+        test_case!(Bt2020, 10, Limited, Rgb: 0, 255, 0; YCbCr: 658, 189, 100);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::color::Rgb;
+
+use serde::{Deserialize, Serialize};
+
+/// Red and blue luma coefficients of BT.601 shared by the analog television encodings.
+const KR: f32 = 0.299;
+const KB: f32 = 0.114;
+/// Scale factors of the PAL color difference signals.
+const U_SCALE: f32 = 0.492111;
+const V_SCALE: f32 = 0.877283;
+/// Rotation of the NTSC IQ axes relative to the UV axes.
+const IQ_ANGLE: f32 = 33. * std::f32::consts::PI / 180.;
+
+/// Luma and scaled color difference signals of analog PAL video.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Yuv {
+    y: f32,
+    u: f32,
+    v: f32,
+}
+
+impl Yuv {
+    pub fn new(y: f32, u: f32, v: f32) -> Self {
+        let y = if y.is_nan() { 0. } else { y };
+        let u = if u.is_nan() { 0. } else { u };
+        let v = if v.is_nan() { 0. } else { v };
+        Self { y, u, v }
+    }
+
+    #[inline(always)]
+    /// Returns Luma in the range of 0.0 ..= 1.0
+    pub fn y(&self) -> f32 {
+        self.y
+    }
+
+    #[inline(always)]
+    /// Returns U in the range of -0.436 ..= 0.436
+    pub fn u(&self) -> f32 {
+        self.u
+    }
+
+    #[inline(always)]
+    /// Returns V in the range of -0.615 ..= 0.615
+    pub fn v(&self) -> f32 {
+        self.v
+    }
+}
+
+/// Luma and in-phase and quadrature chroma of analog NTSC video.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Yiq {
+    y: f32,
+    i: f32,
+    q: f32,
+}
+
+impl Yiq {
+    pub fn new(y: f32, i: f32, q: f32) -> Self {
+        let y = if y.is_nan() { 0. } else { y };
+        let i = if i.is_nan() { 0. } else { i };
+        let q = if q.is_nan() { 0. } else { q };
+        Self { y, i, q }
+    }
+
+    #[inline(always)]
+    /// Returns Luma in the range of 0.0 ..= 1.0
+    pub fn y(&self) -> f32 {
+        self.y
+    }
+
+    #[inline(always)]
+    /// Returns In-phase chroma in the range of -0.596 ..= 0.596
+    pub fn i(&self) -> f32 {
+        self.i
+    }
+
+    #[inline(always)]
+    /// Returns Quadrature chroma in the range of -0.523 ..= 0.523
+    pub fn q(&self) -> f32 {
+        self.q
+    }
+}
+
+//####################################################################################################
+
+impl From<Rgb> for Yuv {
+    fn from(rgb: Rgb) -> Self {
+        let y = KR * rgb.r() + (1. - KR - KB) * rgb.g() + KB * rgb.b();
+        Yuv::new(y, U_SCALE * (rgb.b() - y), V_SCALE * (rgb.r() - y))
+    }
+}
+
+impl From<Yuv> for Rgb {
+    /// Colors outside of the RGB cube are clipped
+    fn from(color: Yuv) -> Self {
+        let r = color.y() + color.v() / V_SCALE;
+        let b = color.y() + color.u() / U_SCALE;
+        let g = (color.y() - KR * r - KB * b) / (1. - KR - KB);
+        Rgb::new(r, g, b)
+    }
+}
+
+impl From<Yuv> for Yiq {
+    fn from(color: Yuv) -> Self {
+        let (sin, cos) = IQ_ANGLE.sin_cos();
+        Yiq::new(
+            color.y(),
+            color.v() * cos - color.u() * sin,
+            color.v() * sin + color.u() * cos,
+        )
+    }
+}
+
+impl From<Yiq> for Yuv {
+    fn from(color: Yiq) -> Self {
+        let (sin, cos) = IQ_ANGLE.sin_cos();
+        Yuv::new(
+            color.y(),
+            color.q() * cos - color.i() * sin,
+            color.q() * sin + color.i() * cos,
+        )
+    }
+}
+
+impl From<Rgb> for Yiq {
+    fn from(rgb: Rgb) -> Self {
+        Yuv::from(rgb).into()
+    }
+}
+
+impl From<Yiq> for Rgb {
+    fn from(color: Yiq) -> Self {
+        Yuv::from(color).into()
+    }
+}
+
+//####################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use super::{Rgb, Yiq, Yuv};
+    #[test]
+    fn rgb_to_yuv() {
+        macro_rules! test_case {
+            (Rgb: $r:expr, $g:expr, $b:expr ;Yuv: $y:expr, $u:expr, $v:expr) => {
+                let got = Yuv::from(Rgb::new_scaled($r, $g, $b));
+                assert!((got.y() - $y).abs() < 1e-3, "{got:?}");
+                assert!((got.u() - $u).abs() < 1e-3, "{got:?}");
+                assert!((got.v() - $v).abs() < 1e-3, "{got:?}");
+                let rgb = Rgb::from(got);
+                assert!((rgb.r_scaled() - $r as f32).abs() < 0.5, "{rgb:?}");
+                assert!((rgb.g_scaled() - $g as f32).abs() < 0.5, "{rgb:?}");
+                assert!((rgb.b_scaled() - $b as f32).abs() < 0.5, "{rgb:?}");
+            };
+        }
+
+        test_case!(Rgb: 0, 0, 0; Yuv: 0., 0., 0.);
+        test_case!(Rgb: 255, 255, 255; Yuv: 1., 0., 0.);
+        test_case!(Rgb: 255, 0, 0; Yuv: 0.299, -0.147, 0.615);
+        test_case!(Rgb: 0, 0, 255; Yuv: 0.114, 0.436, -0.1);
+    }
+
+    #[test]
+    fn rgb_to_yiq() {
+        macro_rules! test_case {
+            (Rgb: $r:expr, $g:expr, $b:expr ;Yiq: $y:expr, $i:expr, $q:expr) => {
+                let got = Yiq::from(Rgb::new_scaled($r, $g, $b));
+                assert!((got.y() - $y).abs() < 1e-3, "{got:?}");
+                assert!((got.i() - $i).abs() < 1e-3, "{got:?}");
+                assert!((got.q() - $q).abs() < 1e-3, "{got:?}");
+                let rgb = Rgb::from(got);
+                assert!((rgb.r_scaled() - $r as f32).abs() < 0.5, "{rgb:?}");
+                assert!((rgb.g_scaled() - $g as f32).abs() < 0.5, "{rgb:?}");
+                assert!((rgb.b_scaled() - $b as f32).abs() < 0.5, "{rgb:?}");
+            };
+        }
+
+        test_case!(Rgb: 255, 255, 255; Yiq: 1., 0., 0.);
+        test_case!(Rgb: 255, 0, 0; Yiq: 0.299, 0.596, 0.212);
+        test_case!(Rgb: 0, 255, 0; Yiq: 0.587, -0.275, -0.523);
+        test_case!(Rgb: 0, 0, 255; Yiq: 0.114, -0.321, 0.311);
+    }
+}
@@ -2,15 +2,17 @@ mod sliders;
 
 use crate::{
     color::{
-        CIEColor, Cmyk, Color, Hsl, Hsv, Illuminant, Lab, LchAB, LchUV, Luv, Rgb, RgbWorkingSpace,
-        U8_MAX, U8_MIN, Xyz,
+        CIEColor, Cam16, Cam16Ucs, CctLocus, Cmyk, Color, DUV_RANGE, Gamut, Hct, Hsi, Hsl, Hsv,
+        Hwb, Illuminant, KELVIN_RANGE, Lab, LchAB, LchUV, Luv, Rgb, RgbWorkingSpace, Surround,
+        U8_MAX, U8_MIN, Xyz, YCBCR_BIT_DEPTHS, YCbCr, YCbCrRange, YCbCrStandard, Yiq, Yuv,
+        brightest_rgb, delta_e2000,
     },
     math,
     ui::{slider_1d, slider_2d},
 };
 use sliders::ColorSliders;
 
-use egui::{DragValue, Grid, Ui, ecolor::Hsva};
+use egui::{Color32, ComboBox, DragValue, Grid, Ui, ecolor::Hsva};
 use serde::{Deserialize, Serialize};
 use std::mem;
 
@@ -23,6 +25,23 @@ macro_rules! slider {
             $ui.label(format!("{}: ", $label));
             $ui.add(DragValue::new(&mut $it.sliders.$field));
     };
+    (cie $it:ident, $ui:ident, $field:ident, $label:literal, $range:expr, $ws:expr, $xyz_at:expr) => {
+            let xyz_at = $xyz_at;
+            let gamut = Gamut::new($ws);
+            let resp = slider_1d::color_in_gamut(
+                &mut $ui,
+                &mut $it.sliders.$field,
+                $range,
+                |v| xyz_at(v).to_rgb($ws).into(),
+                |v| gamut.contains(xyz_at(v)),
+            )
+            .on_hover_text($label);
+            if resp.changed() {
+                $it.check_for_change();
+            }
+            $ui.label(format!("{}: ", $label));
+            $ui.add(DragValue::new(&mut $it.sliders.$field));
+    };
     (int $it:ident, $ui:ident, $field:ident, $label:literal, $range:expr, $($tt:tt)+) => {
             let resp = slider_1d::color(&mut $ui, &mut $it.sliders.$field, $range, $($tt)+).on_hover_text($label);
             if resp.changed() {
@@ -46,6 +65,10 @@ pub struct ColorPicker {
     pub saved_sliders: Option<ColorSliders>,
     pub new_workspace: Option<RgbWorkingSpace>,
     pub new_illuminant: Option<Illuminant>,
+    /// CIEDE2000 difference between the last color set from CIE sliders and the color it was
+    /// mapped to if it was out of gamut
+    #[serde(skip)]
+    pub gamut_warning: Option<f32>,
 }
 
 impl Default for ColorPicker {
@@ -57,6 +80,7 @@ impl Default for ColorPicker {
             saved_sliders: None,
             new_workspace: None,
             new_illuminant: None,
+            gamut_warning: None,
         }
     }
 }
@@ -66,14 +90,37 @@ impl ColorPicker {
         let color = color.into();
         self.sliders.set_color(color);
         self.current_color = color;
+        self.gamut_warning = None;
     }
 
-    pub fn set_cie_color(&mut self, color: impl CIEColor) {
-        let color = color.to_rgb(self.sliders.rgb_working_space).into();
+    /// Sets the current color from CIE XYZ, mapping it into the working space gamut if needed.
+    pub fn set_cie_color(&mut self, xyz: Xyz) {
+        let ws = self.sliders.rgb_working_space;
+        let rgb = self.sliders.gamut_mapping.map(xyz, ws);
+        self.gamut_warning = (!xyz.in_gamut(ws)).then(|| {
+            let white = ws.reference_illuminant();
+            delta_e2000(
+                &Lab::from_xyz(xyz, white),
+                &Lab::from_xyz(Xyz::from_rgb(rgb, ws), white),
+            )
+        });
+        let color = rgb.into();
         self.sliders.set_color(color);
         self.current_color = color;
     }
 
+    /// Displays a warning when the last color set from CIE sliders was out of gamut.
+    fn gamut_badge(&self, ui: &mut Ui) {
+        if let Some(delta_e) = self.gamut_warning {
+            ui.colored_label(Color32::YELLOW, "⚠ out of gamut")
+                .on_hover_text(format!(
+                    "The selected color is outside of {} and was mapped using {} (ΔE {delta_e:.2})",
+                    self.sliders.rgb_working_space.as_ref(),
+                    self.sliders.gamut_mapping.as_ref(),
+                ));
+        }
+    }
+
     fn restore_sliders_if_saved(&mut self) {
         if let Some(saved) = mem::take(&mut self.saved_sliders) {
             self.sliders.restore(saved);
@@ -167,6 +214,97 @@ impl ColorPicker {
         }
     }
 
+    fn hwb_changed(&mut self) -> bool {
+        let hwb = self.current_color.hwb();
+        if !math::eq_f32(self.sliders.hwb_h, hwb.h_scaled())
+            || !math::eq_f32(self.sliders.hwb_w, hwb.w_scaled())
+            || !math::eq_f32(self.sliders.hwb_b, hwb.b_scaled())
+        {
+            self.set_cur_color(Rgb::from(Hwb::new(
+                self.sliders.hwb_h / 360.,
+                self.sliders.hwb_w / 100.,
+                self.sliders.hwb_b / 100.,
+            )));
+            true
+        } else {
+            false
+        }
+    }
+
+    fn hsi_changed(&mut self) -> bool {
+        let hsi = self.current_color.hsi();
+        if !math::eq_f32(self.sliders.hsi_h, hsi.h_scaled())
+            || !math::eq_f32(self.sliders.hsi_s, hsi.s_scaled())
+            || !math::eq_f32(self.sliders.hsi_i, hsi.i_scaled())
+        {
+            self.set_cur_color(Rgb::from(Hsi::new(
+                self.sliders.hsi_h / 360.,
+                self.sliders.hsi_s / 100.,
+                self.sliders.hsi_i / 100.,
+            )));
+            true
+        } else {
+            false
+        }
+    }
+
+    fn ycbcr_changed(&mut self) -> bool {
+        let (standard, bits, range) = (
+            self.sliders.ycbcr_standard,
+            self.sliders.ycbcr_bits,
+            self.sliders.ycbcr_range,
+        );
+        let [y, cb, cr] = self.current_color.ycbcr(standard).code_values(bits, range);
+        if !math::eq_f32(self.sliders.ycbcr_y, y)
+            || !math::eq_f32(self.sliders.ycbcr_cb, cb)
+            || !math::eq_f32(self.sliders.ycbcr_cr, cr)
+        {
+            let values = [
+                self.sliders.ycbcr_y,
+                self.sliders.ycbcr_cb,
+                self.sliders.ycbcr_cr,
+            ];
+            self.set_cur_color(YCbCr::from_code_values(values, bits, range).to_rgb(standard));
+            true
+        } else {
+            false
+        }
+    }
+
+    fn yuv_changed(&mut self) -> bool {
+        let yuv = self.current_color.yuv();
+        if !math::eq_f32(self.sliders.yuv_y, yuv.y() * 100.)
+            || !math::eq_f32(self.sliders.yuv_u, yuv.u() * 100.)
+            || !math::eq_f32(self.sliders.yuv_v, yuv.v() * 100.)
+        {
+            self.set_cur_color(Rgb::from(Yuv::new(
+                self.sliders.yuv_y / 100.,
+                self.sliders.yuv_u / 100.,
+                self.sliders.yuv_v / 100.,
+            )));
+            true
+        } else {
+            false
+        }
+    }
+
+    fn yiq_changed(&mut self) -> bool {
+        let yiq = self.current_color.yiq();
+        if !math::eq_f32(self.sliders.yiq_y, yiq.y() * 100.)
+            || !math::eq_f32(self.sliders.yiq_i, yiq.i() * 100.)
+            || !math::eq_f32(self.sliders.yiq_q, yiq.q() * 100.)
+        {
+            self.set_cur_color(Rgb::from(Yiq::new(
+                self.sliders.yiq_y / 100.,
+                self.sliders.yiq_i / 100.,
+                self.sliders.yiq_q / 100.,
+            )));
+            true
+        } else {
+            false
+        }
+    }
+
     fn luv_changed(&mut self) -> bool {
         let luv = Luv::from(self.current_color.xyz(self.sliders.rgb_working_space));
         if !math::eq_f32(self.sliders.luv_l, luv.l())
@@ -244,6 +382,66 @@ impl ColorPicker {
         }
     }
 
+    fn cam16_changed(&mut self) -> bool {
+        let ws = self.sliders.rgb_working_space;
+        let conditions = self.sliders.viewing_conditions;
+        let cam = self.current_color.cam16(ws, &conditions);
+        if !math::eq_f32(self.sliders.cam16_j, cam.j())
+            || !math::eq_f32(self.sliders.cam16_c, cam.c())
+            || !math::eq_f32(self.sliders.cam16_h, cam.h())
+        {
+            self.set_cie_color(
+                Cam16::new(
+                    self.sliders.cam16_j,
+                    self.sliders.cam16_c,
+                    self.sliders.cam16_h,
+                )
+                .to_xyz(ws.reference_illuminant(), &conditions),
+            );
+            true
+        } else {
+            false
+        }
+    }
+
+    fn cam16_ucs_changed(&mut self) -> bool {
+        let ws = self.sliders.rgb_working_space;
+        let conditions = self.sliders.viewing_conditions;
+        let ucs = self.current_color.cam16_ucs(ws, &conditions);
+        if !math::eq_f32(self.sliders.cam16_ucs_j, ucs.j())
+            || !math::eq_f32(self.sliders.cam16_ucs_a, ucs.a())
+            || !math::eq_f32(self.sliders.cam16_ucs_b, ucs.b())
+        {
+            self.set_cie_color(
+                Cam16Ucs::new(
+                    self.sliders.cam16_ucs_j,
+                    self.sliders.cam16_ucs_a,
+                    self.sliders.cam16_ucs_b,
+                )
+                .to_xyz(ws.reference_illuminant(), &conditions),
+            );
+            true
+        } else {
+            false
+        }
+    }
+
+    fn hct_changed(&mut self) -> bool {
+        let ws = self.sliders.rgb_working_space;
+        let hct = self.current_color.hct(ws);
+        if !math::eq_f32(self.sliders.hct_h, hct.h())
+            || !math::eq_f32(self.sliders.hct_c, hct.c())
+            || !math::eq_f32(self.sliders.hct_t, hct.t())
+        {
+            self.set_cie_color(
+                Hct::new(self.sliders.hct_h, self.sliders.hct_c, self.sliders.hct_t).to_xyz(ws),
+            );
+            true
+        } else {
+            false
+        }
+    }
+
     fn workspace_changed(&mut self) -> bool {
         if let Some(ws) = mem::take(&mut self.new_workspace) {
             self.sliders.rgb_working_space = ws;
@@ -275,10 +473,18 @@ impl ColorPicker {
             || self.cmyk_changed()
             || self.hsv_changed()
             || self.hsl_changed()
+            || self.hwb_changed()
+            || self.hsi_changed()
+            || self.ycbcr_changed()
+            || self.yuv_changed()
+            || self.yiq_changed()
             || self.luv_changed()
             || self.lch_uv_changed()
             || self.lab_changed()
             || self.lch_ab_changed()
+            || self.cam16_changed()
+            || self.cam16_ucs_changed()
+            || self.hct_changed()
     }
 
     pub fn check_for_change(&mut self) {
@@ -400,28 +606,69 @@ impl ColorPicker {
             });
     }
 
+    pub fn hwb_sliders(&mut self, ui: &mut Ui) {
+        let opaque = self.current_color.hwb();
+        Grid::new("HWB sliders")
+            .spacing((8., 8.))
+            .show(ui, |mut ui| {
+                slider!(self, ui, hwb_h, "hue", 0. ..=360., |mut h| {
+                    h /= 360.;
+                    Rgb::from(Hwb::new(h, opaque.w(), opaque.b())).into()
+                });
+                ui.end_row();
+                slider!(self, ui, hwb_w, "whiteness", 0. ..=100., |mut w| {
+                    w /= 100.;
+                    Rgb::from(Hwb::new(opaque.h(), w, opaque.b())).into()
+                });
+                ui.end_row();
+                slider!(self, ui, hwb_b, "blackness", 0. ..=100., |mut b| {
+                    b /= 100.;
+                    Rgb::from(Hwb::new(opaque.h(), opaque.w(), b)).into()
+                });
+                ui.end_row();
+            });
+    }
+
+    pub fn hsi_sliders(&mut self, ui: &mut Ui) {
+        let opaque = self.current_color.hsi();
+        Grid::new("HSI sliders")
+            .spacing((8., 8.))
+            .show(ui, |mut ui| {
+                slider!(self, ui, hsi_h, "hue", 0. ..=360., |mut h| {
+                    h /= 360.;
+                    Rgb::from(Hsi::new(h, opaque.s(), opaque.i())).into()
+                });
+                ui.end_row();
+                slider!(self, ui, hsi_s, "saturation", 0. ..=100., |mut s| {
+                    s /= 100.;
+                    Rgb::from(Hsi::new(opaque.h(), s, opaque.i())).into()
+                });
+                ui.end_row();
+                slider!(self, ui, hsi_i, "intensity", 0. ..=100., |mut i| {
+                    i /= 100.;
+                    Rgb::from(Hsi::new(opaque.h(), opaque.s(), i)).into()
+                });
+                ui.end_row();
+            });
+    }
+
     pub fn luv_sliders(&mut self, ui: &mut Ui) {
         let ws = self.sliders.rgb_working_space;
         let opaque = self.current_color.luv(ws);
+        self.gamut_badge(ui);
         Grid::new("Luv sliders")
             .spacing((8., 8.))
             .show(ui, |mut ui| {
-                slider!(self, ui, luv_l, "light", 0. ..=100., |l| {
+                slider!(cie self, ui, luv_l, "light", 0. ..=100., ws, |l| {
                     Xyz::from(Luv::new(l, opaque.u(), opaque.v()))
-                        .to_rgb(ws)
-                        .into()
                 });
                 ui.end_row();
-                slider!(self, ui, luv_u, "u", -134. ..=220., |u| {
+                slider!(cie self, ui, luv_u, "u", -134. ..=220., ws, |u| {
                     Xyz::from(Luv::new(opaque.l(), u, opaque.v()))
-                        .to_rgb(ws)
-                        .into()
                 });
                 ui.end_row();
-                slider!(self, ui, luv_v, "v", -140. ..=122., |v| {
+                slider!(cie self, ui, luv_v, "v", -140. ..=122., ws, |v| {
                     Xyz::from(Luv::new(opaque.l(), opaque.u(), v))
-                        .to_rgb(ws)
-                        .into()
                 });
                 ui.end_row();
             });
@@ -430,25 +677,20 @@ impl ColorPicker {
     pub fn lch_uv_sliders(&mut self, ui: &mut Ui) {
         let ws = self.sliders.rgb_working_space;
         let opaque = self.current_color.lch_uv(ws);
+        self.gamut_badge(ui);
         Grid::new("LCH(uv) sliders")
             .spacing((8., 8.))
             .show(ui, |mut ui| {
-                slider!(self, ui, lch_uv_l, "light", 0. ..=100., |l| {
+                slider!(cie self, ui, lch_uv_l, "light", 0. ..=100., ws, |l| {
                     Xyz::from(LchUV::new(l, opaque.c(), opaque.h()))
-                        .to_rgb(ws)
-                        .into()
                 });
                 ui.end_row();
-                slider!(self, ui, lch_uv_c, "c", 0. ..=270., |c| {
+                slider!(cie self, ui, lch_uv_c, "c", 0. ..=270., ws, |c| {
                     Xyz::from(LchUV::new(opaque.l(), c, opaque.h()))
-                        .to_rgb(ws)
-                        .into()
                 });
                 ui.end_row();
-                slider!(self, ui, lch_uv_h, "h", 0. ..=360., |h| {
+                slider!(cie self, ui, lch_uv_h, "h", 0. ..=360., ws, |h| {
                     Xyz::from(LchUV::new(opaque.l(), opaque.c(), h))
-                        .to_rgb(ws)
-                        .into()
                 });
                 ui.end_row();
             });
@@ -461,31 +703,37 @@ impl ColorPicker {
             self.current_color
                 .lab(ws, ref_white, self.sliders.chromatic_adaptation_method);
 
+        self.gamut_badge(ui);
         Grid::new("Lab sliders")
             .spacing((8., 8.))
             .show(ui, |mut ui| {
-                slider!(self, ui, lab_l, "light", 0. ..=100., |l| {
-                    Lab::new(l, opaque.a(), opaque.b())
-                        .to_xyz(ref_white)
-                        .to_rgb(ws)
-                        .into()
+                slider!(cie self, ui, lab_l, "light", 0. ..=100., ws, |l| {
+                    Lab::new(l, opaque.a(), opaque.b()).to_xyz(ref_white)
                 });
                 ui.end_row();
-                slider!(self, ui, lab_a, "a", -128. ..=127., |a| {
-                    Lab::new(opaque.l(), a, opaque.b())
-                        .to_xyz(ref_white)
-                        .to_rgb(ws)
-                        .into()
+                slider!(cie self, ui, lab_a, "a", -128. ..=127., ws, |a| {
+                    Lab::new(opaque.l(), a, opaque.b()).to_xyz(ref_white)
                 });
                 ui.end_row();
-                slider!(self, ui, lab_b, "b", -128. ..=127., |b| {
-                    Lab::new(opaque.l(), opaque.a(), b)
-                        .to_xyz(ref_white)
-                        .to_rgb(ws)
-                        .into()
+                slider!(cie self, ui, lab_b, "b", -128. ..=127., ws, |b| {
+                    Lab::new(opaque.l(), opaque.a(), b).to_xyz(ref_white)
                 });
                 ui.end_row();
             });
+        let xyz_at = |a, b| Lab::new(opaque.l(), a, b).to_xyz(ref_white);
+        let gamut = Gamut::new(ws);
+        let resp = slider_2d::color_in_gamut(
+            ui,
+            &mut self.sliders.lab_a,
+            &mut self.sliders.lab_b,
+            -128.0..=127.,
+            -128.0..=127.,
+            |a, b| xyz_at(a, b).to_rgb(ws).into(),
+            |a, b| gamut.contains(xyz_at(a, b)),
+        );
+        if resp.changed() {
+            self.check_for_change();
+        }
     }
 
     pub fn lch_ab_sliders(&mut self, ui: &mut Ui) {
@@ -496,28 +744,332 @@ impl ColorPicker {
             self.sliders.illuminant,
             self.sliders.chromatic_adaptation_method,
         );
+        self.gamut_badge(ui);
         Grid::new("LCH(ab) sliders")
             .spacing((8., 8.))
             .show(ui, |mut ui| {
-                slider!(self, ui, lch_ab_l, "light", 0. ..=100., |l| {
-                    LchAB::new(l, opaque.c(), opaque.h())
-                        .to_xyz(ref_white)
-                        .to_rgb(ws)
-                        .into()
+                slider!(cie self, ui, lch_ab_l, "light", 0. ..=100., ws, |l| {
+                    LchAB::new(l, opaque.c(), opaque.h()).to_xyz(ref_white)
+                });
+                ui.end_row();
+                slider!(cie self, ui, lch_ab_c, "c", 0. ..=270., ws, |c| {
+                    LchAB::new(opaque.l(), c, opaque.h()).to_xyz(ref_white)
+                });
+                ui.end_row();
+                slider!(cie self, ui, lch_ab_h, "h", 0. ..=360., ws, |h| {
+                    LchAB::new(opaque.l(), opaque.c(), h).to_xyz(ref_white)
+                });
+                ui.end_row();
+            });
+        let xyz_at = |c, l| LchAB::new(l, c, opaque.h()).to_xyz(ref_white);
+        let gamut = Gamut::new(ws);
+        let resp = slider_2d::color_in_gamut(
+            ui,
+            &mut self.sliders.lch_ab_c,
+            &mut self.sliders.lch_ab_l,
+            0.0..=270.,
+            0.0..=100.,
+            |c, l| xyz_at(c, l).to_rgb(ws).into(),
+            |c, l| gamut.contains(xyz_at(c, l)),
+        );
+        if resp.changed() {
+            self.check_for_change();
+        }
+    }
+
+    /// Displays the CAM16 viewing conditions, recomputing the sliders when they change.
+    fn viewing_conditions_ui(&mut self, ui: &mut Ui) {
+        let conditions = &mut self.sliders.viewing_conditions;
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("adapting luminance: ");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut conditions.adapting_luminance)
+                        .range(0.0..=10000.)
+                        .suffix(" cd/m²"),
+                )
+                .changed();
+            ui.label("background: ");
+            changed |= ui
+                .add(
+                    DragValue::new(&mut conditions.background_luminance)
+                        .range(1.0..=100.)
+                        .speed(0.1),
+                )
+                .on_hover_text("Relative luminance Y of the background")
+                .changed();
+            ComboBox::from_id_salt("cam16_surround")
+                .selected_text(conditions.surround.as_ref())
+                .show_ui(ui, |ui| {
+                    for it in Surround::ALL {
+                        changed |= ui
+                            .selectable_value(&mut conditions.surround, it, it.as_ref())
+                            .changed();
+                    }
+                });
+            ui.label("surround");
+        });
+        if changed {
+            self.sliders.set_color(self.current_color);
+        }
+    }
+
+    pub fn cam16_sliders(&mut self, ui: &mut Ui) {
+        self.viewing_conditions_ui(ui);
+        let ws = self.sliders.rgb_working_space;
+        let white = ws.reference_illuminant();
+        let conditions = self.sliders.viewing_conditions;
+        let opaque = self.current_color.cam16(ws, &conditions);
+        self.gamut_badge(ui);
+        Grid::new("CAM16 sliders")
+            .spacing((8., 8.))
+            .show(ui, |mut ui| {
+                slider!(cie self, ui, cam16_j, "lightness", 0. ..=100., ws, |j| {
+                    Cam16::new(j, opaque.c(), opaque.h()).to_xyz(white, &conditions)
+                });
+                ui.end_row();
+                slider!(cie self, ui, cam16_c, "chroma", 0. ..=120., ws, |c| {
+                    Cam16::new(opaque.j(), c, opaque.h()).to_xyz(white, &conditions)
+                });
+                ui.end_row();
+                slider!(cie self, ui, cam16_h, "hue", 0. ..=360., ws, |h| {
+                    Cam16::new(opaque.j(), opaque.c(), h).to_xyz(white, &conditions)
+                });
+                ui.end_row();
+            });
+    }
+
+    pub fn cam16_ucs_sliders(&mut self, ui: &mut Ui) {
+        self.viewing_conditions_ui(ui);
+        let ws = self.sliders.rgb_working_space;
+        let white = ws.reference_illuminant();
+        let conditions = self.sliders.viewing_conditions;
+        let opaque = self.current_color.cam16_ucs(ws, &conditions);
+        self.gamut_badge(ui);
+        Grid::new("CAM16-UCS sliders")
+            .spacing((8., 8.))
+            .show(ui, |mut ui| {
+                slider!(cie self, ui, cam16_ucs_j, "J'", 0. ..=100., ws, |j| {
+                    Cam16Ucs::new(j, opaque.a(), opaque.b()).to_xyz(white, &conditions)
+                });
+                ui.end_row();
+                slider!(cie self, ui, cam16_ucs_a, "a'", -50. ..=50., ws, |a| {
+                    Cam16Ucs::new(opaque.j(), a, opaque.b()).to_xyz(white, &conditions)
+                });
+                ui.end_row();
+                slider!(cie self, ui, cam16_ucs_b, "b'", -50. ..=50., ws, |b| {
+                    Cam16Ucs::new(opaque.j(), opaque.a(), b).to_xyz(white, &conditions)
+                });
+                ui.end_row();
+            });
+    }
+
+    pub fn hct_sliders(&mut self, ui: &mut Ui) {
+        let ws = self.sliders.rgb_working_space;
+        let opaque = self.current_color.hct(ws);
+        self.gamut_badge(ui);
+        Grid::new("HCT sliders")
+            .spacing((8., 8.))
+            .show(ui, |mut ui| {
+                slider!(cie self, ui, hct_h, "hue", 0. ..=360., ws, |h| {
+                    Hct::new(h, opaque.c(), opaque.t()).to_xyz(ws)
+                });
+                ui.end_row();
+                slider!(cie self, ui, hct_c, "chroma", 0. ..=120., ws, |c| {
+                    Hct::new(opaque.h(), c, opaque.t()).to_xyz(ws)
+                });
+                ui.end_row();
+                slider!(cie self, ui, hct_t, "tone", 0. ..=100., ws, |t| {
+                    Hct::new(opaque.h(), opaque.c(), t).to_xyz(ws)
+                });
+                ui.end_row();
+            });
+    }
+
+    /// Sets the current color from the temperature sliders keeping their values.
+    fn set_kelvin_color(&mut self) {
+        let (kelvin, duv) = (self.sliders.kelvin, self.sliders.duv);
+        let (x, y) = self.sliders.cct_locus.xy(kelvin, duv);
+        self.set_cur_color(brightest_rgb(x, y, self.sliders.rgb_working_space));
+        self.sliders.kelvin = kelvin;
+        self.sliders.duv = duv;
+    }
+
+    pub fn kelvin_sliders(&mut self, ui: &mut Ui) {
+        let ws = self.sliders.rgb_working_space;
+        let xyz = self.current_color.xyz(ws);
+        ui.horizontal(|ui| {
+            match xyz.cct() {
+                Some(cct) => ui.label(format!("CCT {:.0} K, Duv {:.4}", cct.kelvin, cct.duv)),
+                None => ui.label("no correlated color temperature"),
+            }
+            .on_hover_text("Correlated color temperature by Ohno's method and the distance from the Planckian locus");
+            if let Some(kelvin) = xyz.cct_mccamy() {
+                ui.weak(format!("McCamy {kelvin:.0} K"))
+                    .on_hover_text("McCamy's approximation, accurate only close to the Planckian locus");
+            }
+        });
+
+        let locus = self.sliders.cct_locus;
+        let mut changed = false;
+        Grid::new("Kelvin sliders")
+            .spacing((8., 8.))
+            .show(ui, |ui| {
+                ComboBox::from_id_salt("cct_locus")
+                    .selected_text(locus.as_ref())
+                    .show_ui(ui, |ui| {
+                        for it in CctLocus::ALL {
+                            changed |= ui
+                                .selectable_value(&mut self.sliders.cct_locus, it, it.as_ref())
+                                .changed();
+                        }
+                    });
+                ui.label("locus");
+                ui.end_row();
+
+                let duv = self.sliders.duv;
+                changed |= slider_1d::color(ui, &mut self.sliders.kelvin, KELVIN_RANGE, |k| {
+                    let (x, y) = locus.xy(k, duv);
+                    brightest_rgb(x, y, ws).into()
+                })
+                .on_hover_text("temperature")
+                .changed();
+                ui.label("temperature: ");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut self.sliders.kelvin)
+                            .range(KELVIN_RANGE)
+                            .suffix(" K"),
+                    )
+                    .changed();
+                ui.end_row();
+
+                let kelvin = self.sliders.kelvin;
+                changed |= slider_1d::color(ui, &mut self.sliders.duv, DUV_RANGE, |d| {
+                    let (x, y) = locus.xy(kelvin, d);
+                    brightest_rgb(x, y, ws).into()
+                })
+                .on_hover_text("Duv")
+                .changed();
+                ui.label("Duv: ");
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut self.sliders.duv)
+                            .range(DUV_RANGE)
+                            .speed(0.0001)
+                            .fixed_decimals(4),
+                    )
+                    .changed();
+                ui.end_row();
+            });
+        if changed {
+            self.set_kelvin_color();
+        }
+    }
+
+    pub fn ycbcr_sliders(&mut self, ui: &mut Ui) {
+        let mut encoding_changed = false;
+        ui.horizontal(|ui| {
+            ComboBox::from_id_salt("ycbcr_standard")
+                .selected_text(self.sliders.ycbcr_standard.as_ref())
+                .show_ui(ui, |ui| {
+                    for it in YCbCrStandard::ALL {
+                        encoding_changed |= ui
+                            .selectable_value(&mut self.sliders.ycbcr_standard, it, it.as_ref())
+                            .changed();
+                    }
+                });
+            ComboBox::from_id_salt("ycbcr_range")
+                .selected_text(self.sliders.ycbcr_range.as_ref())
+                .show_ui(ui, |ui| {
+                    for it in YCbCrRange::ALL {
+                        encoding_changed |= ui
+                            .selectable_value(&mut self.sliders.ycbcr_range, it, it.as_ref())
+                            .changed();
+                    }
+                });
+            ComboBox::from_id_salt("ycbcr_bits")
+                .selected_text(format!("{} bit", self.sliders.ycbcr_bits))
+                .show_ui(ui, |ui| {
+                    for it in YCBCR_BIT_DEPTHS {
+                        encoding_changed |= ui
+                            .selectable_value(&mut self.sliders.ycbcr_bits, it, format!("{it} bit"))
+                            .changed();
+                    }
+                });
+        });
+        if encoding_changed {
+            self.sliders.set_color(self.current_color);
+        }
+
+        let (standard, bits, range) = (
+            self.sliders.ycbcr_standard,
+            self.sliders.ycbcr_bits,
+            self.sliders.ycbcr_range,
+        );
+        let [y, cb, cr] = self.current_color.ycbcr(standard).code_values(bits, range);
+        let color_at = |values| YCbCr::from_code_values(values, bits, range).to_rgb(standard);
+        let max = ((1u32 << bits) - 1) as f32;
+        Grid::new("YCbCr sliders")
+            .spacing((8., 8.))
+            .show(ui, |mut ui| {
+                slider!(int self, ui, ycbcr_y, "Y'", 0. ..=max, |y| {
+                    color_at([y, cb, cr]).into()
+                });
+                ui.end_row();
+                slider!(int self, ui, ycbcr_cb, "Cb", 0. ..=max, |cb| {
+                    color_at([y, cb, cr]).into()
+                });
+                ui.end_row();
+                slider!(int self, ui, ycbcr_cr, "Cr", 0. ..=max, |cr| {
+                    color_at([y, cb, cr]).into()
+                });
+                ui.end_row();
+            });
+    }
+
+    pub fn yuv_sliders(&mut self, ui: &mut Ui) {
+        let opaque = self.current_color.yuv();
+        Grid::new("YUV sliders")
+            .spacing((8., 8.))
+            .show(ui, |mut ui| {
+                slider!(self, ui, yuv_y, "Y'", 0. ..=100., |mut y| {
+                    y /= 100.;
+                    Rgb::from(Yuv::new(y, opaque.u(), opaque.v())).into()
+                });
+                ui.end_row();
+                slider!(self, ui, yuv_u, "U", -43.6..=43.6, |mut u| {
+                    u /= 100.;
+                    Rgb::from(Yuv::new(opaque.y(), u, opaque.v())).into()
+                });
+                ui.end_row();
+                slider!(self, ui, yuv_v, "V", -61.5..=61.5, |mut v| {
+                    v /= 100.;
+                    Rgb::from(Yuv::new(opaque.y(), opaque.u(), v)).into()
+                });
+                ui.end_row();
+            });
+    }
+
+    pub fn yiq_sliders(&mut self, ui: &mut Ui) {
+        let opaque = self.current_color.yiq();
+        Grid::new("YIQ sliders")
+            .spacing((8., 8.))
+            .show(ui, |mut ui| {
+                slider!(self, ui, yiq_y, "Y'", 0. ..=100., |mut y| {
+                    y /= 100.;
+                    Rgb::from(Yiq::new(y, opaque.i(), opaque.q())).into()
                 });
                 ui.end_row();
-                slider!(self, ui, lch_ab_c, "c", 0. ..=270., |c| {
-                    LchAB::new(opaque.l(), c, opaque.h())
-                        .to_xyz(ref_white)
-                        .to_rgb(ws)
-                        .into()
+                slider!(self, ui, yiq_i, "I", -59.6..=59.6, |mut i| {
+                    i /= 100.;
+                    Rgb::from(Yiq::new(opaque.y(), i, opaque.q())).into()
                 });
                 ui.end_row();
-                slider!(self, ui, lch_ab_h, "h", 0. ..=360., |h| {
-                    LchAB::new(opaque.l(), opaque.c(), h)
-                        .to_xyz(ref_white)
-                        .to_rgb(ws)
-                        .into()
+                slider!(self, ui, yiq_q, "Q", -52.3..=52.3, |mut q| {
+                    q /= 100.;
+                    Rgb::from(Yiq::new(opaque.y(), opaque.i(), q)).into()
                 });
                 ui.end_row();
             });
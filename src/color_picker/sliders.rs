@@ -1,4 +1,7 @@
-use crate::color::{ChromaticAdaptationMethod, Color, Illuminant, RgbWorkingSpace};
+use crate::color::{
+    CctLocus, ChromaticAdaptationMethod, Color, DUV_RANGE, GamutMapping, Illuminant,
+    RgbWorkingSpace, ViewingConditions, YCbCrRange, YCbCrStandard,
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -7,6 +10,8 @@ pub struct ColorSliders {
     pub rgb_working_space: RgbWorkingSpace,
     pub illuminant: Illuminant,
     pub chromatic_adaptation_method: ChromaticAdaptationMethod,
+    #[serde(default)]
+    pub gamut_mapping: GamutMapping,
     pub r: f32,
     pub g: f32,
     pub b: f32,
@@ -20,6 +25,18 @@ pub struct ColorSliders {
     pub hsl_h: f32,
     pub hsl_s: f32,
     pub hsl_l: f32,
+    #[serde(default)]
+    pub hwb_h: f32,
+    #[serde(default)]
+    pub hwb_w: f32,
+    #[serde(default)]
+    pub hwb_b: f32,
+    #[serde(default)]
+    pub hsi_h: f32,
+    #[serde(default)]
+    pub hsi_s: f32,
+    #[serde(default)]
+    pub hsi_i: f32,
     pub luv_l: f32,
     pub luv_u: f32,
     pub luv_v: f32,
@@ -32,6 +49,66 @@ pub struct ColorSliders {
     pub lch_ab_l: f32,
     pub lch_ab_c: f32,
     pub lch_ab_h: f32,
+    #[serde(default)]
+    pub viewing_conditions: ViewingConditions,
+    #[serde(default)]
+    pub cam16_j: f32,
+    #[serde(default)]
+    pub cam16_c: f32,
+    #[serde(default)]
+    pub cam16_h: f32,
+    #[serde(default)]
+    pub cam16_ucs_j: f32,
+    #[serde(default)]
+    pub cam16_ucs_a: f32,
+    #[serde(default)]
+    pub cam16_ucs_b: f32,
+    #[serde(default)]
+    pub hct_h: f32,
+    #[serde(default)]
+    pub hct_c: f32,
+    #[serde(default)]
+    pub hct_t: f32,
+    #[serde(default = "default_kelvin")]
+    pub kelvin: f32,
+    #[serde(default)]
+    pub duv: f32,
+    #[serde(default)]
+    pub cct_locus: CctLocus,
+    /// YCbCr code values with `ycbcr_bits` bits per component
+    #[serde(default)]
+    pub ycbcr_y: f32,
+    #[serde(default)]
+    pub ycbcr_cb: f32,
+    #[serde(default)]
+    pub ycbcr_cr: f32,
+    #[serde(default)]
+    pub ycbcr_standard: YCbCrStandard,
+    #[serde(default)]
+    pub ycbcr_range: YCbCrRange,
+    #[serde(default = "default_ycbcr_bits")]
+    pub ycbcr_bits: u8,
+    /// YUV and YIQ components scaled by 100
+    #[serde(default)]
+    pub yuv_y: f32,
+    #[serde(default)]
+    pub yuv_u: f32,
+    #[serde(default)]
+    pub yuv_v: f32,
+    #[serde(default)]
+    pub yiq_y: f32,
+    #[serde(default)]
+    pub yiq_i: f32,
+    #[serde(default)]
+    pub yiq_q: f32,
+}
+
+fn default_kelvin() -> f32 {
+    6500.
+}
+
+fn default_ycbcr_bits() -> u8 {
+    8
 }
 
 impl Default for ColorSliders {
@@ -41,6 +118,7 @@ impl Default for ColorSliders {
             rgb_working_space: ws,
             illuminant: ws.reference_illuminant(),
             chromatic_adaptation_method: ChromaticAdaptationMethod::default(),
+            gamut_mapping: GamutMapping::default(),
             r: 0.,
             g: 0.,
             b: 0.,
@@ -54,6 +132,12 @@ impl Default for ColorSliders {
             hsl_h: 0.,
             hsl_s: 0.,
             hsl_l: 0.,
+            hwb_h: 0.,
+            hwb_w: 0.,
+            hwb_b: 100.,
+            hsi_h: 0.,
+            hsi_s: 0.,
+            hsi_i: 0.,
             luv_l: 0.,
             luv_u: 0.,
             luv_v: 0.,
@@ -66,6 +150,31 @@ impl Default for ColorSliders {
             lch_ab_l: 0.,
             lch_ab_c: 0.,
             lch_ab_h: 0.,
+            viewing_conditions: ViewingConditions::default(),
+            cam16_j: 0.,
+            cam16_c: 0.,
+            cam16_h: 0.,
+            cam16_ucs_j: 0.,
+            cam16_ucs_a: 0.,
+            cam16_ucs_b: 0.,
+            hct_h: 0.,
+            hct_c: 0.,
+            hct_t: 0.,
+            kelvin: default_kelvin(),
+            duv: 0.,
+            cct_locus: CctLocus::default(),
+            ycbcr_y: 16.,
+            ycbcr_cb: 128.,
+            ycbcr_cr: 128.,
+            ycbcr_standard: YCbCrStandard::default(),
+            ycbcr_range: YCbCrRange::default(),
+            ycbcr_bits: default_ycbcr_bits(),
+            yuv_y: 0.,
+            yuv_u: 0.,
+            yuv_v: 0.,
+            yiq_y: 0.,
+            yiq_i: 0.,
+            yiq_q: 0.,
         }
     }
 }
@@ -89,6 +198,25 @@ impl ColorSliders {
         self.hsl_h = hsl.h_scaled();
         self.hsl_s = hsl.s_scaled();
         self.hsl_l = hsl.l_scaled();
+        let hwb = color.hwb();
+        self.hwb_h = hwb.h_scaled();
+        self.hwb_w = hwb.w_scaled();
+        self.hwb_b = hwb.b_scaled();
+        let hsi = color.hsi();
+        self.hsi_h = hsi.h_scaled();
+        self.hsi_s = hsi.s_scaled();
+        self.hsi_i = hsi.i_scaled();
+        [self.ycbcr_y, self.ycbcr_cb, self.ycbcr_cr] = color
+            .ycbcr(self.ycbcr_standard)
+            .code_values(self.ycbcr_bits, self.ycbcr_range);
+        let yuv = color.yuv();
+        self.yuv_y = yuv.y() * 100.;
+        self.yuv_u = yuv.u() * 100.;
+        self.yuv_v = yuv.v() * 100.;
+        let yiq = color.yiq();
+        self.yiq_y = yiq.y() * 100.;
+        self.yiq_i = yiq.i() * 100.;
+        self.yiq_q = yiq.q() * 100.;
         let luv = color.luv(self.rgb_working_space);
         self.luv_l = luv.l();
         self.luv_u = luv.u();
@@ -113,6 +241,25 @@ impl ColorSliders {
         self.lch_ab_l = lch_ab.l();
         self.lch_ab_c = lch_ab.c();
         self.lch_ab_h = lch_ab.h();
+        let cam16 = color.cam16(self.rgb_working_space, &self.viewing_conditions);
+        self.cam16_j = cam16.j();
+        self.cam16_c = cam16.c();
+        self.cam16_h = cam16.h();
+        let cam16_ucs = color.cam16_ucs(self.rgb_working_space, &self.viewing_conditions);
+        self.cam16_ucs_j = cam16_ucs.j();
+        self.cam16_ucs_a = cam16_ucs.a();
+        self.cam16_ucs_b = cam16_ucs.b();
+        let hct = color.hct(self.rgb_working_space);
+        self.hct_h = hct.h();
+        self.hct_c = hct.c();
+        self.hct_t = hct.t();
+        // Keep the temperature sliders when the color is far from the Planckian locus
+        if let Some(cct) = color.xyz(self.rgb_working_space).cct()
+            && DUV_RANGE.contains(&cct.duv)
+        {
+            self.kelvin = cct.kelvin;
+            self.duv = cct.duv;
+        }
     }
 
     pub fn restore(&mut self, other: Self) {
@@ -129,6 +276,12 @@ impl ColorSliders {
         self.hsl_h = other.hsl_h;
         self.hsl_s = other.hsl_s;
         self.hsl_l = other.hsl_l;
+        self.hwb_h = other.hwb_h;
+        self.hwb_w = other.hwb_w;
+        self.hwb_b = other.hwb_b;
+        self.hsi_h = other.hsi_h;
+        self.hsi_s = other.hsi_s;
+        self.hsi_i = other.hsi_i;
         self.luv_l = other.luv_l;
         self.luv_u = other.luv_u;
         self.luv_v = other.luv_v;
@@ -141,5 +294,25 @@ impl ColorSliders {
         self.lch_ab_l = other.lch_ab_l;
         self.lch_ab_c = other.lch_ab_c;
         self.lch_ab_h = other.lch_ab_h;
+        self.cam16_j = other.cam16_j;
+        self.cam16_c = other.cam16_c;
+        self.cam16_h = other.cam16_h;
+        self.cam16_ucs_j = other.cam16_ucs_j;
+        self.cam16_ucs_a = other.cam16_ucs_a;
+        self.cam16_ucs_b = other.cam16_ucs_b;
+        self.hct_h = other.hct_h;
+        self.hct_c = other.hct_c;
+        self.hct_t = other.hct_t;
+        self.kelvin = other.kelvin;
+        self.duv = other.duv;
+        self.ycbcr_y = other.ycbcr_y;
+        self.ycbcr_cb = other.ycbcr_cb;
+        self.ycbcr_cr = other.ycbcr_cr;
+        self.yuv_y = other.yuv_y;
+        self.yuv_u = other.yuv_u;
+        self.yuv_v = other.yuv_v;
+        self.yiq_y = other.yiq_y;
+        self.yiq_i = other.yiq_i;
+        self.yiq_q = other.yiq_q;
     }
 }
@@ -1,8 +1,12 @@
 use crate::{
     app::{CentralPanelTab, DARK_VISUALS},
-    color::{Color, ColorFormat, Palettes},
+    color::{
+        Color, ColorFormat, ColorGradient, DEFAULT_GROUP_THRESHOLD, IccProfile, Palettes,
+        PickHistory,
+    },
     color_picker::ColorPicker,
     error::append_global_error,
+    history::{Snapshot, UndoHistory},
     render::{TextureAllocator, TextureManager},
     screen_size::ScreenSize,
     settings,
@@ -19,8 +23,11 @@ pub struct AppCtx {
     pub picker: ColorPicker,
 
     pub palettes: Palettes,
+    pub pick_history: PickHistory,
     pub palettes_tab_color_size: f32,
     pub palettes_tab_display_label: bool,
+    pub palettes_group_threshold: f32,
+    pub gradient: ColorGradient,
 
     pub screen_size: ScreenSize,
     /// Color under cursor
@@ -28,6 +35,12 @@ pub struct AppCtx {
     pub central_panel_tab: CentralPanelTab,
 
     pub show_zoom_window: bool,
+
+    #[serde(skip)]
+    pub history: UndoHistory,
+    /// Loaded ICC profile of the display
+    #[serde(skip)]
+    pub display_profile: Option<IccProfile>,
 }
 
 impl Default for AppCtx {
@@ -38,14 +51,20 @@ impl Default for AppCtx {
             picker: ColorPicker::default(),
 
             palettes: Palettes::default(),
+            pick_history: PickHistory::default(),
             palettes_tab_color_size: 50.,
             palettes_tab_display_label: false,
+            palettes_group_threshold: DEFAULT_GROUP_THRESHOLD,
+            gradient: ColorGradient::default(),
 
             screen_size: ScreenSize::Desktop(0., 0.),
             cursor_pick_color: Color::black(),
             central_panel_tab: CentralPanelTab::Picker,
 
             show_zoom_window: false,
+
+            history: UndoHistory::default(),
+            display_profile: None,
         }
     }
 }
@@ -58,27 +77,41 @@ impl AppCtx {
             picker: ColorPicker::default(),
 
             palettes: Palettes::default(),
+            pick_history: PickHistory::default(),
             palettes_tab_color_size: 50.,
             palettes_tab_display_label: false,
+            palettes_group_threshold: DEFAULT_GROUP_THRESHOLD,
+            gradient: ColorGradient::default(),
 
             screen_size: ScreenSize::Desktop(0., 0.),
             cursor_pick_color: Color::black(),
             central_panel_tab: CentralPanelTab::Picker,
 
             show_zoom_window: false,
+
+            history: UndoHistory::default(),
+            display_profile: None,
         }
     }
 
     /// Current color display format
     pub fn display_format(&self) -> ColorFormat<'_> {
-        match self.settings.color_display_format {
+        self.color_format(&self.settings.color_display_format)
+    }
+
+    /// Color format corresponding to the format selection, falls back to default display format
+    /// if a custom format doesn't exist
+    pub fn color_format(&self, format: &ColorDisplayFmtEnum) -> ColorFormat<'_> {
+        match format {
             ColorDisplayFmtEnum::Hex => ColorFormat::Hex,
             ColorDisplayFmtEnum::HexUppercase => ColorFormat::HexUpercase,
             ColorDisplayFmtEnum::CssRgb => ColorFormat::CssRgb,
             ColorDisplayFmtEnum::CssHsl => ColorFormat::CssHsl {
                 degree_symbol: true,
             },
-            ColorDisplayFmtEnum::Custom(ref name) => {
+            ColorDisplayFmtEnum::CssDisplayP3 => ColorFormat::CssDisplayP3,
+            ColorDisplayFmtEnum::CssHwb => ColorFormat::CssHwb,
+            ColorDisplayFmtEnum::Custom(name) => {
                 if self.settings.saved_color_formats.contains_key(name) {
                     ColorFormat::Custom(&self.settings.saved_color_formats[name])
                 } else {
@@ -91,8 +124,13 @@ impl AppCtx {
 
     /// Format a color as a string using display color format from settings
     pub fn display_color(&self, color: &Color) -> String {
+        self.format_color(color, &self.settings.color_display_format)
+    }
+
+    /// Format a color as a string using the provided format selection
+    pub fn format_color(&self, color: &Color, format: &ColorDisplayFmtEnum) -> String {
         color.display(
-            self.display_format(),
+            self.color_format(format),
             self.settings.rgb_working_space,
             self.settings.illuminant,
         )
@@ -112,6 +150,8 @@ impl AppCtx {
             ColorDisplayFmtEnum::CssHsl => ColorFormat::CssHsl {
                 degree_symbol: false,
             },
+            ColorDisplayFmtEnum::CssDisplayP3 => ColorFormat::CssDisplayP3,
+            ColorDisplayFmtEnum::CssHwb => ColorFormat::CssHwb,
             ColorDisplayFmtEnum::Custom(name) => {
                 if self.settings.saved_color_formats.contains_key(name) {
                     ColorFormat::Custom(&self.settings.saved_color_formats[name])
@@ -128,6 +168,37 @@ impl AppCtx {
         )
     }
 
+    /// Records palettes and current color in the undo history if they changed since the last
+    /// recorded state
+    pub fn record_history(&mut self) {
+        self.history.record(
+            &self.palettes,
+            self.picker.current_color,
+            self.settings.undo_history_limit,
+        );
+    }
+
+    pub fn undo(&mut self) {
+        if let Some(snapshot) = self.history.undo() {
+            let snapshot = snapshot.clone();
+            self.restore(snapshot);
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(snapshot) = self.history.redo() {
+            let snapshot = snapshot.clone();
+            self.restore(snapshot);
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.palettes = snapshot.palettes;
+        if self.picker.current_color.color32() != snapshot.current_color.color32() {
+            self.picker.set_cur_color(snapshot.current_color);
+        }
+    }
+
     /// Load palettes from appropriate location based on the target arch
     pub fn load_palettes(&mut self, _storage: Option<&dyn Storage>) {
         if self.settings.cache_colors
@@ -152,11 +223,87 @@ impl AppCtx {
         }
     }
 
+    /// Load the display profile selected in the settings
+    pub fn load_display_profile(&mut self) {
+        if let Some(path) = &self.settings.display_profile {
+            match IccProfile::load(path) {
+                Ok(profile) => self.display_profile = Some(profile),
+                Err(e) => append_global_error(format!("failed to load display profile, {e:?}")),
+            }
+        }
+    }
+
+    /// Converts a color picked from the screen from the display profile to the working space if
+    /// enabled in the settings.
+    pub fn screen_color(&self, color: Color) -> Color {
+        match &self.display_profile {
+            Some(profile) if self.settings.use_display_profile => Color::Rgb(profile.convert(
+                color.rgb(),
+                self.settings.rgb_working_space,
+                self.settings.gamut_mapping,
+            )),
+            _ => color,
+        }
+    }
+
+    /// Load picked colors history stored next to the palettes
+    pub fn load_pick_history(&mut self) {
+        if self.settings.cache_colors
+            && let Some(path) = Palettes::dir("epick")
+        {
+            let path = path.join(PickHistory::FILE_NAME);
+            if !path.exists() {
+                return;
+            }
+            match PickHistory::load(path) {
+                Ok(history) => self.pick_history = history,
+                Err(e) => append_global_error(format!("failed to load pick history, {e:?}")),
+            }
+        }
+    }
+
+    /// Save picked colors history next to the palettes
+    pub fn save_pick_history(&self) {
+        if let Some(dir) = Palettes::dir("epick") {
+            if !dir.exists() {
+                let _ = std::fs::create_dir_all(&dir);
+            }
+            if let Err(e) = self.pick_history.save(dir.join(PickHistory::FILE_NAME)) {
+                append_global_error(format!("failed to save pick history, {e:?}"));
+            }
+        }
+    }
+
+    /// Sets the current color and remembers it in the pick history
+    pub fn pick_color(&mut self, color: Color) {
+        self.picker.set_cur_color(color);
+        self.pick_history.push(
+            color,
+            crate::get_timestamp(),
+            self.settings.pick_history_limit,
+        );
+    }
+
     /// Adds a color to the currently selected palette
     pub fn add_color(&mut self, color: Color) {
-        if !self.palettes.current_mut().palette.add(color) {
-            let color_str = self.display_color(&color);
-            append_global_error(format!("Color {color_str} already saved!"));
+        let palette = &self.palettes.current().palette;
+        match palette.find_similar(&color, self.settings.duplicate_tolerance) {
+            Some(i) => {
+                let saved = palette.0[i].color;
+                let color_str = self.display_color(&color);
+                if saved.as_rgb_triplet_scaled() == color.as_rgb_triplet_scaled() {
+                    append_global_error(format!("Color {color_str} already saved!"));
+                } else {
+                    let saved_str = self.display_color(&saved);
+                    append_global_error(format!(
+                        "Color {color_str} is too similar to saved color {saved_str} (ΔE {:.2})",
+                        color.delta_e(&saved)
+                    ));
+                }
+            }
+            None => {
+                self.palettes.current_mut().palette.add(color);
+            }
         }
     }
 
@@ -171,6 +318,9 @@ impl AppCtx {
             self.picker.sliders.chromatic_adaptation_method =
                 self.settings.chromatic_adaptation_method;
         }
+        if self.settings.gamut_mapping != self.picker.sliders.gamut_mapping {
+            self.picker.sliders.gamut_mapping = self.settings.gamut_mapping;
+        }
         if self.settings.rgb_working_space != self.picker.sliders.rgb_working_space {
             self.picker.new_workspace = Some(self.settings.rgb_working_space);
             if self.settings.illuminant != self.picker.sliders.illuminant {
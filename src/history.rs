@@ -0,0 +1,193 @@
+use crate::color::{Color, Palettes};
+
+use std::collections::VecDeque;
+
+pub const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+/// State of the application that can be undone.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    pub palettes: Palettes,
+    pub current_color: Color,
+}
+
+impl Snapshot {
+    fn color_changed(&self, other: &Color) -> bool {
+        self.current_color.color32() != other.color32()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// State before the change was made
+    pub snapshot: Snapshot,
+    pub description: String,
+}
+
+/// Undo and redo stacks of palettes and picker state.
+///
+/// Changes are detected by comparing the state with the last recorded snapshot so that all
+/// kinds of edits are undoable without every edit site having to record them explicitly.
+#[derive(Clone, Debug, Default)]
+pub struct UndoHistory {
+    undo: VecDeque<HistoryEntry>,
+    redo: Vec<HistoryEntry>,
+    current: Option<Snapshot>,
+}
+
+impl UndoHistory {
+    /// Records a change if the state differs from the last recorded one. Returns `true` if
+    /// a new history entry was created.
+    pub fn record(&mut self, palettes: &Palettes, current_color: Color, limit: usize) -> bool {
+        let Some(current) = &self.current else {
+            self.current = Some(Snapshot {
+                palettes: palettes.clone(),
+                current_color,
+            });
+            return false;
+        };
+        if &current.palettes == palettes && !current.color_changed(&current_color) {
+            return false;
+        }
+        let description = describe(current, palettes, current_color);
+        let new = Snapshot {
+            palettes: palettes.clone(),
+            current_color,
+        };
+        let snapshot = self.current.replace(new).unwrap();
+        self.undo.push_back(HistoryEntry {
+            snapshot,
+            description,
+        });
+        self.redo.clear();
+        self.truncate(limit);
+        true
+    }
+
+    /// Returns the state to restore, if there is anything to undo.
+    pub fn undo(&mut self) -> Option<&Snapshot> {
+        let entry = self.undo.pop_back()?;
+        let current = self.current.replace(entry.snapshot)?;
+        self.redo.push(HistoryEntry {
+            snapshot: current,
+            description: entry.description,
+        });
+        self.current.as_ref()
+    }
+
+    /// Returns the state to restore, if there is anything to redo.
+    pub fn redo(&mut self) -> Option<&Snapshot> {
+        let entry = self.redo.pop()?;
+        let current = self.current.replace(entry.snapshot)?;
+        self.undo.push_back(HistoryEntry {
+            snapshot: current,
+            description: entry.description,
+        });
+        self.current.as_ref()
+    }
+
+    /// Undoable changes, the oldest first.
+    pub fn undo_entries(&self) -> impl DoubleEndedIterator<Item = &HistoryEntry> {
+        self.undo.iter()
+    }
+
+    /// Redoable changes, the most recently undone first.
+    pub fn redo_entries(&self) -> impl DoubleEndedIterator<Item = &HistoryEntry> {
+        self.redo.iter().rev()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    pub fn truncate(&mut self, limit: usize) {
+        while self.undo.len() > limit {
+            self.undo.pop_front();
+        }
+        if self.redo.len() > limit {
+            self.redo.drain(..self.redo.len() - limit);
+        }
+    }
+}
+
+fn color_count(palettes: &Palettes) -> usize {
+    palettes.iter().map(|p| p.palette.0.len()).sum()
+}
+
+fn describe(old: &Snapshot, palettes: &Palettes, color: Color) -> String {
+    if &old.palettes == palettes {
+        return format!("set color {}", color.as_hex());
+    }
+    let (old_len, new_len) = (old.palettes.len(), palettes.len());
+    if new_len > old_len {
+        return "add palette".into();
+    } else if new_len < old_len {
+        return "delete palette".into();
+    }
+    let (old_count, new_count) = (color_count(&old.palettes), color_count(palettes));
+    if new_count > old_count {
+        "add color".into()
+    } else if new_count < old_count {
+        "delete color".into()
+    } else {
+        "edit palette".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{NamedPalette, Rgb};
+
+    fn color(v: u8) -> Color {
+        Rgb::new_scaled(v, 0, 0).into()
+    }
+
+    #[test]
+    fn undoes_and_redoes() {
+        let mut palettes = Palettes::new(NamedPalette::default());
+        let mut history = UndoHistory::default();
+        assert!(!history.record(&palettes, color(0), 10));
+
+        palettes.current_mut().palette.add(color(1));
+        assert!(history.record(&palettes, color(0), 10));
+        assert!(!history.record(&palettes, color(0), 10));
+        assert!(history.record(&palettes, color(2), 10));
+        let descriptions: Vec<_> = history.undo_entries().map(|e| &e.description).collect();
+        assert_eq!(descriptions, ["add color", "set color #020000"]);
+
+        let restored = history.undo().unwrap();
+        assert_eq!(restored.current_color, color(0));
+        assert_eq!(restored.palettes, palettes);
+        let restored = history.undo().unwrap().clone();
+        assert_eq!(restored.palettes.current().palette.0.len(), 0);
+        assert!(history.undo().is_none());
+        assert_eq!(history.redo_entries().count(), 2);
+
+        let restored = history.redo().unwrap().clone();
+        assert_eq!(restored.palettes, palettes);
+        // recording a new change drops the redo stack
+        assert!(history.record(&restored.palettes, color(3), 10));
+        assert!(history.redo().is_none());
+    }
+
+    #[test]
+    fn limits_history() {
+        let palettes = Palettes::default();
+        let mut history = UndoHistory::default();
+        for i in 0..10 {
+            history.record(&palettes, color(i), 3);
+        }
+        assert_eq!(history.undo_entries().count(), 3);
+        assert_eq!(
+            history
+                .undo_entries()
+                .next()
+                .unwrap()
+                .snapshot
+                .current_color,
+            color(6)
+        );
+    }
+}
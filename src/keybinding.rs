@@ -7,6 +7,7 @@ pub type KeyBindingFunc = Box<dyn Fn(&mut FrameCtx<'_>) + Send + Sync + 'static>
 pub struct KeyBinding {
     description: &'static str,
     str_key: &'static str,
+    modifiers: egui::Modifiers,
     key: egui::Key,
     binding: KeyBindingFunc,
 }
@@ -20,6 +21,10 @@ impl KeyBinding {
         self.description
     }
 
+    pub fn modifiers(&self) -> egui::Modifiers {
+        self.modifiers
+    }
+
     pub fn key(&self) -> egui::Key {
         self.key
     }
@@ -29,7 +34,7 @@ impl KeyBinding {
     }
 }
 
-pub struct KeyBindings(HashMap<egui::Key, KeyBinding>);
+pub struct KeyBindings(HashMap<(egui::Modifiers, egui::Key), KeyBinding>);
 impl KeyBindings {
     pub fn iter(&self) -> impl Iterator<Item = &KeyBinding> {
         self.0.values()
@@ -40,13 +45,14 @@ pub fn default_keybindings() -> KeyBindings {
     KeyBindings(
         [
             (
-                egui::Key::P,
+                (egui::Modifiers::NONE, egui::Key::P),
                 KeyBinding {
                     description: "pick a color from under the cursor",
                     str_key: "p",
+                    modifiers: egui::Modifiers::NONE,
                     key: egui::Key::P,
                     binding: Box::new(|ctx| {
-                        ctx.app.picker.set_cur_color(ctx.app.cursor_pick_color);
+                        ctx.app.pick_color(ctx.app.cursor_pick_color);
                         if ctx.app.settings.auto_copy_picked_color {
                             let color = ctx.app.cursor_pick_color;
                             ctx.egui.copy_text(ctx.app.clipboard_color(&color));
@@ -55,10 +61,11 @@ pub fn default_keybindings() -> KeyBindings {
                 },
             ),
             (
-                egui::Key::S,
+                (egui::Modifiers::NONE, egui::Key::S),
                 KeyBinding {
                     description: "save a color from under the cursor",
                     str_key: "s",
+                    modifiers: egui::Modifiers::NONE,
                     key: egui::Key::S,
                     binding: Box::new(|ctx| {
                         ctx.app
@@ -69,6 +76,29 @@ pub fn default_keybindings() -> KeyBindings {
                     }),
                 },
             ),
+            (
+                (egui::Modifiers::COMMAND, egui::Key::Z),
+                KeyBinding {
+                    description: "undo the last palette or color change",
+                    str_key: "ctrl+z",
+                    modifiers: egui::Modifiers::COMMAND,
+                    key: egui::Key::Z,
+                    binding: Box::new(|ctx| ctx.app.undo()),
+                },
+            ),
+            (
+                (
+                    egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                    egui::Key::Z,
+                ),
+                KeyBinding {
+                    description: "redo the last undone change",
+                    str_key: "ctrl+shift+z",
+                    modifiers: egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                    key: egui::Key::Z,
+                    binding: Box::new(|ctx| ctx.app.redo()),
+                },
+            ),
         ]
         .into(),
     )
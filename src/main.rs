@@ -4,11 +4,13 @@ mod color_picker;
 mod context;
 mod display_picker;
 mod error;
+mod history;
 mod keybinding;
 mod math;
 mod render;
 mod screen_size;
 mod settings;
+mod swatch;
 mod ui;
 mod zoom_picker;
 
@@ -1,8 +1,11 @@
 use crate::{
     color::{
-        ChromaticAdaptationMethod, ColorFormat, ColorHarmony, CustomPaletteFormat, Illuminant,
-        PaletteFormat, RgbWorkingSpace,
+        ChromaticAdaptationMethod, ColorFormat, ColorHarmony, CustomPaletteFormat,
+        CustomWorkingSpace, DEFAULT_ANALOGOUS_SPREAD, DEFAULT_CUSTOM_HARMONY_ANGLES,
+        DEFAULT_PICK_HISTORY_LIMIT, GamutMapping, HarmonySpace, Illuminant, PaletteFormat,
+        RgbWorkingSpace, WhitePoint,
     },
+    history::DEFAULT_HISTORY_LIMIT,
     ui::layout::HarmonyLayout,
 };
 
@@ -59,6 +62,30 @@ fn is_default_harmony(it: &ColorHarmony) -> bool {
     *it == ColorHarmony::default()
 }
 
+fn is_default_gamut_mapping(it: &GamutMapping) -> bool {
+    *it == GamutMapping::default()
+}
+
+fn is_default_harmony_space(it: &HarmonySpace) -> bool {
+    *it == HarmonySpace::default()
+}
+
+fn default_analogous_spread() -> f32 {
+    DEFAULT_ANALOGOUS_SPREAD
+}
+
+fn is_default_analogous_spread(it: &f32) -> bool {
+    *it == DEFAULT_ANALOGOUS_SPREAD
+}
+
+fn default_custom_harmony_angles() -> Vec<f32> {
+    DEFAULT_CUSTOM_HARMONY_ANGLES.to_vec()
+}
+
+fn is_default_custom_harmony_angles(it: &[f32]) -> bool {
+    it == DEFAULT_CUSTOM_HARMONY_ANGLES
+}
+
 fn is_default_color_size(it: &f32) -> bool {
     *it == DEFAULT_COLOR_SIZE
 }
@@ -89,12 +116,52 @@ pub struct Settings {
     pub chromatic_adaptation_method: ChromaticAdaptationMethod,
     #[serde(default)]
     pub illuminant: Illuminant,
+    /// How colors set outside of the working space gamut are brought into it
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default_gamut_mapping")]
+    pub gamut_mapping: GamutMapping,
+    /// Working spaces defined by the user, selectable by their name
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub custom_working_spaces: HashMap<String, CustomWorkingSpace>,
+    /// White points defined by the user, selectable by their name
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub custom_illuminants: HashMap<String, WhitePoint>,
+    /// Name of the selected custom working space, `rgb_working_space` holds its definition
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_working_space: Option<String>,
+    /// Name of the selected custom white point, `illuminant` holds its definition
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_illuminant: Option<String>,
+    /// Path of the ICC profile of the display colors are picked from
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_profile: Option<String>,
+    /// Whether picked screen colors are converted from the display profile
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    pub use_display_profile: bool,
     #[serde(default = "enabled")]
     #[serde(skip_serializing_if = "is_true")]
     pub cache_colors: bool,
     #[serde(default)]
     #[serde(skip_serializing_if = "is_default_harmony")]
     pub harmony: ColorHarmony,
+    /// Color space in which the hue of harmonies is rotated
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default_harmony_space")]
+    pub harmony_space: HarmonySpace,
+    /// Hue distance in degrees of the analogous harmony colors
+    #[serde(default = "default_analogous_spread")]
+    #[serde(skip_serializing_if = "is_default_analogous_spread")]
+    pub harmony_analogous_spread: f32,
+    /// Hue rotations in degrees of the custom harmony
+    #[serde(default = "default_custom_harmony_angles")]
+    #[serde(skip_serializing_if = "is_default_custom_harmony_angles")]
+    pub harmony_custom_angles: Vec<f32>,
     #[serde(default = "enabled")]
     #[serde(skip_serializing_if = "is_true")]
     pub is_dark_mode: bool,
@@ -117,6 +184,19 @@ pub struct Settings {
     #[serde(default = "default_pixels_per_point")]
     #[serde(skip_serializing_if = "is_default_pixels_per_point")]
     pub pixels_per_point: f32,
+    /// Maximum number of changes that can be undone
+    #[serde(default = "default_undo_history_limit")]
+    #[serde(skip_serializing_if = "is_default_undo_history_limit")]
+    pub undo_history_limit: usize,
+    /// Maximum number of remembered picked colors
+    #[serde(default = "default_pick_history_limit")]
+    #[serde(skip_serializing_if = "is_default_pick_history_limit")]
+    pub pick_history_limit: usize,
+    /// Colors closer than this CIEDE2000 difference to a saved color are not added to a palette.
+    /// `0` only rejects exact duplicates.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_exact_duplicate_tolerance")]
+    pub duplicate_tolerance: f32,
 }
 
 fn default_pixels_per_point() -> f32 {
@@ -127,6 +207,26 @@ fn is_default_pixels_per_point(ppp: &f32) -> bool {
     *ppp == DEFAULT_PIXELS_PER_POINT
 }
 
+fn default_undo_history_limit() -> usize {
+    DEFAULT_HISTORY_LIMIT
+}
+
+fn is_default_undo_history_limit(limit: &usize) -> bool {
+    *limit == DEFAULT_HISTORY_LIMIT
+}
+
+fn default_pick_history_limit() -> usize {
+    DEFAULT_PICK_HISTORY_LIMIT
+}
+
+fn is_default_pick_history_limit(limit: &usize) -> bool {
+    *limit == DEFAULT_PICK_HISTORY_LIMIT
+}
+
+fn is_exact_duplicate_tolerance(tolerance: &f32) -> bool {
+    *tolerance == 0.
+}
+
 impl Default for Settings {
     fn default() -> Self {
         let ws = RgbWorkingSpace::default();
@@ -139,15 +239,28 @@ impl Default for Settings {
             rgb_working_space: ws,
             chromatic_adaptation_method: ChromaticAdaptationMethod::default(),
             illuminant: ws.reference_illuminant(),
+            gamut_mapping: GamutMapping::default(),
+            custom_working_spaces: HashMap::default(),
+            custom_illuminants: HashMap::default(),
+            custom_working_space: None,
+            custom_illuminant: None,
+            display_profile: None,
+            use_display_profile: false,
             cache_colors: true,
             is_dark_mode: true,
             harmony: ColorHarmony::default(),
+            harmony_space: HarmonySpace::default(),
+            harmony_analogous_spread: DEFAULT_ANALOGOUS_SPREAD,
+            harmony_custom_angles: DEFAULT_CUSTOM_HARMONY_ANGLES.to_vec(),
             harmony_layout: HarmonyLayout::default(),
             harmony_color_size: DEFAULT_COLOR_SIZE,
             harmony_display_color_label: false,
             harmony_display_box: true,
             auto_copy_picked_color: false,
             pixels_per_point: DEFAULT_PIXELS_PER_POINT,
+            undo_history_limit: DEFAULT_HISTORY_LIMIT,
+            pick_history_limit: DEFAULT_PICK_HISTORY_LIMIT,
+            duplicate_tolerance: 0.,
         }
     }
 }
@@ -159,7 +272,10 @@ impl Settings {
     /// expected to be a valid ron file.
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
         let data = fs::read_to_string(path).context("failed to read configuration file")?;
-        ron::from_str(&data).context("Failed to parse configuration file")
+        let mut settings: Self =
+            ron::from_str(&data).context("Failed to parse configuration file")?;
+        settings.sync_custom_spaces();
+        Ok(settings)
     }
 
     /// Saves this settings as ron file in the provided `path`.
@@ -169,6 +285,78 @@ impl Settings {
         fs::write(path, &data).context("failed to write settings to file")
     }
 
+    /// Returns the name of the selected working space.
+    pub fn working_space_name(&self) -> &str {
+        match (&self.rgb_working_space, &self.custom_working_space) {
+            (RgbWorkingSpace::Custom(_), Some(name)) => name,
+            (ws, _) => ws.as_ref(),
+        }
+    }
+
+    /// Returns the name of the selected illuminant.
+    pub fn illuminant_name(&self) -> &str {
+        match (&self.illuminant, &self.custom_illuminant) {
+            (Illuminant::Custom(_), Some(name)) => name,
+            (illuminant, _) => illuminant.as_ref(),
+        }
+    }
+
+    /// Selects the custom working space called `name`.
+    pub fn select_custom_working_space(&mut self, name: &str) {
+        if let Some(ws) = self.custom_working_spaces.get(name) {
+            self.rgb_working_space = RgbWorkingSpace::Custom(*ws);
+            self.custom_working_space = Some(name.to_string());
+        }
+    }
+
+    /// Selects the custom white point called `name`.
+    pub fn select_custom_illuminant(&mut self, name: &str) {
+        if let Some(white) = self.custom_illuminants.get(name) {
+            self.illuminant = Illuminant::Custom(*white);
+            self.custom_illuminant = Some(name.to_string());
+        }
+    }
+
+    /// Updates the selected custom working space and white point from their current definitions.
+    /// Selections of removed or invalid definitions fall back to the defaults.
+    pub fn sync_custom_spaces(&mut self) {
+        let ws = match (&self.rgb_working_space, &self.custom_working_space) {
+            (RgbWorkingSpace::Custom(_), Some(name)) => self
+                .custom_working_spaces
+                .get(name)
+                .filter(|ws| ws.is_valid())
+                .map(|ws| RgbWorkingSpace::Custom(*ws)),
+            (RgbWorkingSpace::Custom(_), None) => None,
+            (ws, _) => Some(*ws),
+        };
+        match ws {
+            Some(ws @ RgbWorkingSpace::Custom(_)) => self.rgb_working_space = ws,
+            Some(_) => self.custom_working_space = None,
+            None => {
+                self.rgb_working_space = RgbWorkingSpace::default();
+                self.custom_working_space = None;
+            }
+        }
+
+        let illuminant = match (&self.illuminant, &self.custom_illuminant) {
+            (Illuminant::Custom(_), Some(name)) => self
+                .custom_illuminants
+                .get(name)
+                .filter(|white| white.is_valid())
+                .map(|white| Illuminant::Custom(*white)),
+            (Illuminant::Custom(_), None) => None,
+            (illuminant, _) => Some(*illuminant),
+        };
+        match illuminant {
+            Some(illuminant @ Illuminant::Custom(_)) => self.illuminant = illuminant,
+            Some(_) => self.custom_illuminant = None,
+            None => {
+                self.illuminant = self.rgb_working_space.reference_illuminant();
+                self.custom_illuminant = None;
+            }
+        }
+    }
+
     /// Returns system directory where configuration should be placed joined by the `name` parameter.
     pub fn dir(name: impl AsRef<str>) -> Option<PathBuf> {
         let name = name.as_ref();
@@ -195,6 +383,10 @@ pub enum ColorDisplayFmtEnum {
     CssRgb,
     #[serde(rename = "css-hsl")]
     CssHsl,
+    #[serde(rename = "css-display-p3")]
+    CssDisplayP3,
+    #[serde(rename = "css-hwb")]
+    CssHwb,
     #[serde(rename = "custom")]
     Custom(String),
 }
@@ -207,6 +399,8 @@ impl AsRef<str> for ColorDisplayFmtEnum {
             HexUppercase => "hex uppercase",
             CssRgb => "css rgb",
             CssHsl => "css hsl",
+            CssDisplayP3 => "css display-p3",
+            CssHwb => "css hwb",
             Custom(name) => name,
         }
     }
@@ -221,7 +415,10 @@ impl ColorDisplayFmtEnum {
 #[cfg(test)]
 mod tests {
     use crate::{
-        color::{ChromaticAdaptationMethod, ColorHarmony, Illuminant, RgbWorkingSpace},
+        color::{
+            ChromaticAdaptationMethod, ColorHarmony, CustomWorkingSpace, Illuminant,
+            RgbWorkingSpace, TransferFunction, WhitePoint,
+        },
         math::eq_f32,
         settings::{DEFAULT_COLOR_SIZE, Settings},
         ui::layout::HarmonyLayout,
@@ -262,4 +459,41 @@ mod tests {
 
         pretty_assertions::assert_eq!(fs::read_to_string(&path).unwrap(), settings_str);
     }
+
+    #[test]
+    fn saves_custom_spaces() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut settings = Settings::default();
+        let ws = CustomWorkingSpace {
+            red: (0.68, 0.32),
+            transfer: TransferFunction::Gamma(2.2),
+            ..Default::default()
+        };
+        let white = WhitePoint::from_cct(5500.);
+        // Identical definitions are told apart by their name
+        settings.custom_working_spaces.insert("Copy".into(), ws);
+        settings
+            .custom_working_spaces
+            .insert("Studio monitor".into(), ws);
+        settings.custom_illuminants.insert("Warm".into(), white);
+        settings.select_custom_working_space("Studio monitor");
+        settings.select_custom_illuminant("Warm");
+
+        let path = tmp.path().join("settings.ron");
+        settings.save(&path).unwrap();
+        let mut loaded = Settings::load(&path).unwrap();
+
+        assert_eq!(loaded.rgb_working_space, RgbWorkingSpace::Custom(ws));
+        assert_eq!(loaded.illuminant, Illuminant::Custom(white));
+        assert_eq!(loaded.working_space_name(), "Studio monitor");
+        assert_eq!(loaded.illuminant_name(), "Warm");
+
+        loaded.custom_working_spaces.remove("Studio monitor");
+        loaded.custom_illuminants.remove("Warm");
+        loaded.sync_custom_spaces();
+        assert_eq!(loaded.rgb_working_space, RgbWorkingSpace::SRGB);
+        assert_eq!(loaded.illuminant, Illuminant::D65);
+        assert_eq!(loaded.working_space_name(), RgbWorkingSpace::SRGB.as_ref());
+        assert_eq!(loaded.illuminant_name(), "D65");
+    }
 }
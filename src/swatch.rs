@@ -0,0 +1,381 @@
+//! Rendering of palettes to swatch sheet images.
+
+use crate::color::{Color, NamedPalette};
+
+use ab_glyph::{Font, FontRef, PxScale, ScaleFont, point};
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+
+pub const FONT: &[u8] = include_bytes!("../assets/fonts/FiraCode/FiraCode-Regular.ttf");
+
+const PADDING: u32 = 8;
+const FONT_SIZE: f32 = 12.;
+const LINE_HEIGHT: u32 = 16;
+/// Fira Code is monospaced with every glyph advancing by 0.6em.
+const CHAR_WIDTH: f32 = FONT_SIZE * 0.6;
+const BACKGROUND: (u8, u8, u8) = (255, 255, 255);
+const TEXT: (u8, u8, u8) = (32, 32, 32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwatchFormat {
+    Png,
+    Svg,
+}
+
+impl AsRef<str> for SwatchFormat {
+    fn as_ref(&self) -> &str {
+        match self {
+            SwatchFormat::Png => "PNG swatches",
+            SwatchFormat::Svg => "SVG swatches",
+        }
+    }
+}
+
+impl SwatchFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SwatchFormat::Png => "png",
+            SwatchFormat::Svg => "svg",
+        }
+    }
+}
+
+/// Layout options of a swatch sheet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwatchSheet {
+    pub swatch_size: u32,
+    pub columns: u32,
+    /// Display each color formatted as text below the swatch
+    pub labels: bool,
+    /// Display names of palette entries below the swatch
+    pub names: bool,
+}
+
+impl Default for SwatchSheet {
+    fn default() -> Self {
+        Self {
+            swatch_size: 64,
+            columns: 8,
+            labels: true,
+            names: true,
+        }
+    }
+}
+
+struct Swatch {
+    color: (u8, u8, u8),
+    lines: Vec<String>,
+}
+
+struct Layout {
+    swatches: Vec<Swatch>,
+    swatch_size: u32,
+    columns: u32,
+    cell_width: u32,
+    cell_height: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Layout {
+    fn cell_origin(&self, i: usize) -> (u32, u32) {
+        let i = i as u32;
+        (
+            PADDING + (i % self.columns) * (self.cell_width + PADDING),
+            PADDING + (i / self.columns) * (self.cell_height + PADDING),
+        )
+    }
+}
+
+impl SwatchSheet {
+    /// Renders the palette in the provided format. Colors are labeled with the text returned by
+    /// the `label` function.
+    pub fn render(
+        &self,
+        palette: &NamedPalette,
+        format: SwatchFormat,
+        label: impl Fn(&Color) -> String,
+    ) -> Result<Vec<u8>> {
+        let layout = self.layout(palette, label);
+        match format {
+            SwatchFormat::Png => render_png(&layout),
+            SwatchFormat::Svg => Ok(render_svg(&layout).into_bytes()),
+        }
+    }
+
+    fn layout(&self, palette: &NamedPalette, label: impl Fn(&Color) -> String) -> Layout {
+        let swatches: Vec<_> = palette
+            .palette
+            .entries()
+            .map(|entry| {
+                let mut lines = vec![];
+                if self.names
+                    && let Some(name) = entry.name()
+                {
+                    lines.push(name.to_string());
+                }
+                if self.labels {
+                    lines.push(label(&entry.color));
+                }
+                Swatch {
+                    color: entry.color.as_rgb_triplet_scaled(),
+                    lines,
+                }
+            })
+            .collect();
+
+        let swatch_size = self.swatch_size.max(1);
+        let columns = self.columns.clamp(1, swatches.len().max(1) as u32);
+        let rows = swatches.len().div_ceil(columns as usize).max(1) as u32;
+        let max_chars = swatches
+            .iter()
+            .flat_map(|s| s.lines.iter().map(|line| line.chars().count()))
+            .max()
+            .unwrap_or_default();
+        let max_lines = swatches
+            .iter()
+            .map(|s| s.lines.len())
+            .max()
+            .unwrap_or_default() as u32;
+        let cell_width = swatch_size.max((max_chars as f32 * CHAR_WIDTH).ceil() as u32);
+        let text_height = if max_lines > 0 {
+            PADDING / 2 + max_lines * LINE_HEIGHT
+        } else {
+            0
+        };
+        let cell_height = swatch_size + text_height;
+
+        Layout {
+            swatches,
+            swatch_size,
+            columns,
+            cell_width,
+            cell_height,
+            width: PADDING + columns * (cell_width + PADDING),
+            height: PADDING + rows * (cell_height + PADDING),
+        }
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut s = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => s.push_str("&amp;"),
+            '<' => s.push_str("&lt;"),
+            '>' => s.push_str("&gt;"),
+            '"' => s.push_str("&quot;"),
+            ch => s.push(ch),
+        }
+    }
+    s
+}
+
+fn render_svg(layout: &Layout) -> String {
+    let (w, h) = (layout.width, layout.height);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n"
+    );
+    let (r, g, b) = BACKGROUND;
+    let _ = writeln!(
+        svg,
+        "<rect width=\"{w}\" height=\"{h}\" fill=\"#{r:02x}{g:02x}{b:02x}\"/>"
+    );
+    let (r, g, b) = TEXT;
+    let _ = writeln!(
+        svg,
+        "<g font-family=\"Fira Code, monospace\" font-size=\"{FONT_SIZE}\" fill=\"#{r:02x}{g:02x}{b:02x}\">"
+    );
+    for (i, swatch) in layout.swatches.iter().enumerate() {
+        let (x, y) = layout.cell_origin(i);
+        let (r, g, b) = swatch.color;
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{size}\" height=\"{size}\" fill=\"#{r:02x}{g:02x}{b:02x}\"/>",
+            size = layout.swatch_size,
+        );
+        for (line_idx, line) in swatch.lines.iter().enumerate() {
+            let _ = writeln!(
+                svg,
+                "<text x=\"{x}\" y=\"{}\">{}</text>",
+                text_baseline(layout, y, line_idx),
+                xml_escape(line)
+            );
+        }
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+fn text_baseline(layout: &Layout, cell_y: u32, line: usize) -> u32 {
+    cell_y + layout.swatch_size + PADDING / 2 + (line as u32 + 1) * LINE_HEIGHT - 4
+}
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, fill: (u8, u8, u8)) -> Self {
+        let mut pixels = Vec::with_capacity((width * height * 3) as usize);
+        for _ in 0..width * height {
+            pixels.extend_from_slice(&[fill.0, fill.1, fill.2]);
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn blend(&mut self, x: u32, y: u32, color: (u8, u8, u8), alpha: f32) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let alpha = alpha.clamp(0., 1.);
+        let idx = ((y * self.width + x) * 3) as usize;
+        for (channel, value) in [color.0, color.1, color.2].into_iter().enumerate() {
+            let bg = self.pixels[idx + channel] as f32;
+            self.pixels[idx + channel] = (bg + (value as f32 - bg) * alpha).round() as u8;
+        }
+    }
+
+    fn fill_rect(&mut self, x: u32, y: u32, w: u32, h: u32, color: (u8, u8, u8)) {
+        for py in y..(y + h).min(self.height) {
+            for px in x..(x + w).min(self.width) {
+                self.blend(px, py, color, 1.);
+            }
+        }
+    }
+
+    fn draw_text(&mut self, font: &FontRef, text: &str, x: u32, baseline: u32, max_x: u32) {
+        let scale = PxScale::from(FONT_SIZE);
+        let scaled = font.as_scaled(scale);
+        let mut caret = x as f32;
+        for ch in text.chars() {
+            let id = scaled.glyph_id(ch);
+            let glyph = id.with_scale_and_position(scale, point(caret, baseline as f32));
+            caret += scaled.h_advance(id);
+            if caret > max_x as f32 {
+                break;
+            }
+            if let Some(outlined) = font.outline_glyph(glyph) {
+                let bounds = outlined.px_bounds();
+                outlined.draw(|gx, gy, coverage| {
+                    let px = bounds.min.x as i32 + gx as i32;
+                    let py = bounds.min.y as i32 + gy as i32;
+                    if px >= 0 && py >= 0 {
+                        self.blend(px as u32, py as u32, TEXT, coverage);
+                    }
+                });
+            }
+        }
+    }
+}
+
+fn render_png(layout: &Layout) -> Result<Vec<u8>> {
+    let font = FontRef::try_from_slice(FONT).context("failed to load font")?;
+    let mut canvas = Canvas::new(layout.width, layout.height, BACKGROUND);
+    let size = layout.swatch_size;
+    for (i, swatch) in layout.swatches.iter().enumerate() {
+        let (x, y) = layout.cell_origin(i);
+        canvas.fill_rect(x, y, size, size, swatch.color);
+        for (line_idx, line) in swatch.lines.iter().enumerate() {
+            canvas.draw_text(
+                &font,
+                line,
+                x,
+                text_baseline(layout, y, line_idx),
+                x + layout.cell_width,
+            );
+        }
+    }
+
+    let mut data = vec![];
+    let mut encoder = png::Encoder::new(&mut data, canvas.width, canvas.height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder
+        .write_header()
+        .context("failed to write png header")?;
+    writer
+        .write_image_data(&canvas.pixels)
+        .context("failed to write png data")?;
+    writer.finish().context("failed to finish png")?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{Palette, PaletteEntry, Rgb};
+    use pretty_assertions::assert_eq;
+
+    fn test_palette() -> NamedPalette {
+        NamedPalette {
+            name: "test".into(),
+            palette: Palette::from_iter([
+                PaletteEntry::named(Rgb::new_scaled(255, 0, 0).into(), "a<b"),
+                PaletteEntry::new(Rgb::new_scaled(0, 0, 255).into()),
+            ]),
+        }
+    }
+
+    #[test]
+    fn renders_svg() {
+        let sheet = SwatchSheet {
+            swatch_size: 20,
+            columns: 1,
+            labels: false,
+            names: true,
+        };
+        let svg = sheet
+            .render(&test_palette(), SwatchFormat::Svg, |c| c.as_hex())
+            .unwrap();
+        let want = r##"<svg xmlns="http://www.w3.org/2000/svg" width="38" height="104" viewBox="0 0 38 104">
+<rect width="38" height="104" fill="#ffffff"/>
+<g font-family="Fira Code, monospace" font-size="12" fill="#202020">
+<rect x="8" y="8" width="20" height="20" fill="#ff0000"/>
+<text x="8" y="44">a&lt;b</text>
+<rect x="8" y="56" width="20" height="20" fill="#0000ff"/>
+</g>
+</svg>
+"##;
+        assert_eq!(String::from_utf8(svg).unwrap(), want);
+    }
+
+    #[test]
+    fn renders_png() {
+        let sheet = SwatchSheet {
+            swatch_size: 40,
+            columns: 4,
+            labels: true,
+            names: false,
+        };
+        let png = sheet
+            .render(&test_palette(), SwatchFormat::Png, |c| c.as_hex())
+            .unwrap();
+
+        let decoder = png::Decoder::new(std::io::Cursor::new(png));
+        let mut reader = decoder.read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut buf).unwrap();
+        // two cells wide enough for `#ff0000` labels, one row with a line of text
+        assert_eq!(
+            (info.width, info.height),
+            (8 + 2 * (51 + 8), 8 + 40 + 4 + 16 + 8)
+        );
+
+        let pixel = |x: u32, y: u32| {
+            let idx = ((y * info.width + x) * 3) as usize;
+            (buf[idx], buf[idx + 1], buf[idx + 2])
+        };
+        assert_eq!(pixel(0, 0), BACKGROUND);
+        assert_eq!(pixel(28, 28), (255, 0, 0));
+        assert_eq!(pixel(8 + 59 + 20, 28), (0, 0, 255));
+        let text_row = (8..59).any(|x| (48..68).any(|y| pixel(x, y) != BACKGROUND));
+        assert!(text_row, "label was not drawn");
+    }
+}
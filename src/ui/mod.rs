@@ -3,7 +3,7 @@ pub mod layout;
 pub mod slider_1d;
 pub mod slider_2d;
 
-use crate::color::{Color, ColorFormat, Illuminant, RgbWorkingSpace};
+use crate::color::{Color, ColorFormat, ColorName, Illuminant, RgbWorkingSpace};
 
 use egui::{
     InnerResponse, Rect, Sense, Shape, Stroke, Ui, Vec2, Visuals, ecolor,
@@ -30,6 +30,8 @@ pub mod icon {
     pub static HELP: &str = "\u{FF1F}";
     pub static EDIT: &str = "\u{270F}";
     pub static APPLY: &str = "\u{2714}";
+    pub static HISTORY: &str = "\u{21BA}";
+    pub static SORT: &str = "\u{2B0D}";
 }
 
 #[allow(dead_code)]
@@ -128,6 +130,15 @@ pub fn drop_target<R>(
     InnerResponse::new(ret, response)
 }
 
+/// Returns the nearest named color prefixed with `≈` unless it's an exact match.
+pub fn color_name_label(name: &ColorName) -> String {
+    if name.is_exact() {
+        name.name.to_string()
+    } else {
+        format!("≈ {}", name.name)
+    }
+}
+
 pub fn color_tooltip(
     color: &Color,
     display_format: ColorFormat,
@@ -136,8 +147,9 @@ pub fn color_tooltip(
     text: Option<&str>,
 ) -> String {
     format!(
-        "{}\n\n{}",
+        "{}\n{}\n\n{}",
         color.display(display_format, ws, illuminant),
+        color_name_label(&color.nearest_name()),
         text.unwrap_or_default()
     )
 }
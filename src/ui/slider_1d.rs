@@ -1,18 +1,31 @@
 use crate::color::Color;
 use eframe::egui::{Shape, Stroke, epaint::Mesh, lerp, remap_clamp};
-use egui::{Color32, CursorIcon, Response, Sense, Ui, pos2, vec2};
+use egui::{Color32, CursorIcon, Painter, Rect, Response, Sense, Ui, pos2, vec2};
 use std::ops::{Neg, RangeInclusive};
 
 /// Number of vertices per dimension in the color sliders.
 /// We need at least 6 for hues, and more for smooth 2D areas.
 /// Should always be a multiple of 6 to hit the peak hues in HSV/HSL (every 60°).
 pub const NUM_OF_VERTICES: u32 = 6 * 6;
+/// Distance between the lines hatching out of gamut regions.
+const HATCH_SPACING: f32 = 6.;
 
 pub fn color(
     ui: &mut Ui,
     value: &mut f32,
     range: RangeInclusive<f32>,
     color_at: impl Fn(f32) -> Color32,
+) -> Response {
+    color_in_gamut(ui, value, range, color_at, |_| true)
+}
+
+/// Same as [`color`] but hatches the parts of the slider where `in_gamut` returns false.
+pub fn color_in_gamut(
+    ui: &mut Ui,
+    value: &mut f32,
+    range: RangeInclusive<f32>,
+    color_at: impl Fn(f32) -> Color32,
+    in_gamut: impl Fn(f32) -> bool,
 ) -> Response {
     let width = ui.spacing().slider_width * 2.;
 
@@ -50,6 +63,18 @@ pub fn color(
             }
         }
         ui.painter().add(Shape::mesh(mesh));
+
+        for i in 0..NUM_OF_VERTICES {
+            let start = i as f32 / (NUM_OF_VERTICES as f32);
+            let end = (i + 1) as f32 / (NUM_OF_VERTICES as f32);
+            if !in_gamut(lerp(range_start..=_range_end, (start + end) / 2.)) {
+                let segment = Rect::from_x_y_ranges(
+                    lerp(rect.left()..=rect.right(), start)..=lerp(rect.left()..=rect.right(), end),
+                    rect.y_range(),
+                );
+                hatch(ui.painter(), segment);
+            }
+        }
     }
 
     ui.painter()
@@ -82,3 +107,22 @@ pub fn color(
 
     response
 }
+
+/// Draws diagonal lines over `rect`. The lines are aligned to the screen so that hatching of
+/// adjacent rectangles forms a continuous pattern.
+pub fn hatch(painter: &Painter, rect: Rect) {
+    let painter = painter.with_clip_rect(rect.intersect(painter.clip_rect()));
+    let stroke = Stroke::new(1., Color32::from_black_alpha(140));
+    let first = ((rect.left() + rect.top()) / HATCH_SPACING).floor() as i32;
+    let last = ((rect.right() + rect.bottom()) / HATCH_SPACING).ceil() as i32;
+    for k in first..=last {
+        let c = k as f32 * HATCH_SPACING;
+        painter.line_segment(
+            [
+                pos2(c - rect.bottom(), rect.bottom()),
+                pos2(c - rect.top(), rect.top()),
+            ],
+            stroke,
+        );
+    }
+}
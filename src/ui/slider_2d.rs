@@ -1,8 +1,8 @@
-use super::slider_1d::NUM_OF_VERTICES;
+use super::slider_1d::{NUM_OF_VERTICES, hatch};
 
 use crate::color::Color;
 use eframe::egui::{Sense, Shape, Stroke, Vec2, epaint::Mesh, lerp, remap_clamp};
-use egui::{Color32, CursorIcon, Response, Ui, epaint::CircleShape, pos2};
+use egui::{Color32, CursorIcon, Rect, Response, Ui, epaint::CircleShape, pos2};
 use std::ops::RangeInclusive;
 
 pub fn color(
@@ -12,6 +12,21 @@ pub fn color(
     x_range: RangeInclusive<f32>,
     y_range: RangeInclusive<f32>,
     color_at: impl Fn(f32, f32) -> Color32,
+) -> Response {
+    color_in_gamut(ui, x_value, y_value, x_range, y_range, color_at, |_, _| {
+        true
+    })
+}
+
+/// Same as [`color`] but hatches the parts of the area where `in_gamut` returns false.
+pub fn color_in_gamut(
+    ui: &mut Ui,
+    x_value: &mut f32,
+    y_value: &mut f32,
+    x_range: RangeInclusive<f32>,
+    y_range: RangeInclusive<f32>,
+    color_at: impl Fn(f32, f32) -> Color32,
+    in_gamut: impl Fn(f32, f32) -> bool,
 ) -> Response {
     let width = ui.spacing().slider_width * 2.;
     let desired_size = Vec2::new(width, width * 2. / 3.);
@@ -47,6 +62,28 @@ pub fn color(
     }
     ui.painter().add(Shape::mesh(mesh)); // fill
 
+    for xi in 0..NUM_OF_VERTICES {
+        for yi in 0..NUM_OF_VERTICES {
+            let xt = (xi as f32 + 0.5) / (NUM_OF_VERTICES as f32);
+            let yt = (yi as f32 + 0.5) / (NUM_OF_VERTICES as f32);
+            if in_gamut(lerp(x_range.clone(), xt), lerp(y_range.clone(), yt)) {
+                continue;
+            }
+            let step = 1. / (NUM_OF_VERTICES as f32);
+            let cell = Rect::from_two_pos(
+                pos2(
+                    lerp(rect.left()..=rect.right(), xt - step / 2.),
+                    lerp(rect.bottom()..=rect.top(), yt - step / 2.),
+                ),
+                pos2(
+                    lerp(rect.left()..=rect.right(), xt + step / 2.),
+                    lerp(rect.bottom()..=rect.top(), yt + step / 2.),
+                ),
+            );
+            hatch(ui.painter(), cell);
+        }
+    }
+
     ui.painter()
         .rect_stroke(rect, 0.0, visuals.bg_stroke, egui::StrokeKind::Outside); // outline
 
@@ -41,6 +41,7 @@ impl ZoomPicker {
         if let Some(picker) = self.display_picker.clone()
             && let Ok(color) = picker.get_color_under_cursor(self.offset)
         {
+            let color = ctx.app.screen_color(color);
             ctx.app.cursor_pick_color = color;
             ui.horizontal(|ui| {
                 let cb = ColorBox::builder()